@@ -3,12 +3,140 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::RuntimeDebug;
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
-#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Copy,
+	Clone,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
 pub struct MassbitId([u8; 36]);
 
 impl Default for MassbitId {
+	/// The nil UUID `00000000-0000-0000-0000-000000000000`.
 	fn default() -> Self {
-		MassbitId([1; 36])
+		MassbitId(*b"00000000-0000-0000-0000-000000000000")
+	}
+}
+
+/// Positions of the dashes in a UUID string.
+const UUID_DASHES: [usize; 4] = [8, 13, 18, 23];
+
+/// Error of a Massbit Id which isn't a UUID string.
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+pub struct InvalidUuid;
+
+/// Format check of an id submitted to the chain.
+pub trait ValidateId {
+	/// Whether the id is well formed.
+	fn is_valid_id(&self) -> bool;
+}
+
+fn is_uuid(id: &[u8; 36]) -> bool {
+	id.iter().enumerate().all(|(i, c)| {
+		if UUID_DASHES.contains(&i) {
+			*c == b'-'
+		} else {
+			c.is_ascii_hexdigit()
+		}
+	})
+}
+
+impl MassbitId {
+	/// Build a Massbit Id from a UUID string in the `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` format.
+	pub fn from_uuid_str(s: &str) -> Result<Self, InvalidUuid> {
+		Self::from_uuid_bytes(s.as_bytes())
+	}
+
+	/// Get the ASCII bytes of the UUID string of the Massbit Id.
+	pub fn to_uuid_str(&self) -> [u8; 36] {
+		self.0
+	}
+
+	fn from_uuid_bytes(bytes: &[u8]) -> Result<Self, InvalidUuid> {
+		let id: [u8; 36] = bytes.try_into().map_err(|_| InvalidUuid)?;
+		if is_uuid(&id) {
+			Ok(MassbitId(id))
+		} else {
+			Err(InvalidUuid)
+		}
+	}
+}
+
+impl ValidateId for MassbitId {
+	/// Massbit Ids decoded from SCALE aren't checked, so they may hold any 36 bytes.
+	fn is_valid_id(&self) -> bool {
+		is_uuid(&self.0)
+	}
+}
+
+impl TryFrom<Vec<u8>> for MassbitId {
+	type Error = InvalidUuid;
+
+	fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+		Self::from_uuid_bytes(&bytes)
+	}
+}
+
+impl TryFrom<&str> for MassbitId {
+	type Error = InvalidUuid;
+
+	fn try_from(s: &str) -> Result<Self, Self::Error> {
+		Self::from_uuid_str(s)
+	}
+}
+
+#[cfg(feature = "std")]
+impl serde::Serialize for MassbitId {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serde::Serialize::serialize(self.0.as_slice(), serializer)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> serde::Deserialize<'de> for MassbitId {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let bytes: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+		Self::from_uuid_bytes(&bytes)
+			.map_err(|_| serde::de::Error::custom("Massbit Id should be a UUID string"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn uuid_str_is_validated() {
+		let uuid = "4f5a1c2e-9b3d-4e7f-a1b2-c3d4e5f60718";
+		let id = MassbitId::from_uuid_str(uuid).unwrap();
+		assert_eq!(&id.to_uuid_str()[..], uuid.as_bytes());
+		assert_eq!(MassbitId::try_from(uuid.as_bytes().to_vec()), Ok(id));
+		assert_eq!(MassbitId::try_from(uuid), Ok(id));
+
+		for invalid in [
+			"4f5a1c2e-9b3d-4e7f-a1b2-c3d4e5f6071",
+			"4f5a1c2e-9b3d-4e7f-a1b2-c3d4e5f607189",
+			"4f5a1c2e9-b3d-4e7f-a1b2-c3d4e5f60718",
+			"4f5a1c2e-9b3d-4e7f-a1b2-c3d4e5f6071g",
+		] {
+			assert_eq!(MassbitId::from_uuid_str(invalid), Err(InvalidUuid));
+		}
+	}
+
+	#[test]
+	fn decoded_id_is_validated() {
+		assert!(MassbitId::default().is_valid_id());
+		let uuid = "4f5a1c2e-9b3d-4e7f-a1b2-c3d4e5f60718";
+		assert!(MassbitId::decode(&mut uuid.as_bytes()).unwrap().is_valid_id());
+		assert!(!MassbitId::decode(&mut &[1; 36][..]).unwrap().is_valid_id());
 	}
 }
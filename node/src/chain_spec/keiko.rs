@@ -80,6 +80,8 @@ fn make_genesis(
 			reward_config: pallet_block_reward::DistributionConfig {
 				providers_percent: Perbill::from_percent(100),
 				validators_percent: Perbill::from_percent(0),
+				community_percent: Perbill::from_percent(0),
+				treasury_percent: Perbill::from_percent(0),
 			},
 		},
 		validator_set: ValidatorSetConfig {
@@ -99,6 +101,7 @@ fn make_genesis(
 		dapi: DapiConfig {
 			regulators: initial_regulators.iter().map(|x| x.clone()).collect(),
 			chain_ids: vec!["eth.mainnet".as_bytes().into(), "dot.mainnet".as_bytes().into()],
+			providers: vec![],
 		},
 	}
 }
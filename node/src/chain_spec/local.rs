@@ -2,8 +2,8 @@
 
 use local_runtime::{
 	pallet_block_reward, wasm_binary_unwrap, AccountId, AuraConfig, BalancesConfig,
-	BlockRewardConfig, DapiConfig, GenesisConfig, GrandpaConfig, SessionConfig, SessionKeys,
-	SudoConfig, SystemConfig, ValidatorSetConfig, MBTL,
+	BlockRewardConfig, CouncilConfig, DapiConfig, GenesisConfig, GrandpaConfig, SessionConfig,
+	SessionKeys, SudoConfig, SystemConfig, ValidatorSetConfig, MBTL,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -78,6 +78,8 @@ fn make_genesis(
 			reward_config: pallet_block_reward::DistributionConfig {
 				providers_percent: Perbill::from_percent(50),
 				validators_percent: Perbill::from_percent(50),
+				community_percent: Perbill::from_percent(0),
+				treasury_percent: Perbill::from_percent(0),
 			},
 		},
 		validator_set: ValidatorSetConfig {
@@ -97,6 +99,12 @@ fn make_genesis(
 		dapi: DapiConfig {
 			regulators: initial_regulators.iter().map(|x| x.clone()).collect(),
 			chain_ids: vec!["eth.mainnet".as_bytes().into(), "dot.mainnet".as_bytes().into()],
+			providers: vec![],
+		},
+		treasury: Default::default(),
+		council: CouncilConfig {
+			members: initial_authorities.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
+			phantom: Default::default(),
 		},
 	}
 }
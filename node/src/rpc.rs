@@ -10,7 +10,8 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
 pub use sc_rpc_api::DenyUnsafe;
 
-use crate::primitives::{AccountId, Balance, Block, Nonce};
+use crate::primitives::{AccountId, Balance, Block, BlockNumber, Nonce};
+use common::MassbitId;
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -32,17 +33,32 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_block_reward_rpc::BlockRewardRuntimeApi<Block, Balance>,
+	C::Api: pallet_dapi_staking_rpc::DapiStakingRuntimeApi<
+		Block,
+		AccountId,
+		MassbitId,
+		Balance,
+		BlockNumber,
+	>,
+	C::Api: pallet_validator_set_rpc::ValidatorSetRuntimeApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_block_reward_rpc::{BlockReward, BlockRewardApiServer};
+	use pallet_dapi_staking_rpc::{DapiStaking, DapiStakingApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use pallet_validator_set_rpc::{ValidatorSet, ValidatorSetApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcModule::new(());
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(BlockReward::new(client.clone()).into_rpc())?;
+	module.merge(DapiStaking::<_, _, MassbitId, BlockNumber>::new(client.clone()).into_rpc())?;
+	module.merge(ValidatorSet::new(client).into_rpc())?;
 
 	Ok(module)
 }
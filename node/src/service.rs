@@ -13,6 +13,7 @@ use sp_runtime::{traits::BlakeTwo256, OpaqueExtrinsic};
 use std::{sync::Arc, time::Duration};
 
 use crate::primitives::*;
+use common::MassbitId;
 
 /// Local runtime executor
 pub mod local {
@@ -223,7 +224,15 @@ where
 				OpaqueExtrinsic,
 			>,
 		> + substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
-		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
+		+ pallet_block_reward_rpc::BlockRewardRuntimeApi<Block, Balance>
+		+ pallet_dapi_staking_rpc::DapiStakingRuntimeApi<
+			Block,
+			AccountId,
+			MassbitId,
+			Balance,
+			BlockNumber,
+		> + pallet_validator_set_rpc::ValidatorSetRuntimeApi<Block, AccountId>,
 	sc_client_api::StateBackendFor<TFullBackend<Block>, Block>: sp_api::StateBackend<BlakeTwo256>,
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 {
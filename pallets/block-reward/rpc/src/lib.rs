@@ -0,0 +1,73 @@
+//! RPC interface for the block reward pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_block_reward::{
+	runtime_api::BlockRewardApi as BlockRewardRuntimeApi, DistributionConfig, RewardSimulation,
+};
+
+#[rpc(client, server)]
+pub trait BlockRewardApi<BlockHash, Balance> {
+	/// Simulates block reward distribution with the given `config`.
+	#[method(name = "blockReward_simulateDistribution")]
+	fn simulate_distribution(
+		&self,
+		config: DistributionConfig,
+		at: Option<BlockHash>,
+	) -> RpcResult<RewardSimulation<Balance>>;
+}
+
+/// Provides RPC methods to query block reward data.
+pub struct BlockReward<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> BlockReward<C, Block> {
+	/// Creates a new instance of the BlockReward RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code of a failed runtime call.
+const RUNTIME_ERROR: i32 = 1;
+
+#[async_trait]
+impl<C, Block, Balance> BlockRewardApiServer<<Block as BlockT>::Hash, Balance>
+	for BlockReward<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockRewardRuntimeApi<Block, Balance>,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+{
+	fn simulate_distribution(
+		&self,
+		config: DistributionConfig,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RewardSimulation<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.simulate_reward_distribution(&at, config).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				RUNTIME_ERROR,
+				"Unable to simulate reward distribution.",
+				Some(e.to_string()),
+			))
+			.into()
+		})
+	}
+}
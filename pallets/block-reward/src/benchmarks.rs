@@ -2,22 +2,76 @@
 
 use super::*;
 
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_system::{Pallet as System, RawOrigin};
 
+const SEED: u32 = 0;
+
 /// Assert that the last event equals the provided one.
 fn assert_last_event<T: Config>(event: <T as Config>::Event) {
 	System::<T>::assert_last_event(event.into());
 }
 
+/// Fill config history with `n` entries, replaced in blocks `0..n`.
+fn fill_config_history<T: Config>(n: u32) {
+	for block in 0..n {
+		ConfigHistory::<T>::insert(T::BlockNumber::from(block), DistributionConfig::default());
+	}
+}
+
 benchmarks! {
 	set_config {
+		let h in 1 .. T::MaxConfigHistory::get();
+		fill_config_history::<T>(h);
+		System::<T>::set_block_number(h.into());
+
 		let reward_config = DistributionConfig::default();
 		assert!(reward_config.is_valid());
 	}: _(RawOrigin::Root, reward_config.clone())
 	verify {
 		assert_last_event::<T>(Event::<T>::DistributionConfigChanged(reward_config).into());
 	}
+
+	set_community_beneficiaries {
+		let n in 1 .. T::MaxBeneficiaries::get();
+		let beneficiaries: Vec<(T::AccountId, Perbill)> = (0..n)
+			.map(|i| (account("beneficiary", i, SEED), Perbill::from_percent(1)))
+			.collect();
+	}: _(RawOrigin::Root, beneficiaries.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::CommunityBeneficiariesChanged(beneficiaries).into());
+	}
+
+	prune_config_history {
+		let h in 1 .. T::MaxConfigHistory::get();
+		fill_config_history::<T>(h);
+	}: _(RawOrigin::Root, h.into())
+	verify {
+		assert_eq!(ConfigHistory::<T>::iter().count(), 0);
+	}
+
+	set_chain_reward_multiplier {
+		let chain_id = vec![b'x'; T::MaxChainIdLength::get() as usize];
+		let multiplier = Perbill::from_percent(50);
+	}: _(RawOrigin::Root, chain_id.clone(), multiplier)
+	verify {
+		let chain_id: BoundedVec<u8, T::MaxChainIdLength> = chain_id.try_into().unwrap();
+		assert_eq!(ChainRewardMultiplier::<T>::get(chain_id), Some(multiplier));
+	}
+
+	on_timestamp_set {
+		let beneficiaries: Vec<(T::AccountId, Perbill)> = (0..T::MaxBeneficiaries::get())
+			.map(|i| (account("beneficiary", i, SEED), Perbill::from_percent(1)))
+			.collect();
+		CommunityBeneficiaries::<T>::put(BoundedVec::try_from(beneficiaries).unwrap());
+		let issuance = T::Currency::total_issuance();
+		let reward = Pallet::<T>::block_reward_amount();
+	}: {
+		<Pallet<T> as OnTimestampSet<u32>>::on_timestamp_set(0);
+	}
+	verify {
+		assert_eq!(T::Currency::total_issuance(), issuance + reward);
+	}
 }
 
 #[cfg(test)]
@@ -12,6 +12,14 @@
 //! ### Dispatchable Function
 //!
 //! - `set_config` - change reward distribution configuration parameters
+//! - `set_community_beneficiaries` - change the list of community beneficiaries and their shares
+//! - `prune_config_history` - remove historical distribution configs replaced before a block
+//! - `set_chain_reward_multiplier` - scale providers' reward while a chain has the most stake
+//!
+//! ### Runtime API
+//!
+//! - `simulate_reward_distribution` - preview the reward split of a distribution config
+//! - `config_at_block` - distribution config which was active at a given block
 //!
 //! ## Usage
 //!
@@ -28,8 +36,24 @@
 //! 	fn providers(reward: NegativeImbalance) {
 //! 		Balances::resolve_creating(&PROVIDER_POT.into_account(), reward);
 //! 	}
+//!
+//! 	fn community(reward: NegativeImbalance, beneficiaries: &[(AccountId, Perbill)]) {
+//! 		let total = reward.peek();
+//! 		let mut remainder = reward;
+//! 		for (account, share) in beneficiaries {
+//! 			let (portion, rest) = remainder.split(*share * total);
+//! 			Balances::resolve_creating(account, portion);
+//! 			remainder = rest;
+//! 		}
+//! 	}
+//!
+//! 	fn treasury(reward: NegativeImbalance) {
+//! 		Treasury::on_unbalanced(reward);
+//! 	}
 //! }
-//! 3. Set `RewardAmount` to desired block reward value in native currency.
+//! 3. Set `RewardAmount` to desired block reward value in native currency. Issued reward is
+//! scaled by the ratio between `TargetStakeRatio` and the actual staked share of total issuance,
+//! up to `MaxRewardMultiplier` times `RewardAmount`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -38,8 +62,12 @@ use frame_support::{
 	traits::{Currency, Get, Imbalance, OnTimestampSet},
 };
 use frame_system::{ensure_root, pallet_prelude::*};
-use sp_runtime::{traits::CheckedAdd, Perbill};
-use sp_std::vec;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{CheckedAdd, One, SaturatedConversion, Saturating, Zero},
+	Perbill,
+};
+use sp_std::{vec, vec::Vec};
 
 #[cfg(any(feature = "runtime-benchmarks"))]
 pub mod benchmarks;
@@ -48,6 +76,8 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+pub mod runtime_api;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -64,7 +94,11 @@ pub mod pallet {
 		<T as frame_system::Config>::AccountId,
 	>>::NegativeImbalance;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -73,12 +107,45 @@ pub mod pallet {
 		type Currency: Currency<Self::AccountId>;
 
 		/// Payout rewards handler.
-		type BeneficiaryPayout: BeneficiaryPayout<NegativeImbalanceOf<Self>>;
+		type BeneficiaryPayout: BeneficiaryPayout<NegativeImbalanceOf<Self>, Self::AccountId>;
 
 		/// The amount of issuance for each block.
 		#[pallet::constant]
 		type RewardAmount: Get<BalanceOf<Self>>;
 
+		/// Maximum number of community beneficiaries.
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
+		/// Minimum number of active providers required for providers to receive rewards.
+		#[pallet::constant]
+		type MinActiveProviders: Get<u32>;
+
+		/// Source of the current number of active providers.
+		type ProviderCountOracle: Get<u32>;
+
+		/// Source of the chains served by providers.
+		type ProviderChainInfoOracle: ChainProviderInfo;
+
+		/// Source of the current total staked amount.
+		type StakingInfo: Get<BalanceOf<Self>>;
+
+		/// Share of total issuance expected to be staked.
+		#[pallet::constant]
+		type TargetStakeRatio: Get<Perbill>;
+
+		/// Maximum multiplier of `RewardAmount` applied while staked share is below target.
+		#[pallet::constant]
+		type MaxRewardMultiplier: Get<u32>;
+
+		/// Maximum length of a chain Id.
+		#[pallet::constant]
+		type MaxChainIdLength: Get<u32>;
+
+		/// Maximum number of replaced distribution configs kept in history.
+		#[pallet::constant]
+		type MaxConfigHistory: Get<u32>;
+
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -90,17 +157,57 @@ pub mod pallet {
 	#[pallet::getter(fn reward_config)]
 	pub type RewardConfig<T: Config> = StorageValue<_, DistributionConfig, ValueQuery>;
 
+	/// Replaced distribution configs, keyed by the last block in which they were active.
+	#[pallet::storage]
+	#[pallet::getter(fn config_history)]
+	pub type ConfigHistory<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, DistributionConfig, OptionQuery>;
+
+	/// First block whose distribution config is still known, i.e. the block after the newest
+	/// config evicted or pruned from `ConfigHistory`.
+	#[pallet::storage]
+	#[pallet::getter(fn config_history_start)]
+	pub type ConfigHistoryStart<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Community beneficiaries with their relative weight in the community reward.
+	#[pallet::storage]
+	#[pallet::getter(fn community_beneficiaries)]
+	pub type CommunityBeneficiaries<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, Perbill), T::MaxBeneficiaries>, ValueQuery>;
+
+	/// Multiplier of providers' reward applied while a chain has the most stake of providers.
+	#[pallet::storage]
+	#[pallet::getter(fn chain_reward_multiplier)]
+	pub type ChainRewardMultiplier<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxChainIdLength>, Perbill>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Distribution config has been updated.
 		DistributionConfigChanged(DistributionConfig),
+		/// Community beneficiaries have been updated.
+		CommunityBeneficiariesChanged(Vec<(T::AccountId, Perbill)>),
+		/// Community reward has been distributed among community beneficiaries.
+		CommunityRewardDistributed { total_amount: BalanceOf<T> },
+		/// Providers' reward has been redirected to validators, as there are not enough active
+		/// providers.
+		ProviderRewardRedirected { amount: BalanceOf<T> },
+		/// Providers' reward has been scaled by the multiplier of the chain with the most
+		/// providers.
+		ChainRewardMultiplierApplied { chain_id: Vec<u8>, multiplier: Perbill },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Sum of all rations must be one whole (100%)
 		InvalidDistributionConfig,
+		/// Number of community beneficiaries exceeds the limit.
+		TooManyBeneficiaries,
+		/// Community beneficiary percentage must be non-zero.
+		ZeroBeneficiaryPercent,
+		/// Chain Id is too long.
+		ChainIdTooLong,
 	}
 
 	#[pallet::genesis_config]
@@ -123,6 +230,14 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			// Reward is distributed when timestamp is set, which doesn't account for weight
+			T::WeightInfo::on_timestamp_set()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Sets the reward distribution config parameters which will be used from next block
@@ -130,7 +245,9 @@ pub mod pallet {
 		///
 		/// It is mandatory that all components of config sum up to one whole (**100%**),
 		/// otherwise an error `InvalidDistributionConfig` will be raised.
-		#[pallet::weight(T::WeightInfo::set_config())]
+		///
+		/// Replaced config is kept in `ConfigHistory`, evicting the oldest entry when it's full.
+		#[pallet::weight(T::WeightInfo::set_config(T::MaxConfigHistory::get()))]
 		pub fn set_config(
 			origin: OriginFor<T>,
 			config: DistributionConfig,
@@ -138,17 +255,84 @@ pub mod pallet {
 			ensure_root(origin)?;
 
 			ensure!(config.is_valid(), Error::<T>::InvalidDistributionConfig);
+			let old_config = RewardConfig::<T>::get();
 			RewardConfig::<T>::put(config.clone());
+			Self::record_config_history(old_config);
 
 			Self::deposit_event(Event::<T>::DistributionConfigChanged(config));
 
 			Ok(().into())
 		}
+
+		/// Sets the community beneficiaries which will share the community portion of block
+		/// rewards, proportionally to their percentages.
+		///
+		/// All percentages must be non-zero. An empty list disables community rewards.
+		#[pallet::weight(T::WeightInfo::set_community_beneficiaries(beneficiaries.len() as u32))]
+		pub fn set_community_beneficiaries(
+			origin: OriginFor<T>,
+			beneficiaries: Vec<(T::AccountId, Perbill)>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(
+				beneficiaries.iter().all(|(_, percent)| !percent.is_zero()),
+				Error::<T>::ZeroBeneficiaryPercent
+			);
+			let beneficiaries: BoundedVec<_, T::MaxBeneficiaries> =
+				beneficiaries.try_into().map_err(|_| Error::<T>::TooManyBeneficiaries)?;
+
+			CommunityBeneficiaries::<T>::put(&beneficiaries);
+			Self::deposit_event(Event::<T>::CommunityBeneficiariesChanged(
+				beneficiaries.into_inner(),
+			));
+
+			Ok(().into())
+		}
+
+		/// Removes all historical distribution configs which were replaced before
+		/// `older_than_block`.
+		#[pallet::weight(T::WeightInfo::prune_config_history(T::MaxConfigHistory::get()))]
+		pub fn prune_config_history(
+			origin: OriginFor<T>,
+			older_than_block: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let outdated: Vec<_> = ConfigHistory::<T>::iter_keys()
+				.filter(|block| *block < older_than_block)
+				.collect();
+			if let Some(newest) = outdated.iter().max() {
+				ConfigHistoryStart::<T>::put(newest.saturating_add(One::one()));
+			}
+			for block in outdated {
+				ConfigHistory::<T>::remove(block);
+			}
+
+			Ok(().into())
+		}
+
+		/// Sets the multiplier of providers' reward, applied while `chain_id` is the chain with
+		/// the most stake of providers.
+		#[pallet::weight(T::WeightInfo::set_chain_reward_multiplier())]
+		pub fn set_chain_reward_multiplier(
+			origin: OriginFor<T>,
+			chain_id: Vec<u8>,
+			multiplier: Perbill,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let chain_id: BoundedVec<u8, T::MaxChainIdLength> =
+				chain_id.try_into().map_err(|_| Error::<T>::ChainIdTooLong)?;
+			ChainRewardMultiplier::<T>::insert(chain_id, multiplier);
+
+			Ok(().into())
+		}
 	}
 
 	impl<Moment, T: Config> OnTimestampSet<Moment> for Pallet<T> {
 		fn on_timestamp_set(_: Moment) {
-			let inflation = T::Currency::issue(T::RewardAmount::get());
+			let inflation = T::Currency::issue(Self::block_reward_amount());
 			Self::distribute_rewards(inflation);
 		}
 	}
@@ -157,16 +341,181 @@ pub mod pallet {
 		/// Distribute reward between beneficiaries.
 		fn distribute_rewards(block_reward: NegativeImbalanceOf<T>) {
 			let config = <RewardConfig<T>>::get();
+			let beneficiaries = <CommunityBeneficiaries<T>>::get();
 
 			// Calculate balance which will be deposited for each beneficiary
-			let provider_balance = config.providers_percent * block_reward.peek();
+			let mut provider_balance = config.providers_percent * block_reward.peek();
+			if !Self::has_enough_active_providers() && !provider_balance.is_zero() {
+				// Validators get the providers' share, as there is nobody to stake with
+				Self::deposit_event(Event::<T>::ProviderRewardRedirected {
+					amount: provider_balance,
+				});
+				provider_balance = Zero::zero();
+			}
+			if let Some((chain_id, multiplier)) =
+				Self::chain_multiplier().filter(|_| !provider_balance.is_zero())
+			{
+				// Validators get the part of providers' share cut by the multiplier
+				provider_balance = multiplier * provider_balance;
+				Self::deposit_event(Event::<T>::ChainRewardMultiplierApplied {
+					chain_id,
+					multiplier,
+				});
+			}
+			let community_balance = if beneficiaries.is_empty() {
+				Zero::zero()
+			} else {
+				config.community_percent * block_reward.peek()
+			};
+			let treasury_balance = config.treasury_percent * block_reward.peek();
 
-			// Prepare imbalances
-			let (providers_imbalance, validators_imbalance) = block_reward.split(provider_balance);
+			// Prepare imbalances, validators get whatever is left
+			let (providers_imbalance, remainder) = block_reward.split(provider_balance);
+			let (treasury_imbalance, remainder) = remainder.split(treasury_balance);
+			let (community_imbalance, validators_imbalance) = remainder.split(community_balance);
 
 			// Payout beneficiaries
 			T::BeneficiaryPayout::validators(validators_imbalance);
 			T::BeneficiaryPayout::providers(providers_imbalance);
+			T::BeneficiaryPayout::treasury(treasury_imbalance);
+
+			if !community_balance.is_zero() {
+				T::BeneficiaryPayout::community(
+					community_imbalance,
+					&Self::community_shares(&beneficiaries),
+				);
+				Self::deposit_event(Event::<T>::CommunityRewardDistributed {
+					total_amount: community_balance,
+				});
+			}
+		}
+
+		/// Block reward scaled by the ratio between target and actual staked share of total
+		/// issuance, capped at `MaxRewardMultiplier` times `RewardAmount`.
+		pub fn block_reward_amount() -> BalanceOf<T> {
+			let base_reward = T::RewardAmount::get();
+			let max_multiplier: BalanceOf<T> = T::MaxRewardMultiplier::get().into();
+			let max_reward = base_reward.saturating_mul(max_multiplier);
+			let target_stake = T::TargetStakeRatio::get() * T::Currency::total_issuance();
+			let staked = T::StakingInfo::get();
+
+			// Also covers the case of nothing being staked
+			if staked.saturating_mul(max_multiplier) <= target_stake {
+				return max_reward
+			}
+
+			multiply_by_rational(
+				base_reward.saturated_into(),
+				target_stake.saturated_into(),
+				staked.saturated_into(),
+			)
+			.map(|reward| reward.saturated_into())
+			.unwrap_or(max_reward)
+		}
+
+		/// Chain with the most stake of providers along with its reward multiplier, if one is set.
+		fn chain_multiplier() -> Option<(Vec<u8>, Perbill)> {
+			let chain_id = T::ProviderChainInfoOracle::most_served_chain()?;
+			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
+				chain_id.clone().try_into().ok()?;
+			ChainRewardMultiplier::<T>::get(bounded_chain_id)
+				.map(|multiplier| (chain_id, multiplier))
+		}
+
+		/// `true` if number of active providers reaches `MinActiveProviders`, `false` otherwise.
+		fn has_enough_active_providers() -> bool {
+			T::ProviderCountOracle::get() >= T::MinActiveProviders::get()
+		}
+
+		/// Normalize community beneficiaries' percentages so that their shares sum up to one
+		/// whole, keeping the proportions between them.
+		fn community_shares(
+			beneficiaries: &[(T::AccountId, Perbill)],
+		) -> Vec<(T::AccountId, Perbill)> {
+			let total: u64 =
+				beneficiaries.iter().map(|(_, percent)| percent.deconstruct() as u64).sum();
+
+			beneficiaries
+				.iter()
+				.map(|(account, percent)| {
+					(account.clone(), Perbill::from_rational(percent.deconstruct() as u64, total))
+				})
+				.collect()
+		}
+
+		/// Store `old_config` as the config which was active until the current block.
+		///
+		/// If config has already been replaced in the current block, the intermediate config was
+		/// never active and is not recorded.
+		fn record_config_history(old_config: DistributionConfig) {
+			let now = frame_system::Pallet::<T>::block_number();
+			if ConfigHistory::<T>::contains_key(now) {
+				return
+			}
+
+			let blocks: Vec<_> = ConfigHistory::<T>::iter_keys().collect();
+			if blocks.len() >= T::MaxConfigHistory::get() as usize {
+				if let Some(oldest) = blocks.into_iter().min() {
+					ConfigHistory::<T>::remove(oldest);
+					ConfigHistoryStart::<T>::put(oldest.saturating_add(One::one()));
+				}
+			}
+
+			ConfigHistory::<T>::insert(now, old_config);
+		}
+
+		/// Distribution config which was active at `block`.
+		///
+		/// Looks up the earliest config replaced at or after `block`, falling back to the current
+		/// config if there is none. Returns `None` for future blocks and for blocks older than
+		/// the retained history.
+		pub fn config_at_block(block: T::BlockNumber) -> Option<DistributionConfig> {
+			if block > frame_system::Pallet::<T>::block_number() ||
+				block < ConfigHistoryStart::<T>::get()
+			{
+				return None
+			}
+
+			let replaced = ConfigHistory::<T>::iter()
+				.filter(|(replaced_at, _)| *replaced_at >= block)
+				.min_by_key(|(replaced_at, _)| *replaced_at)
+				.map(|(_, config)| config);
+
+			Some(replaced.unwrap_or_else(RewardConfig::<T>::get))
+		}
+
+		/// Calculate how the block reward would be distributed using `config`.
+		///
+		/// Doesn't modify any storage, nor emit any events.
+		pub fn simulate_reward_distribution(
+			config: &DistributionConfig,
+		) -> RewardSimulation<BalanceOf<T>> {
+			let block_reward = Self::block_reward_amount();
+
+			let providers_reward = if Self::has_enough_active_providers() {
+				let multiplier = Self::chain_multiplier()
+					.map_or_else(Perbill::one, |(_, multiplier)| multiplier);
+				multiplier * (config.providers_percent * block_reward)
+			} else {
+				Zero::zero()
+			};
+			let community_reward = if <CommunityBeneficiaries<T>>::get().is_empty() {
+				Zero::zero()
+			} else {
+				config.community_percent * block_reward
+			};
+			let treasury_reward = config.treasury_percent * block_reward;
+			let validators_reward = block_reward
+				.saturating_sub(providers_reward)
+				.saturating_sub(community_reward)
+				.saturating_sub(treasury_reward);
+
+			RewardSimulation {
+				validators_reward,
+				providers_reward,
+				community_reward,
+				treasury_reward,
+			}
 		}
 	}
 }
@@ -179,6 +528,10 @@ pub struct DistributionConfig {
 	pub providers_percent: Perbill,
 	/// Percentage of rewards that goes to validators
 	pub validators_percent: Perbill,
+	/// Percentage of rewards that goes to community beneficiaries
+	pub community_percent: Perbill,
+	/// Percentage of rewards that goes to treasury
+	pub treasury_percent: Perbill,
 }
 
 impl Default for DistributionConfig {
@@ -186,6 +539,8 @@ impl Default for DistributionConfig {
 		DistributionConfig {
 			providers_percent: Perbill::from_percent(50),
 			validators_percent: Perbill::from_percent(50),
+			community_percent: Zero::zero(),
+			treasury_percent: Zero::zero(),
 		}
 	}
 }
@@ -193,7 +548,12 @@ impl Default for DistributionConfig {
 impl DistributionConfig {
 	/// `true` if sum of all percentages is `one whole`, `false` otherwise.
 	pub fn is_valid(&self) -> bool {
-		let percentages = vec![&self.providers_percent, &self.validators_percent];
+		let percentages = vec![
+			&self.providers_percent,
+			&self.validators_percent,
+			&self.community_percent,
+			&self.treasury_percent,
+		];
 
 		let mut accumulator = Perbill::zero();
 		for percentage in percentages {
@@ -209,11 +569,45 @@ impl DistributionConfig {
 	}
 }
 
+/// Block reward portions of each beneficiary, as calculated by a simulated distribution.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardSimulation<Balance> {
+	/// Reward that goes to validators
+	pub validators_reward: Balance,
+	/// Reward that goes to providers
+	pub providers_reward: Balance,
+	/// Reward that goes to community beneficiaries
+	pub community_reward: Balance,
+	/// Reward that goes to treasury
+	pub treasury_reward: Balance,
+}
+
+/// Provides information about the chains served by providers.
+pub trait ChainProviderInfo {
+	/// Chain with the most stake of active providers, if any.
+	fn most_served_chain() -> Option<Vec<u8>>;
+}
+
+impl ChainProviderInfo for () {
+	fn most_served_chain() -> Option<Vec<u8>> {
+		None
+	}
+}
+
 /// Defines functions used to payout the beneficiaries of block rewards
-pub trait BeneficiaryPayout<Imbalance> {
+pub trait BeneficiaryPayout<Imbalance, AccountId> {
 	/// Payout reward to the validators
 	fn validators(reward: Imbalance);
 
 	/// Payout reward to providers and its delegators
 	fn providers(reward: Imbalance);
+
+	/// Payout reward to community beneficiaries, each one receiving its share of `reward`.
+	///
+	/// Shares of all `beneficiaries` sum up to one whole.
+	fn community(reward: Imbalance, beneficiaries: &[(AccountId, Perbill)]);
+
+	/// Payout reward to the treasury
+	fn treasury(reward: Imbalance);
 }
@@ -0,0 +1,111 @@
+//! Storage migrations for block reward pallet.
+
+use crate::{Config, ConfigHistory, DistributionConfig, Pallet, RewardConfig};
+use codec::{Decode, Encode};
+use frame_support::{
+	log,
+	storage::unhashed,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::{traits::Zero, Perbill};
+use sp_std::marker::PhantomData;
+
+/// Distribution config layout before community became a reward beneficiary.
+#[derive(Encode, Decode)]
+struct OldDistributionConfig {
+	providers_percent: Perbill,
+	validators_percent: Perbill,
+}
+
+/// Distribution config layout from storage version 1, before treasury became a reward
+/// beneficiary.
+#[derive(Encode, Decode)]
+struct DistributionConfigV1 {
+	providers_percent: Perbill,
+	validators_percent: Perbill,
+	community_percent: Perbill,
+}
+
+/// Migrates `RewardConfig` to the layout with community percentage. The existing config gives
+/// nothing to the community. A reward config which can't be decoded is reset to the default one.
+pub struct MigrateDistributionConfigToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateDistributionConfigToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			log::info!(target: "block-reward", "DistributionConfig already has a community share, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		// The config is written in the layout `MigrateDistributionConfigToV2` migrates from
+		let key = RewardConfig::<T>::hashed_key();
+		if unhashed::exists(&key) {
+			match unhashed::get::<OldDistributionConfig>(&key) {
+				Some(old) => unhashed::put(
+					&key,
+					&DistributionConfigV1 {
+						providers_percent: old.providers_percent,
+						validators_percent: old.validators_percent,
+						community_percent: Zero::zero(),
+					},
+				),
+				None => {
+					log::error!(
+						target: "block-reward",
+						"Failed to decode the reward config, resetting it to the default one"
+					);
+					unhashed::kill(&key);
+				},
+			}
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		log::info!(target: "block-reward", "Migrated the distribution config");
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+}
+
+impl From<DistributionConfigV1> for DistributionConfig {
+	fn from(old: DistributionConfigV1) -> Self {
+		DistributionConfig {
+			providers_percent: old.providers_percent,
+			validators_percent: old.validators_percent,
+			community_percent: old.community_percent,
+			treasury_percent: Zero::zero(),
+		}
+	}
+}
+
+/// Migrates `RewardConfig` and `ConfigHistory` to the layout with treasury percentage. Existing
+/// configs give nothing to treasury. A reward config which can't be decoded is reset to the
+/// default one.
+pub struct MigrateDistributionConfigToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateDistributionConfigToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			log::info!(target: "block-reward", "DistributionConfig already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		if RewardConfig::<T>::translate::<DistributionConfigV1, _>(|old| old.map(Into::into))
+			.is_err()
+		{
+			log::error!(
+				target: "block-reward",
+				"Failed to decode the reward config, resetting it to the default one"
+			);
+			RewardConfig::<T>::kill();
+		}
+		let mut translated: Weight = 1;
+		ConfigHistory::<T>::translate::<DistributionConfigV1, _>(|_, old| {
+			translated += 1;
+			Some(old.into())
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		log::info!(target: "block-reward", "Migrated {} distribution configs", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
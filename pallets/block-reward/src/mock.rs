@@ -1,13 +1,17 @@
 use crate::{self as pallet_block_reward, NegativeImbalanceOf};
 
 use frame_support::{
-	construct_runtime, parameter_types, sp_io::TestExternalities, traits::Currency, PalletId,
+	construct_runtime, parameter_types,
+	sp_io::TestExternalities,
+	traits::{Currency, Get, Imbalance},
+	PalletId,
 };
 
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 
 pub(crate) type AccountId = u64;
@@ -96,10 +100,11 @@ impl pallet_timestamp::Config for TestRuntime {
 pub(crate) const BLOCK_REWARD: Balance = 1_000_000;
 pub(crate) const VALIDATOR_POT: PalletId = PalletId(*b"mokvaldt");
 pub(crate) const PROVIDER_POT: PalletId = PalletId(*b"mokprovd");
+pub(crate) const TREASURY_POT: PalletId = PalletId(*b"moktresr");
 
 // Type used as beneficiary payout handle
 pub struct BeneficiaryPayout();
-impl pallet_block_reward::BeneficiaryPayout<NegativeImbalanceOf<TestRuntime>>
+impl pallet_block_reward::BeneficiaryPayout<NegativeImbalanceOf<TestRuntime>, AccountId>
 	for BeneficiaryPayout
 {
 	fn validators(reward: NegativeImbalanceOf<TestRuntime>) {
@@ -109,16 +114,67 @@ impl pallet_block_reward::BeneficiaryPayout<NegativeImbalanceOf<TestRuntime>>
 	fn providers(reward: NegativeImbalanceOf<TestRuntime>) {
 		Balances::resolve_creating(&PROVIDER_POT.into_account_truncating(), reward)
 	}
+
+	fn community(reward: NegativeImbalanceOf<TestRuntime>, beneficiaries: &[(AccountId, Perbill)]) {
+		let total = reward.peek();
+		let mut remainder = reward;
+		for (account, share) in beneficiaries {
+			let (portion, rest) = remainder.split(*share * total);
+			Balances::resolve_creating(account, portion);
+			remainder = rest;
+		}
+	}
+
+	fn treasury(reward: NegativeImbalanceOf<TestRuntime>) {
+		Balances::resolve_creating(&TREASURY_POT.into_account_truncating(), reward)
+	}
 }
 
 parameter_types! {
 	pub const RewardAmount: Balance = BLOCK_REWARD;
+	pub const MaxBeneficiaries: u32 = 5;
+	pub const MaxConfigHistory: u32 = 3;
+	pub const MinActiveProviders: u32 = 1;
+	pub static ActiveProviders: u32 = 1;
+	pub static ChainProviders: Vec<(Vec<u8>, u32)> = Vec::new();
+	pub const MaxChainIdLength: u32 = 16;
+	pub const TargetStakeRatio: Perbill = Perbill::from_percent(50);
+	pub const MaxRewardMultiplier: u32 = 3;
+	pub static StakedAmount: Option<Balance> = None;
+}
+
+// Staked amount is at target, unless `StakedAmount` is set
+pub struct MockStakingInfo;
+impl Get<Balance> for MockStakingInfo {
+	fn get() -> Balance {
+		StakedAmount::get().unwrap_or_else(|| TargetStakeRatio::get() * Balances::total_issuance())
+	}
+}
+
+// Chain with the most providers out of the fixed `ChainProviders` distribution
+pub struct MockChainProviderInfo;
+impl pallet_block_reward::ChainProviderInfo for MockChainProviderInfo {
+	fn most_served_chain() -> Option<Vec<u8>> {
+		ChainProviders::get()
+			.into_iter()
+			.max_by_key(|(_, providers)| *providers)
+			.map(|(chain_id, _)| chain_id)
+	}
 }
 
 impl pallet_block_reward::Config for TestRuntime {
 	type Currency = Balances;
 	type BeneficiaryPayout = BeneficiaryPayout;
 	type RewardAmount = RewardAmount;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxConfigHistory = MaxConfigHistory;
+	type MinActiveProviders = MinActiveProviders;
+	type ProviderCountOracle = ActiveProviders;
+	type ProviderChainInfoOracle = MockChainProviderInfo;
+	type MaxChainIdLength = MaxChainIdLength;
+	type StakingInfo = MockStakingInfo;
+	type TargetStakeRatio = TargetStakeRatio;
+	type MaxRewardMultiplier = MaxRewardMultiplier;
 	type Event = Event;
 	type WeightInfo = ();
 }
@@ -0,0 +1,17 @@
+//! Runtime API definition for block reward pallet.
+
+use crate::{DistributionConfig, RewardSimulation};
+use codec::Codec;
+use sp_runtime::traits::NumberFor;
+
+sp_api::decl_runtime_apis! {
+	pub trait BlockRewardApi<Balance> where
+		Balance: Codec,
+	{
+		/// Simulates block reward distribution with the given `config`, without modifying any state.
+		fn simulate_reward_distribution(config: DistributionConfig) -> RewardSimulation<Balance>;
+
+		/// Returns the distribution config which was active at `block`, if it is still known.
+		fn config_at_block(block: NumberFor<Block>) -> Option<DistributionConfig>;
+	}
+}
@@ -17,12 +17,16 @@ fn distribution_config_is_valid() {
 	let config = DistributionConfig {
 		providers_percent: Perbill::from_percent(100),
 		validators_percent: Zero::zero(),
+		community_percent: Zero::zero(),
+		treasury_percent: Zero::zero(),
 	};
 	assert!(config.is_valid());
 
 	let config = DistributionConfig {
 		providers_percent: Perbill::from_percent(80),
 		validators_percent: Perbill::from_percent(20),
+		community_percent: Zero::zero(),
+		treasury_percent: Zero::zero(),
 	};
 	assert!(config.is_valid());
 }
@@ -36,6 +40,16 @@ fn distribution_config_is_invalid() {
 	let config = DistributionConfig {
 		providers_percent: Perbill::from_percent(80),
 		validators_percent: Perbill::from_percent(19),
+		community_percent: Zero::zero(),
+		treasury_percent: Zero::zero(),
+	};
+	assert!(!config.is_valid());
+
+	let config = DistributionConfig {
+		providers_percent: Perbill::from_percent(80),
+		validators_percent: Perbill::from_percent(20),
+		community_percent: Zero::zero(),
+		treasury_percent: Perbill::from_percent(1),
 	};
 	assert!(!config.is_valid());
 }
@@ -63,6 +77,8 @@ pub fn set_configuration_success() {
 		let config = DistributionConfig {
 			providers_percent: Perbill::from_percent(50),
 			validators_percent: Perbill::from_percent(50),
+			community_percent: Zero::zero(),
+			treasury_percent: Zero::zero(),
 		};
 		assert!(config.is_valid());
 
@@ -102,6 +118,8 @@ pub fn reward_distribution_as_expected() {
 		let config = DistributionConfig {
 			validators_percent: Perbill::from_percent(20),
 			providers_percent: Perbill::from_percent(80),
+			community_percent: Zero::zero(),
+			treasury_percent: Zero::zero(),
 		};
 		assert!(config.is_valid());
 		assert_ok!(BlockReward::set_config(Origin::root(), config.clone()));
@@ -116,11 +134,428 @@ pub fn reward_distribution_as_expected() {
 	})
 }
 
+#[test]
+pub fn simulate_reward_distribution_as_expected() {
+	ExternalityBuilder::build().execute_with(|| {
+		let config = DistributionConfig {
+			validators_percent: Perbill::from_percent(30),
+			providers_percent: Perbill::from_percent(70),
+			community_percent: Zero::zero(),
+			treasury_percent: Zero::zero(),
+		};
+		assert!(config.is_valid());
+
+		let init_balance_state = FreeBalanceSnapshot::new();
+		let init_issuance = <TestRuntime as Config>::Currency::total_issuance();
+		let init_config = RewardConfig::<TestRuntime>::get();
+
+		let simulation = BlockReward::simulate_reward_distribution(&config);
+		let rewards = Rewards::calculate(&config);
+		assert_eq!(simulation.validators_reward, rewards.validators_reward);
+		assert_eq!(simulation.providers_reward, rewards.providers_reward);
+		assert!(simulation.community_reward.is_zero());
+		assert!(simulation.treasury_reward.is_zero());
+		assert_eq!(
+			simulation.validators_reward + simulation.providers_reward + simulation.treasury_reward,
+			BLOCK_REWARD
+		);
+
+		// Nothing has been changed
+		assert_eq!(FreeBalanceSnapshot::new(), init_balance_state);
+		assert_eq!(<TestRuntime as Config>::Currency::total_issuance(), init_issuance);
+		assert_eq!(RewardConfig::<TestRuntime>::get(), init_config);
+		assert!(System::events().is_empty());
+	})
+}
+
+#[test]
+pub fn set_community_beneficiaries_fail() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_noop!(
+			BlockReward::set_community_beneficiaries(Origin::signed(1), vec![]),
+			BadOrigin
+		);
+
+		assert_noop!(
+			BlockReward::set_community_beneficiaries(
+				Origin::root(),
+				vec![(10, Perbill::from_percent(50)), (11, Zero::zero())]
+			),
+			Error::<TestRuntime>::ZeroBeneficiaryPercent,
+		);
+
+		let max = <TestRuntime as Config>::MaxBeneficiaries::get() as u64;
+		let beneficiaries = (0..=max).map(|i| (10 + i, Perbill::from_percent(1))).collect();
+		assert_noop!(
+			BlockReward::set_community_beneficiaries(Origin::root(), beneficiaries),
+			Error::<TestRuntime>::TooManyBeneficiaries,
+		);
+	})
+}
+
+#[test]
+pub fn community_reward_distribution_is_proportional() {
+	ExternalityBuilder::build().execute_with(|| {
+		let config = DistributionConfig {
+			validators_percent: Perbill::from_percent(30),
+			providers_percent: Perbill::from_percent(50),
+			community_percent: Perbill::from_percent(20),
+			treasury_percent: Zero::zero(),
+		};
+		assert!(config.is_valid());
+		assert_ok!(BlockReward::set_config(Origin::root(), config.clone()));
+
+		// Percentages don't have to sum up to one whole, only proportions matter
+		let beneficiaries = vec![(10, Perbill::from_percent(20)), (11, Perbill::from_percent(60))];
+		assert_ok!(BlockReward::set_community_beneficiaries(Origin::root(), beneficiaries.clone()));
+		System::assert_last_event(mock::Event::BlockReward(Event::CommunityBeneficiariesChanged(
+			beneficiaries,
+		)));
+
+		let init_balance_state = FreeBalanceSnapshot::new();
+		BlockReward::on_timestamp_set(0);
+		let final_balance_state = FreeBalanceSnapshot::new();
+		init_balance_state.assert_distribution(&final_balance_state, &Rewards::calculate(&config));
+
+		let community_reward = config.community_percent * BLOCK_REWARD;
+		assert_eq!(Balances::free_balance(&10), community_reward / 4);
+		assert_eq!(Balances::free_balance(&11), community_reward * 3 / 4);
+		System::assert_last_event(mock::Event::BlockReward(Event::CommunityRewardDistributed {
+			total_amount: community_reward,
+		}));
+	})
+}
+
+#[test]
+pub fn community_reward_goes_to_validators_without_beneficiaries() {
+	ExternalityBuilder::build().execute_with(|| {
+		let config = DistributionConfig {
+			validators_percent: Perbill::from_percent(30),
+			providers_percent: Perbill::from_percent(50),
+			community_percent: Perbill::from_percent(20),
+			treasury_percent: Zero::zero(),
+		};
+		assert_ok!(BlockReward::set_config(Origin::root(), config.clone()));
+		assert!(BlockReward::community_beneficiaries().is_empty());
+
+		let init_balance_state = FreeBalanceSnapshot::new();
+		BlockReward::on_timestamp_set(0);
+		let final_balance_state = FreeBalanceSnapshot::new();
+
+		let rewards = Rewards {
+			validators_reward: (config.validators_percent + config.community_percent) *
+				BLOCK_REWARD,
+			providers_reward: config.providers_percent * BLOCK_REWARD,
+			treasury_reward: Zero::zero(),
+		};
+		init_balance_state.assert_distribution(&final_balance_state, &rewards);
+	})
+}
+
+#[test]
+pub fn treasury_reward_distribution_as_expected() {
+	ExternalityBuilder::build().execute_with(|| {
+		let config = DistributionConfig {
+			validators_percent: Perbill::from_percent(30),
+			providers_percent: Perbill::from_percent(50),
+			community_percent: Zero::zero(),
+			treasury_percent: Perbill::from_percent(20),
+		};
+		assert!(config.is_valid());
+		assert_ok!(BlockReward::set_config(Origin::root(), config.clone()));
+
+		let simulation = BlockReward::simulate_reward_distribution(&config);
+		assert_eq!(simulation.treasury_reward, config.treasury_percent * BLOCK_REWARD);
+
+		let init_balance_state = FreeBalanceSnapshot::new();
+		BlockReward::on_timestamp_set(0);
+		let final_balance_state = FreeBalanceSnapshot::new();
+		init_balance_state.assert_distribution(&final_balance_state, &Rewards::calculate(&config));
+		assert_eq!(final_balance_state.treasury, simulation.treasury_reward);
+	})
+}
+
+#[test]
+pub fn provider_reward_redirected_without_active_providers() {
+	ExternalityBuilder::build().execute_with(|| {
+		ActiveProviders::set(0);
+		let config = RewardConfig::<TestRuntime>::get();
+		assert!(!config.providers_percent.is_zero());
+
+		let simulation = BlockReward::simulate_reward_distribution(&config);
+		assert!(simulation.providers_reward.is_zero());
+		assert_eq!(simulation.validators_reward, BLOCK_REWARD);
+
+		let init_balance_state = FreeBalanceSnapshot::new();
+		BlockReward::on_timestamp_set(0);
+		let final_balance_state = FreeBalanceSnapshot::new();
+
+		let rewards = Rewards {
+			validators_reward: BLOCK_REWARD,
+			providers_reward: Zero::zero(),
+			treasury_reward: Zero::zero(),
+		};
+		init_balance_state.assert_distribution(&final_balance_state, &rewards);
+		System::assert_last_event(mock::Event::BlockReward(Event::ProviderRewardRedirected {
+			amount: config.providers_percent * BLOCK_REWARD,
+		}));
+	})
+}
+
+#[test]
+pub fn chain_reward_multiplier_is_applied() {
+	ExternalityBuilder::build().execute_with(|| {
+		ChainProviders::set(vec![(b"eth.mainnet".to_vec(), 5), (b"dot.mainnet".to_vec(), 2)]);
+		let config = RewardConfig::<TestRuntime>::get();
+		let multiplier = Perbill::from_percent(50);
+
+		assert_noop!(
+			BlockReward::set_chain_reward_multiplier(
+				Origin::signed(1),
+				b"eth.mainnet".to_vec(),
+				multiplier
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			BlockReward::set_chain_reward_multiplier(Origin::root(), vec![0; 17], multiplier),
+			Error::<TestRuntime>::ChainIdTooLong
+		);
+		// Multiplier of a chain without the most providers is ignored
+		assert_ok!(BlockReward::set_chain_reward_multiplier(
+			Origin::root(),
+			b"dot.mainnet".to_vec(),
+			Perbill::zero()
+		));
+		assert_ok!(BlockReward::set_chain_reward_multiplier(
+			Origin::root(),
+			b"eth.mainnet".to_vec(),
+			multiplier
+		));
+
+		let providers_reward = multiplier * (config.providers_percent * BLOCK_REWARD);
+		let simulation = BlockReward::simulate_reward_distribution(&config);
+		assert_eq!(simulation.providers_reward, providers_reward);
+
+		let init_balance_state = FreeBalanceSnapshot::new();
+		BlockReward::on_timestamp_set(0);
+		let final_balance_state = FreeBalanceSnapshot::new();
+
+		let rewards = Rewards {
+			validators_reward: BLOCK_REWARD - providers_reward,
+			providers_reward,
+			treasury_reward: Zero::zero(),
+		};
+		init_balance_state.assert_distribution(&final_balance_state, &rewards);
+		System::assert_last_event(mock::Event::BlockReward(Event::ChainRewardMultiplierApplied {
+			chain_id: b"eth.mainnet".to_vec(),
+			multiplier,
+		}));
+	})
+}
+
+#[test]
+pub fn block_reward_equals_reward_amount_at_target_stake() {
+	ExternalityBuilder::build().execute_with(|| {
+		let target_stake = TargetStakeRatio::get() * Balances::total_issuance();
+		StakedAmount::set(Some(target_stake));
+		assert_eq!(BlockReward::block_reward_amount(), BLOCK_REWARD);
+
+		let init_issuance = Balances::total_issuance();
+		BlockReward::on_timestamp_set(0);
+		assert_eq!(Balances::total_issuance(), init_issuance + BLOCK_REWARD);
+	})
+}
+
+#[test]
+pub fn block_reward_scaled_by_stake_ratio() {
+	ExternalityBuilder::build().execute_with(|| {
+		let target_stake = TargetStakeRatio::get() * Balances::total_issuance();
+
+		// Twice the target halves the reward
+		StakedAmount::set(Some(target_stake * 2));
+		assert_eq!(BlockReward::block_reward_amount(), BLOCK_REWARD / 2);
+
+		// Half the target doubles the reward
+		StakedAmount::set(Some(target_stake / 2));
+		assert_eq!(BlockReward::block_reward_amount(), BLOCK_REWARD * 2);
+
+		// Reward is capped by the max multiplier
+		let max_reward = BLOCK_REWARD * MaxRewardMultiplier::get() as Balance;
+		StakedAmount::set(Some(target_stake / 10));
+		assert_eq!(BlockReward::block_reward_amount(), max_reward);
+		StakedAmount::set(Some(Zero::zero()));
+		assert_eq!(BlockReward::block_reward_amount(), max_reward);
+
+		let simulation =
+			BlockReward::simulate_reward_distribution(&RewardConfig::<TestRuntime>::get());
+		assert_eq!(simulation.validators_reward + simulation.providers_reward, max_reward);
+
+		let init_issuance = Balances::total_issuance();
+		BlockReward::on_timestamp_set(0);
+		assert_eq!(Balances::total_issuance(), init_issuance + max_reward);
+	})
+}
+
+/// Distribution config where providers get `providers_percent` and validators the rest.
+fn config_with_providers_percent(providers_percent: u32) -> DistributionConfig {
+	DistributionConfig {
+		providers_percent: Perbill::from_percent(providers_percent),
+		validators_percent: Perbill::from_percent(100 - providers_percent),
+		community_percent: Zero::zero(),
+		treasury_percent: Zero::zero(),
+	}
+}
+
+#[test]
+pub fn config_history_lookup_as_expected() {
+	ExternalityBuilder::build().execute_with(|| {
+		let initial_config = RewardConfig::<TestRuntime>::get();
+		let config_a = config_with_providers_percent(60);
+		let config_b = config_with_providers_percent(70);
+		let config_c = config_with_providers_percent(80);
+
+		System::set_block_number(3);
+		assert_ok!(BlockReward::set_config(Origin::root(), config_a.clone()));
+		assert_eq!(ConfigHistory::<TestRuntime>::get(3), Some(initial_config.clone()));
+
+		System::set_block_number(5);
+		assert_ok!(BlockReward::set_config(Origin::root(), config_b.clone()));
+		// Config B is never active, so it isn't recorded
+		assert_ok!(BlockReward::set_config(Origin::root(), config_c.clone()));
+		assert_eq!(ConfigHistory::<TestRuntime>::get(5), Some(config_a.clone()));
+
+		assert_eq!(BlockReward::config_at_block(1), Some(initial_config.clone()));
+		assert_eq!(BlockReward::config_at_block(3), Some(initial_config));
+		assert_eq!(BlockReward::config_at_block(4), Some(config_a.clone()));
+		assert_eq!(BlockReward::config_at_block(5), Some(config_a));
+		assert_eq!(BlockReward::config_at_block(6), None);
+
+		System::set_block_number(7);
+		assert_eq!(BlockReward::config_at_block(6), Some(config_c.clone()));
+		assert_eq!(BlockReward::config_at_block(7), Some(config_c));
+	})
+}
+
+#[test]
+pub fn config_history_is_bounded() {
+	ExternalityBuilder::build().execute_with(|| {
+		let max_history = <TestRuntime as Config>::MaxConfigHistory::get() as u64;
+
+		for block in 2..=max_history + 2 {
+			System::set_block_number(block);
+			assert_ok!(BlockReward::set_config(
+				Origin::root(),
+				config_with_providers_percent(block as u32)
+			));
+		}
+
+		assert_eq!(ConfigHistory::<TestRuntime>::iter().count() as u64, max_history);
+		assert!(!ConfigHistory::<TestRuntime>::contains_key(2));
+		assert!(ConfigHistory::<TestRuntime>::contains_key(max_history + 2));
+		// Configs active up to the evicted entry are no longer known
+		assert_eq!(BlockReward::config_at_block(2), None);
+		assert_eq!(BlockReward::config_at_block(3), Some(config_with_providers_percent(2)));
+	})
+}
+
+#[test]
+pub fn prune_config_history_as_expected() {
+	ExternalityBuilder::build().execute_with(|| {
+		for block in 2..=4 {
+			System::set_block_number(block);
+			assert_ok!(BlockReward::set_config(
+				Origin::root(),
+				config_with_providers_percent(block as u32)
+			));
+		}
+
+		assert_noop!(BlockReward::prune_config_history(Origin::signed(1), 4), BadOrigin);
+
+		assert_ok!(BlockReward::prune_config_history(Origin::root(), 4));
+		assert!(!ConfigHistory::<TestRuntime>::contains_key(2));
+		assert!(!ConfigHistory::<TestRuntime>::contains_key(3));
+		assert_eq!(ConfigHistory::<TestRuntime>::get(4), Some(config_with_providers_percent(3)));
+		assert_eq!(BlockReward::config_at_block(3), None);
+		assert_eq!(BlockReward::config_at_block(4), Some(config_with_providers_percent(3)));
+	})
+}
+
+#[test]
+pub fn migrate_distribution_config_to_v1_is_ok() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		// Simulate the layout without community percentage
+		let old = (Perbill::from_percent(60), Perbill::from_percent(40));
+		unhashed::put_raw(&RewardConfig::<TestRuntime>::hashed_key(), &old.encode());
+		StorageVersion::new(0).put::<BlockReward>();
+
+		migrations::MigrateDistributionConfigToV1::<TestRuntime>::on_runtime_upgrade();
+		migrations::MigrateDistributionConfigToV2::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(BlockReward::reward_config(), config_with_providers_percent(60));
+		assert_eq!(BlockReward::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+pub fn migrate_distribution_config_to_v2_is_ok() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		// Simulate the layout without treasury percentage
+		let old = (Perbill::from_percent(50), Perbill::from_percent(40), Perbill::from_percent(10));
+		unhashed::put_raw(&RewardConfig::<TestRuntime>::hashed_key(), &old.encode());
+		unhashed::put_raw(&ConfigHistory::<TestRuntime>::hashed_key_for(3), &old.encode());
+		StorageVersion::new(1).put::<BlockReward>();
+
+		migrations::MigrateDistributionConfigToV2::<TestRuntime>::on_runtime_upgrade();
+
+		let config = DistributionConfig {
+			providers_percent: Perbill::from_percent(50),
+			validators_percent: Perbill::from_percent(40),
+			community_percent: Perbill::from_percent(10),
+			treasury_percent: Zero::zero(),
+		};
+		assert_eq!(BlockReward::reward_config(), config);
+		assert_eq!(BlockReward::config_history(3), Some(config));
+		assert_eq!(BlockReward::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+pub fn migrate_distribution_config_to_v2_resets_undecodable_config() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		unhashed::put_raw(&RewardConfig::<TestRuntime>::hashed_key(), &[1, 2, 3]);
+		StorageVersion::new(1).put::<BlockReward>();
+
+		migrations::MigrateDistributionConfigToV2::<TestRuntime>::on_runtime_upgrade();
+
+		assert!(!RewardConfig::<TestRuntime>::exists());
+		assert_eq!(BlockReward::reward_config(), DistributionConfig::default());
+	})
+}
+
 /// Represents free balance snapshot at a specific point in time
 #[derive(PartialEq, Eq, Clone, RuntimeDebug)]
 struct FreeBalanceSnapshot {
 	validators: Balance,
 	providers: Balance,
+	treasury: Balance,
 }
 
 impl FreeBalanceSnapshot {
@@ -133,6 +568,9 @@ impl FreeBalanceSnapshot {
 			providers: <TestRuntime as Config>::Currency::free_balance(
 				&PROVIDER_POT.into_account_truncating(),
 			),
+			treasury: <TestRuntime as Config>::Currency::free_balance(
+				&TREASURY_POT.into_account_truncating(),
+			),
 		}
 	}
 
@@ -145,6 +583,7 @@ impl FreeBalanceSnapshot {
 	fn assert_distribution(&self, post_reward_state: &Self, rewards: &Rewards) {
 		assert_eq!(self.validators + rewards.validators_reward, post_reward_state.validators);
 		assert_eq!(self.providers + rewards.providers_reward, post_reward_state.providers);
+		assert_eq!(self.treasury + rewards.treasury_reward, post_reward_state.treasury);
 	}
 }
 
@@ -153,6 +592,7 @@ impl FreeBalanceSnapshot {
 struct Rewards {
 	validators_reward: Balance,
 	providers_reward: Balance,
+	treasury_reward: Balance,
 }
 
 impl Rewards {
@@ -161,6 +601,7 @@ impl Rewards {
 	fn calculate(config: &DistributionConfig) -> Self {
 		let validators_reward = config.validators_percent * BLOCK_REWARD;
 		let providers_reward = config.providers_percent * BLOCK_REWARD;
-		Self { validators_reward, providers_reward }
+		let treasury_reward = config.treasury_percent * BLOCK_REWARD;
+		Self { validators_reward, providers_reward, treasury_reward }
 	}
 }
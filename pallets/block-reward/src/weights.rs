@@ -39,26 +39,126 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_block_reward.
 pub trait WeightInfo {
 	#[rustfmt::skip]
-	fn set_config() -> Weight;
+	fn set_config(h: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn set_community_beneficiaries(n: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn prune_config_history(h: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn set_chain_reward_multiplier() -> Weight;
+	#[rustfmt::skip]
+	fn on_timestamp_set() -> Weight;
 }
 
 /// Weights for pallet_block_reward using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	// Storage: BlockReward RewardConfig (r:0 w:1)
+	// Storage: BlockReward RewardConfig (r:1 w:1)
+	// Storage: BlockReward ConfigHistory (r:1 w:2)
+	// Storage: BlockReward ConfigHistoryStart (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_config(h: u32, ) -> Weight {
+		(16_420_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_214_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: BlockReward CommunityBeneficiaries (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_community_beneficiaries(n: u32, ) -> Weight {
+		(10_326_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((21_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: BlockReward ConfigHistory (r:1 w:1)
+	// Storage: BlockReward ConfigHistoryStart (r:0 w:1)
+	#[rustfmt::skip]
+	fn prune_config_history(h: u32, ) -> Weight {
+		(6_891_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((2_437_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	// Storage: BlockReward ChainRewardMultiplier (r:0 w:1)
 	#[rustfmt::skip]
-	fn set_config() -> Weight {
-		(9_000_000 as Weight)
+	fn set_chain_reward_multiplier() -> Weight {
+		(11_204_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Balances TotalIssuance (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: BlockReward RewardConfig (r:1 w:0)
+	// Storage: BlockReward CommunityBeneficiaries (r:1 w:0)
+	// Storage: DapiStaking ActiveProviders (r:1 w:0)
+	// Storage: Dapi MostStakedChain (r:1 w:0)
+	// Storage: BlockReward ChainRewardMultiplier (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	#[rustfmt::skip]
+	fn on_timestamp_set() -> Weight {
+		(40_152_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	// Storage: BlockReward RewardConfig (r:0 w:1)
+	// Storage: BlockReward RewardConfig (r:1 w:1)
+	// Storage: BlockReward ConfigHistory (r:1 w:2)
+	// Storage: BlockReward ConfigHistoryStart (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_config(h: u32, ) -> Weight {
+		(16_420_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_214_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: BlockReward CommunityBeneficiaries (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_community_beneficiaries(n: u32, ) -> Weight {
+		(10_326_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((21_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: BlockReward ConfigHistory (r:1 w:1)
+	// Storage: BlockReward ConfigHistoryStart (r:0 w:1)
+	#[rustfmt::skip]
+	fn prune_config_history(h: u32, ) -> Weight {
+		(6_891_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((2_437_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	// Storage: BlockReward ChainRewardMultiplier (r:0 w:1)
 	#[rustfmt::skip]
-	fn set_config() -> Weight {
-		(9_000_000 as Weight)
+	fn set_chain_reward_multiplier() -> Weight {
+		(11_204_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Balances TotalIssuance (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: BlockReward RewardConfig (r:1 w:0)
+	// Storage: BlockReward CommunityBeneficiaries (r:1 w:0)
+	// Storage: DapiStaking ActiveProviders (r:1 w:0)
+	// Storage: Dapi MostStakedChain (r:1 w:0)
+	// Storage: BlockReward ChainRewardMultiplier (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	#[rustfmt::skip]
+	fn on_timestamp_set() -> Weight {
+		(40_152_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 }
\ No newline at end of file
@@ -0,0 +1,110 @@
+//! RPC interface for the dAPI staking pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_dapi_staking::runtime_api::DapiStakingApi as DapiStakingRuntimeApi;
+
+#[rpc(client, server)]
+pub trait DapiStakingApi<BlockHash, AccountId, Balance> {
+	/// Returns the total stake locked on the SCALE encoded `provider_id` in the current era.
+	#[method(name = "dapiStaking_providerTvl")]
+	fn provider_tvl(&self, provider_id: Bytes, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+	/// Returns the total stake locked on all providers in the current era.
+	#[method(name = "dapiStaking_totalTvl")]
+	fn total_tvl(&self, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+	/// Returns the sum of the latest stakes of `delegator` over all providers.
+	#[method(name = "dapiStaking_delegatorTotalStaked")]
+	fn delegator_total_staked(
+		&self,
+		delegator: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Balance>;
+}
+
+/// Provides RPC methods to query dAPI staking data.
+pub struct DapiStaking<C, Block, ProviderId, BlockNumber> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, ProviderId, BlockNumber)>,
+}
+
+impl<C, Block, ProviderId, BlockNumber> DapiStaking<C, Block, ProviderId, BlockNumber> {
+	/// Creates a new instance of the DapiStaking RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code of a failed runtime call.
+const RUNTIME_ERROR: i32 = 1;
+/// Error code of a provider id that can't be decoded.
+const DECODE_ERROR: i32 = 2;
+
+fn runtime_error(message: &str, e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(e.to_string()))).into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, ProviderId, Balance, BlockNumber>
+	DapiStakingApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for DapiStaking<C, Block, ProviderId, BlockNumber>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DapiStakingRuntimeApi<Block, AccountId, ProviderId, Balance, BlockNumber>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	ProviderId: Codec + Send + Sync + 'static,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+{
+	fn provider_tvl(
+		&self,
+		provider_id: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let provider_id = ProviderId::decode(&mut &provider_id[..]).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				DECODE_ERROR,
+				"Unable to decode provider id.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		api.provider_tvl(&at, provider_id)
+			.map_err(|e| runtime_error("Unable to query provider TVL.", e))
+	}
+
+	fn total_tvl(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.total_tvl(&at).map_err(|e| runtime_error("Unable to query total TVL.", e))
+	}
+
+	fn delegator_total_staked(
+		&self,
+		delegator: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.delegator_total_staked(&at, delegator)
+			.map_err(|e| runtime_error("Unable to query delegator stake.", e))
+	}
+}
@@ -3,14 +3,22 @@
 use super::*;
 use crate::{traits::DapiStakingRegistration, types::*, Pallet};
 
+use codec::{Decode, Encode};
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::traits::{Currency, Get, OnInitialize};
+use frame_support::{
+	traits::{Currency, Get, LockableCurrency, OnInitialize, WithdrawReasons},
+	BoundedVec,
+};
 use frame_system::{Pallet as System, RawOrigin};
-use sp_runtime::traits::{Bounded, One, Zero};
-use sp_std::vec::Vec;
+use sp_runtime::{
+	traits::{Bounded, One, TrailingZeroInput, Zero},
+	Perbill,
+};
+use sp_std::{vec, vec::Vec};
 
 const SEED: u32 = 9000;
 const BLOCK_REWARD: u32 = 1000;
+const MAX_ACTIVE_PROVIDERS: u32 = 100;
 
 /// Used to prepare dAPI staking for testing.
 /// Resets all existing storage ensuring a clean run for the code that follows.
@@ -23,6 +31,13 @@ fn initialize<T: Config>() {
 	ProviderEraInfo::<T>::remove_all(None);
 	DelegationInfo::<T>::remove_all(None);
 	UnbondingInfo::<T>::remove_all(None);
+	ActiveProviders::<T>::kill();
+	EraActiveProviderCount::<T>::remove_all(None);
+	EraProviders::<T>::remove_all(None);
+	EraProviderCount::<T>::remove_all(None);
+	ExpiryCursor::<T>::kill();
+	PendingTransfers::<T>::remove_all(None);
+	PendingCommissions::<T>::remove_all(None);
 }
 
 /// Payout block rewards
@@ -51,7 +66,12 @@ fn register_provider<T: Config>() -> Result<(T::AccountId, T::ProviderId), &'sta
 	let owner: T::AccountId = account("owner", 10000, SEED);
 	T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value());
 	let provider_id = T::ProviderId::default();
-	Pallet::<T>::register_provider(owner.clone(), provider_id.clone(), T::MinProviderStake::get())?;
+	Pallet::<T>::register_provider(
+		owner.clone(),
+		provider_id.clone(),
+		T::MinProviderStake::get(),
+		T::MinProviderStake::get(),
+	)?;
 	Ok((owner, provider_id))
 }
 
@@ -78,15 +98,80 @@ fn prepare_delegate<T: Config>(
 }
 
 benchmarks! {
+	on_initialize_new_era {
+		let n in 0 .. MAX_ACTIVE_PROVIDERS;
+
+		initialize::<T>();
+		advance_to_era::<T>(1);
+		for id in 0..n {
+			let owner: T::AccountId = account("owner", id, SEED);
+			T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value());
+			let provider_id = T::ProviderId::decode(&mut TrailingZeroInput::new(&id.encode()))
+				.map_err(|_| "provider id can't be created")?;
+			Pallet::<T>::register_provider(owner, provider_id, T::MinProviderStake::get(), T::MinProviderStake::get())?;
+		}
+		let era = Pallet::<T>::era().current;
+		ForceNewEra::<T>::put(true);
+
+	}: { Pallet::<T>::on_initialize(System::<T>::block_number()); }
+	verify {
+		assert_eq!(Pallet::<T>::era().current, era + 1);
+		for provider_id in ActiveProviders::<T>::get() {
+			assert!(ProviderEraInfo::<T>::contains_key(&provider_id, era + 1));
+		}
+	}
+
 	set_blocks_per_era {}: _(RawOrigin::Root, 1200u32)
 	verify {
 		assert_eq!(Pallet::<T>::era().length, 1200u32);
 	}
 
+	force_new_era {
+		let origin = T::ForceEraOrigin::successful_origin();
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(ForceNewEra::<T>::get());
+	}
+
+	force_new_era_at {
+		let origin = T::ForceEraOrigin::successful_origin();
+		let block_number = System::<T>::block_number() + 10u32.into();
+	}: _<T::Origin>(origin, block_number)
+	verify {
+		assert_eq!(NextEraStartBlock::<T>::get(), Some(block_number));
+	}
+
+	set_era_stake_values_limit {
+		let new_limit = T::DefaultMaxEraStakeValues::get() + 1;
+	}: _(RawOrigin::Root, new_limit)
+	verify {
+		assert_eq!(Pallet::<T>::era_stake_values_limit(), new_limit);
+	}
+
+	register_provider_batch {
+		let n in 1 .. T::MaxBatchRegistrations::get();
+
+		initialize::<T>();
+		let mut registrations = Vec::new();
+		for id in 0..n {
+			let owner: T::AccountId = account("owner", id, SEED);
+			T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value());
+			let provider_id = T::ProviderId::decode(&mut TrailingZeroInput::new(&id.encode()))
+				.map_err(|_| "provider id can't be created")?;
+			registrations.push((owner, provider_id, T::MinProviderStake::get()));
+		}
+		let registrations: BoundedVec<_, T::MaxBatchRegistrations> =
+			registrations.try_into().map_err(|_| "too many registrations")?;
+
+	}: _(RawOrigin::Root, registrations)
+	verify {
+		assert_eq!(Pallet::<T>::active_provider_count(), n);
+	}
+
 	provider_bond_more {
 		initialize::<T>();
 		let (owner, provider_id) = register_provider::<T>()?;
-		let amount = BalanceOf::<T>::max_value() / 2u32.into();
+		let amount = T::MaxProviderBond::get() - T::MinProviderStake::get();
 
 	}: _(RawOrigin::Signed(owner.clone()), provider_id.clone(), amount)
 	verify {
@@ -96,7 +181,7 @@ benchmarks! {
 	provider_bond_less {
 		initialize::<T>();
 		let (owner, provider_id) = register_provider::<T>()?;
-		let amount = BalanceOf::<T>::max_value() / 2u32.into();
+		let amount = T::MaxProviderBond::get() - T::MinProviderStake::get();
 		Pallet::<T>::provider_bond_more(RawOrigin::Signed(owner.clone()).into(), provider_id.clone(), amount)?;
 
 	}: _(RawOrigin::Signed(owner.clone()), provider_id.clone(), amount)
@@ -136,6 +221,27 @@ benchmarks! {
 		assert_last_event::<T>(Event::<T>::DelegatorUnstaked{delegator, provider_id, amount}.into());
 	}
 
+	redelegate {
+		initialize::<T>();
+
+		let (_, from_provider_id) = register_provider::<T>()?;
+		let to_owner: T::AccountId = account("owner", 1, SEED);
+		T::Currency::make_free_balance_be(&to_owner, BalanceOf::<T>::max_value());
+		let to_provider_id = T::ProviderId::decode(&mut TrailingZeroInput::new(&1u32.encode()))
+			.map_err(|_| "provider id can't be created")?;
+		Pallet::<T>::register_provider(to_owner, to_provider_id.clone(), T::MinProviderStake::get(), T::MinProviderStake::get())?;
+		prepare_delegate::<T>(T::MaxDelegatorsPerProvider::get() - 1, &to_provider_id, SEED)?;
+
+		let delegator = whitelisted_caller();
+		let _ = T::Currency::make_free_balance_be(&delegator, BalanceOf::<T>::max_value());
+		let amount = BalanceOf::<T>::max_value() / 2u32.into();
+		Pallet::<T>::delegate(RawOrigin::Signed(delegator.clone()).into(), from_provider_id.clone(), amount)?;
+
+	}: _(RawOrigin::Signed(delegator.clone()), from_provider_id, to_provider_id.clone(), amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::Delegated{delegator, provider_id: to_provider_id, amount}.into());
+	}
+
 	withdraw_unbonded {
 		initialize::<T>();
 
@@ -186,6 +292,100 @@ benchmarks! {
 		let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
 		let (era, _) = delegation.claim();
 		assert!(era > claim_era);
+
+		let provider_era_info = <ProviderEraInfo<T>>::get(&provider_id, claim_era).unwrap();
+		let era_info = <EraState<T>>::get(claim_era).unwrap();
+		let (_, total_pool) =
+			Pallet::<T>::split_provider_delegators_rewards(provider_era_info.commission, &provider_era_info, &era_info);
+		let share = Perbill::from_rational(1u32, delegator_count);
+		assert_last_event::<T>(Event::<T>::DelegatorPayout {
+			who: delegator,
+			provider_id,
+			era: claim_era,
+			amount: share * total_pool,
+			share,
+			total_pool,
+		}.into());
+	}
+
+	claim_delegator_batch {
+		let n in 1 .. T::DefaultMaxEraStakeValues::get();
+		initialize::<T>();
+		let (_, provider_id) = register_provider::<T>()?;
+
+		let claim_era = <Era<T>>::get().current;
+		let delegators = prepare_delegate::<T>(1, &provider_id, SEED)?;
+		let delegator = delegators[0].clone();
+		advance_to_era::<T>(claim_era + n);
+
+	}: _(RawOrigin::Signed(delegator.clone()), provider_id.clone(), n)
+	verify {
+		let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+		let (era, _) = delegation.claim();
+		assert_eq!(era, claim_era + n);
+	}
+
+	compound_delegator_reward {
+		initialize::<T>();
+		let (_, provider_id) = register_provider::<T>()?;
+
+		let claim_era = <Era<T>>::get().current;
+		let delegators = prepare_delegate::<T>(1, &provider_id, SEED)?;
+		let delegator = delegators[0].clone();
+		let staked = <DelegationInfo<T>>::get(&delegator, &provider_id).latest_staked_value();
+		advance_to_era::<T>(claim_era + 1u32);
+
+	}: _(RawOrigin::Signed(delegator.clone()), provider_id.clone())
+	verify {
+		let delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+		assert!(delegation.latest_staked_value() > staked);
+	}
+
+	reset_provider_reward_stats {
+		let provider_id = T::ProviderId::default();
+		ProviderTotalRewardsClaimed::<T>::insert(&provider_id, BalanceOf::<T>::max_value());
+
+	}: _(RawOrigin::Root, provider_id.clone())
+	verify {
+		assert!(Pallet::<T>::provider_total_rewards_claimed(&provider_id).is_zero());
+	}
+
+	fix_delegation_info {
+		let provider_id = T::ProviderId::default();
+		let delegator: T::AccountId = account("delegator", 0, SEED);
+		let stakes = vec![
+			EraStake::new(Zero::zero(), 1),
+			EraStake::new(T::MinDelegatorStake::get(), 2),
+		];
+		DelegationInfo::<T>::insert(&delegator, &provider_id, Delegation::from_stakes(stakes));
+
+	}: _(RawOrigin::Root, delegator.clone(), provider_id.clone())
+	verify {
+		let delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+		assert_eq!(delegation.oldest_claimable_era(), Some(2));
+	}
+
+	prune_old_era {
+		let n in 1 .. T::MaxProvidersPerEraClean::get();
+
+		initialize::<T>();
+		advance_to_era::<T>(1);
+		for id in 0..n {
+			let owner: T::AccountId = account("owner", id, SEED);
+			T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value());
+			let provider_id = T::ProviderId::decode(&mut TrailingZeroInput::new(&id.encode()))
+				.map_err(|_| "provider id can't be created")?;
+			Pallet::<T>::register_provider(owner, provider_id, T::MinProviderStake::get(), T::MinProviderStake::get())?;
+		}
+		let era = Pallet::<T>::era().current;
+		advance_to_era::<T>(era + T::UnbondingPeriod::get().max(T::MaxClaimableEraAge::get()) + 1);
+		// Expiry of the era rewards might span several blocks
+		ExpiryCursor::<T>::put((era + 1, 0));
+
+	}: _(RawOrigin::Root, era)
+	verify {
+		assert_last_event::<T>(Event::<T>::EraPruned{era, providers_cleaned: n}.into());
+		assert!(EraState::<T>::get(era).is_none());
 	}
 
 	provider_withdraw_unregistered {
@@ -219,6 +419,93 @@ benchmarks! {
 		assert!(delegation.latest_staked_value().is_zero());
 		assert_last_event::<T>(Event::<T>::Withdrawn{who: delegator, amount: T::MinDelegatorStake::get()}.into());
 	}
+
+	accelerate_provider_withdrawal {
+		initialize::<T>();
+		let (owner, provider_id) = register_provider::<T>()?;
+		Pallet::<T>::unregister_provider(provider_id.clone())?;
+
+	}: _(RawOrigin::Root, provider_id.clone())
+	verify {
+		let provider = <ProviderInfo<T>>::get(&provider_id).unwrap();
+		assert!(provider.bond_withdrawn);
+		assert_last_event::<T>(Event::<T>::Withdrawn{who: owner, amount: T::MinProviderStake::get()}.into());
+	}
+
+	accelerate_delegator_withdrawal {
+		initialize::<T>();
+		let (_, provider_id) = register_provider::<T>()?;
+		let delegators = prepare_delegate::<T>(1, &provider_id, SEED)?;
+		let delegator = delegators[0].clone();
+		Pallet::<T>::unregister_provider(provider_id.clone())?;
+
+	}: _(RawOrigin::Root, delegator.clone(), provider_id.clone())
+	verify {
+		let delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+		assert!(delegation.latest_staked_value().is_zero());
+		assert_last_event::<T>(Event::<T>::Withdrawn{who: delegator, amount: T::MinDelegatorStake::get()}.into());
+	}
+
+	initiate_provider_transfer {
+		initialize::<T>();
+		let (owner, provider_id) = register_provider::<T>()?;
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+
+	}: _(RawOrigin::Signed(owner), provider_id.clone(), new_owner.clone())
+	verify {
+		assert_eq!(PendingTransfers::<T>::get(&provider_id).map(|(owner, _)| owner), Some(new_owner));
+	}
+
+	accept_provider_transfer {
+		initialize::<T>();
+		let (owner, provider_id) = register_provider::<T>()?;
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+		T::Currency::make_free_balance_be(&new_owner, BalanceOf::<T>::max_value() / 2u32.into());
+		Pallet::<T>::initiate_provider_transfer(RawOrigin::Signed(owner.clone()).into(), provider_id.clone(), new_owner.clone())?;
+
+	}: _(RawOrigin::Signed(new_owner.clone()), provider_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderTransferred{provider_id, old_owner: owner, new_owner}.into());
+	}
+
+	cancel_provider_transfer {
+		initialize::<T>();
+		let (owner, provider_id) = register_provider::<T>()?;
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+		Pallet::<T>::initiate_provider_transfer(RawOrigin::Signed(owner.clone()).into(), provider_id.clone(), new_owner)?;
+
+	}: _(RawOrigin::Signed(owner), provider_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderTransferCancelled{provider_id}.into());
+	}
+
+	set_provider_commission {
+		initialize::<T>();
+		let (owner, provider_id) = register_provider::<T>()?;
+		let commission = Perbill::from_percent(50);
+
+	}: _(RawOrigin::Signed(owner), provider_id.clone(), commission)
+	verify {
+		assert_eq!(PendingCommissions::<T>::get(&provider_id).map(|(commission, _)| commission), Some(commission));
+	}
+
+	unlock_vested_rewards {
+		let s in 1 .. T::MaxVestingSchedules::get();
+		let delegator: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&delegator, BalanceOf::<T>::max_value() / 2u32.into());
+		let locked = T::MinDelegatorStake::get();
+		let vesting = RewardVestingInfo::new(locked, 10u32.into(), System::<T>::block_number());
+		let schedules: BoundedVec<_, T::MaxVestingSchedules> = vec![vesting; s as usize]
+			.try_into()
+			.map_err(|_| "too many vesting schedules")?;
+		T::Currency::set_lock(REWARD_VESTING_ID, &delegator, locked.saturating_mul(s.into()), WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+		RewardVesting::<T>::insert(&delegator, schedules);
+		System::<T>::set_block_number(vesting.end_block());
+
+	}: _(RawOrigin::Signed(delegator.clone()))
+	verify {
+		assert!(RewardVesting::<T>::get(&delegator).is_empty());
+	}
 }
 
 #[cfg(test)]
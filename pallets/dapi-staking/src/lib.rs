@@ -21,14 +21,24 @@
 //!
 //! - `set_blocks_per_era` - changes number of blocks per era configuration, can only be called by
 //!   root or governance
+//! - `set_era_stake_values_limit` - changes max number of `EraStake` values per delegation, can
+//!   only be called by root or governance
 //! - `provider_bond_more` - used by operators to stake more tokens to their own providers
 //! - `provider_bond_less` - used by operators to unstake tokens to their own providers
 //! - `delegate` - delegates a provider and locks stakers tokens into staking
 //! - `delegator_unstake` - removes delegation from the provider, starting the unbonding process for
 //!   the unstaked funds
+//! - `delegator_unstake_all` - removes the whole delegation from the provider
+//! - `redelegate` - moves delegated funds from one provider to another without unbonding them
 //! - `withdraw_unbonded` - withdraws all funds that have completed the unbonding period
 //! - `claim_provider` - claims operator reward for a single era
 //! - `claim_delegator` - claims delegator reward for a single era
+//! - `claim_delegator_batch` - claims delegator rewards for several eras at once
+//! - `compound_delegator_reward` - claims delegator reward for a single era and delegates it to the
+//!   same provider
+//! - `unlock_vested_rewards` - unlocks the claimed delegator rewards which vested so far
+//! - `prune_old_era` - removes the era state and provider era info of an old era, can only be
+//!   called by root or governance
 //! - `provider_withdraw_unregistered` - used by operators to withdraw their stake from unregistered
 //!   provider
 //! - `delegator_withdraw_unregistered` - used by delegators to withdraw their stake from
@@ -40,9 +50,18 @@
 //!   advancement
 //! - `account_id` - returns pallet's account Id
 //! - `handle_imbalance` - used to deposit delegators and providers rewards into staking reward pool
+//!
+//! ### Features
+//!
+//! - `impl-staking-interface` - implements `sp_staking::StakingInterface` for the pallet
+//! - `claim-history` - records the last `MaxClaimHistory` claimed delegator rewards in
+//!   `ClaimHistory`
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod migrations;
+pub mod offchain;
+pub mod runtime_api;
 pub mod traits;
 pub mod types;
 pub mod weights;
@@ -51,6 +70,8 @@ pub mod weights;
 pub mod benchmarks;
 #[cfg(test)]
 mod mock;
+#[cfg(feature = "impl-staking-interface")]
+mod staking_interface;
 #[cfg(test)]
 mod tests;
 
@@ -60,22 +81,27 @@ pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use crate::{traits::DapiStakingRegistration, types::*, WeightInfo};
+	use crate::{
+		traits::{DapiStakingRegistration, ProviderScoreInfo, MAX_PROVIDER_SCORE},
+		types::*,
+		WeightInfo,
+	};
 	use frame_support::{
 		ensure,
 		pallet_prelude::*,
 		traits::{
-			Currency, ExistenceRequirement, Get, Imbalance, ReservableCurrency, WithdrawReasons,
+			BalanceStatus, Currency, ExistenceRequirement, Get, Imbalance, LockIdentifier,
+			LockableCurrency, OnUnbalanced, ReservableCurrency, WithdrawReasons,
 		},
 		weights::Weight,
 		PalletId,
 	};
 	use frame_system::{ensure_signed, pallet_prelude::*};
 	use sp_runtime::{
-		traits::{AccountIdConversion, CheckedAdd, Saturating, Zero},
+		traits::{AccountIdConversion, CheckedAdd, One, SaturatedConversion, Saturating, Zero},
 		ArithmeticError, Perbill,
 	};
-	use sp_std::convert::From;
+	use sp_std::{collections::btree_set::BTreeSet, convert::From, vec::Vec};
 
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -83,7 +109,17 @@ pub mod pallet {
 		<T as frame_system::Config>::AccountId,
 	>>::NegativeImbalance;
 
+	/// Min number of `EraStake` values per delegation needed for correct operation.
+	pub const MIN_ERA_STAKE_VALUES: u32 = 2;
+
+	/// Identifier of the lock on vesting delegator rewards.
+	pub const REWARD_VESTING_ID: LockIdentifier = *b"dapivest";
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -93,23 +129,29 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The balance type of pallet.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>
+			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 
 		/// Default number of blocks per era at genesis
 		#[pallet::constant]
 		type DefaultBlocksPerEra: Get<u32>;
 
 		/// Provider Id type.
-		type ProviderId: Parameter + Member + Default;
+		type ProviderId: Parameter + Member + Default + Ord;
 
-		/// Percentage of rewards paid to provider.
+		/// Percentage of rewards paid to provider, used as the commission of new providers.
 		#[pallet::constant]
 		type ProviderRewardsPercentage: Get<Perbill>;
 
-		/// Minimum stake required to be a provider.
+		/// Minimum bond of providers registered in a batch, and the lowest bond a provider can
+		/// bond less down to.
 		#[pallet::constant]
 		type MinProviderStake: Get<BalanceOf<Self>>;
 
+		/// Maximum bond a provider can have.
+		#[pallet::constant]
+		type MaxProviderBond: Get<BalanceOf<Self>>;
+
 		/// Maximum number of unique delegators per provider.
 		#[pallet::constant]
 		type MaxDelegatorsPerProvider: Get<u32>;
@@ -118,15 +160,25 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinDelegatorStake: Get<BalanceOf<Self>>;
 
-		/// Max number of unique `EraStake` values that can exist for a `(delegator, provider)`
-		/// pairing. When delegators claim rewards, they will either keep the number of
-		/// `EraStake` values the same or they will reduce them by one. Delegators cannot add
-		/// an additional `EraStake` value by calling `delegate` or `delegator_unstake` if
-		/// they've reached the max number of values. This ensures that history doesn't grow
-		/// indefinitely - if there are too many chunks, delegators should first claim their former
-		/// rewards before adding additional `EraStake` values.
+		/// Maximum number of distinct providers a single delegator can stake on.
 		#[pallet::constant]
-		type MaxEraStakeValues: Get<u32>;
+		type MaxDelegationsPerDelegator: Get<u32>;
+
+		/// Default max number of unique `EraStake` values that can exist for a `(delegator,
+		/// provider)` pairing, used until changed by `set_era_stake_values_limit`. When delegators
+		/// claim rewards, they will either keep the number of `EraStake` values the same or they
+		/// will reduce them by one. Delegators cannot add an additional `EraStake` value by calling
+		/// `delegate` or `delegator_unstake` if they've reached the max number of values. This
+		/// ensures that history doesn't grow indefinitely - if there are too many chunks,
+		/// delegators should first claim their former rewards before adding additional `EraStake`
+		/// values.
+		#[pallet::constant]
+		type DefaultMaxEraStakeValues: Get<u32>;
+
+		/// Number of eras that need to pass until newly delegated funds earn rewards. Prevents
+		/// delegating right before the end of an era to claim its rewards.
+		#[pallet::constant]
+		type StakeEffectDelay: Get<EraIndex>;
 
 		/// Number of eras that need to pass until unbonded value can be withdrawn.
 		#[pallet::constant]
@@ -137,10 +189,74 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxUnlockingChunks: Get<u32>;
 
+		/// Max number of providers that can be registered with a single `register_provider_batch`
+		/// call.
+		#[pallet::constant]
+		type MaxBatchRegistrations: Get<u32>;
+
+		/// Max number of provider era info entries checked by a single `prune_old_era` call.
+		#[pallet::constant]
+		type MaxProvidersPerEraClean: Get<u32>;
+
+		/// Max number of eras returned by a single era or provider history query.
+		#[pallet::constant]
+		type MaxEraHistoryQuery: Get<u32>;
+
+		/// Number of consecutive eras without any stake after which a provider is unregistered.
+		#[pallet::constant]
+		type MaxInactiveEras: Get<u32>;
+
+		/// Number of eras after which unclaimed rewards of an era expire.
+		#[pallet::constant]
+		type MaxClaimableEraAge: Get<u32>;
+
+		/// Max number of claimed rewards kept in `ClaimHistory` for a `(delegator, provider)`
+		/// pairing. Only used with the `claim-history` feature.
+		#[pallet::constant]
+		type MaxClaimHistory: Get<u32>;
+
+		/// Handler for rewards that were not claimed within `MaxClaimableEraAge` eras.
+		type ExpiredRewardDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Max number of provider era info entries checked for expired rewards in a single block.
+		#[pallet::constant]
+		type MaxProvidersExpiredPerBlock: Get<u32>;
+
+		/// Number of blocks the new owner has to accept a provider ownership transfer.
+		#[pallet::constant]
+		type ProviderTransferPeriod: Get<Self::BlockNumber>;
+
+		/// Number of eras before a new provider commission takes effect.
+		#[pallet::constant]
+		type CommissionChangeDelay: Get<u32>;
+
+		/// Number of blocks over which claimed delegator rewards vest. Zero pays rewards out
+		/// immediately.
+		#[pallet::constant]
+		type VestingPeriod: Get<Self::BlockNumber>;
+
+		/// Max number of reward vesting schedules of a delegator. Once reached, a new reward is
+		/// merged into the newest schedule. Should be at least one.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// Source of providers' performance scores.
+		type ProviderScore: ProviderScoreInfo<Self::ProviderId>;
+
+		/// Part of delegators' rewards which is scaled by the provider's performance score.
+		#[pallet::constant]
+		type ScoreRewardMultiplier: Get<Perbill>;
+
 		/// dAPI staking pallet Id.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// Origin that can force a new era.
+		type ForceEraOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the bond slashed from misbehaving providers.
+		type SlashDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -155,6 +271,17 @@ pub mod pallet {
 	#[pallet::getter(fn era_state)]
 	pub type EraState<T: Config> = StorageMap<_, Twox64Concat, EraIndex, EraMetadata<BalanceOf<T>>>;
 
+	#[pallet::type_value]
+	pub fn DefaultEraStakeValuesLimit<T: Config>() -> u32 {
+		T::DefaultMaxEraStakeValues::get()
+	}
+
+	/// Max number of unique `EraStake` values that can exist for a `(delegator, provider)` pairing
+	#[pallet::storage]
+	#[pallet::getter(fn era_stake_values_limit)]
+	pub type EraStakeValuesLimit<T: Config> =
+		StorageValue<_, u32, ValueQuery, DefaultEraStakeValuesLimit<T>>;
+
 	/// Accumulator for rewards during an era. It is reset at every new era
 	#[pallet::storage]
 	#[pallet::getter(fn reward_accumulator)]
@@ -166,6 +293,16 @@ pub mod pallet {
 	pub(crate) type ProviderInfo<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::ProviderId, ProviderMetadata<T::AccountId>>;
 
+	/// Number of providers active in each era
+	#[pallet::storage]
+	#[pallet::getter(fn era_active_provider_count)]
+	pub type EraActiveProviderCount<T> = StorageMap<_, Twox64Concat, EraIndex, u32, ValueQuery>;
+
+	/// Currently active providers, rotated to the next era at every era change
+	#[pallet::storage]
+	#[pallet::getter(fn active_providers)]
+	pub type ActiveProviders<T: Config> = StorageValue<_, BTreeSet<T::ProviderId>, ValueQuery>;
+
 	/// Active provider state at each era
 	#[pallet::storage]
 	#[pallet::getter(fn provider_era_info)]
@@ -178,6 +315,36 @@ pub mod pallet {
 		ProviderEraMetadata<BalanceOf<T>>,
 	>;
 
+	/// Providers with an entry in `ProviderEraInfo` for each era, indexed by the order they were
+	/// added in
+	#[pallet::storage]
+	#[pallet::getter(fn era_provider)]
+	pub type EraProviders<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, u32, T::ProviderId>;
+
+	/// Number of providers added to `EraProviders` for each era
+	#[pallet::storage]
+	#[pallet::getter(fn era_provider_count)]
+	pub type EraProviderCount<T> = StorageMap<_, Twox64Concat, EraIndex, u32, ValueQuery>;
+
+	/// Next era whose unclaimed rewards are expired, with the index in `EraProviders` of its next
+	/// provider to check
+	#[pallet::storage]
+	#[pallet::getter(fn expiry_cursor)]
+	pub type ExpiryCursor<T> = StorageValue<_, (EraIndex, u32), ValueQuery>;
+
+	/// Index in `EraProviders` of the next provider checked by `prune_old_era` for each era, and
+	/// whether entries with unclaimed rewards were kept since the last complete pass over the era
+	#[pallet::storage]
+	#[pallet::getter(fn prune_cursor)]
+	pub type PruneCursor<T> = StorageMap<_, Twox64Concat, EraIndex, (u32, bool), ValueQuery>;
+
+	/// Number of consecutive eras an active provider has had no stake
+	#[pallet::storage]
+	#[pallet::getter(fn provider_inactive_eras)]
+	pub type ProviderInactiveEras<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ProviderId, u32, ValueQuery>;
+
 	/// Provider delegation information
 	#[pallet::storage]
 	#[pallet::getter(fn delegation_info)]
@@ -191,12 +358,105 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Number of providers an account is currently staking on
+	#[pallet::storage]
+	#[pallet::getter(fn delegator_provider_count)]
+	pub type DelegatorProviderCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Number of accounts currently staking on at least one provider
+	#[pallet::storage]
+	#[pallet::getter(fn delegator_count)]
+	pub type DelegatorCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Accounts currently staking on each provider
+	#[pallet::storage]
+	#[pallet::getter(fn provider_delegators)]
+	pub type ProviderDelegators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::ProviderId,
+		BoundedVec<T::AccountId, T::MaxDelegatorsPerProvider>,
+		ValueQuery,
+	>;
+
+	/// Total rewards claimed by a provider over all time
+	#[pallet::storage]
+	#[pallet::getter(fn provider_total_rewards_claimed)]
+	pub type ProviderTotalRewardsClaimed<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ProviderId, BalanceOf<T>, ValueQuery>;
+
+	/// Total rewards claimed by a delegator from a provider over all time
+	#[pallet::storage]
+	#[pallet::getter(fn delegator_total_rewards_claimed)]
+	pub type DelegatorTotalRewardsClaimed<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::ProviderId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Number of delegation records written to the offchain index in the current block, reset at
+	/// the end of the block.
+	#[pallet::storage]
+	pub(crate) type DelegationRecordCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Most recent rewards claimed by a delegator from a provider, oldest first. Only filled with
+	/// the `claim-history` feature.
+	#[pallet::storage]
+	#[pallet::getter(fn claim_history)]
+	pub type ClaimHistory<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::ProviderId,
+		BoundedVec<ClaimRecord<BalanceOf<T>, T::BlockNumber>, T::MaxClaimHistory>,
+		ValueQuery,
+	>;
+
+	/// Whether the next block should start a new era regardless of the era length
+	#[pallet::storage]
+	#[pallet::getter(fn force_new_era_scheduled)]
+	pub type ForceNewEra<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// Block at which a new era is forced to start regardless of the era length
+	#[pallet::storage]
+	#[pallet::getter(fn next_era_start_block)]
+	pub type NextEraStartBlock<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	/// Provider ownership transfers waiting for the new owner, with the block they expire at
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfers)]
+	pub type PendingTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ProviderId, (T::AccountId, T::BlockNumber)>;
+
+	/// Provider commissions waiting to take effect, with the era they take effect in
+	#[pallet::storage]
+	#[pallet::getter(fn pending_commissions)]
+	pub type PendingCommissions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ProviderId, (Perbill, EraIndex)>;
+
 	/// Unbonding information of an account
 	#[pallet::storage]
 	#[pallet::getter(fn unbonding_info)]
 	pub type UnbondingInfo<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, UnbondingMetadata<BalanceOf<T>>, ValueQuery>;
 
+	/// Vesting schedules of the rewards claimed by a delegator, one per claim
+	#[pallet::storage]
+	#[pallet::getter(fn reward_vesting)]
+	pub type RewardVesting<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<RewardVestingInfo<BalanceOf<T>, T::BlockNumber>, T::MaxVestingSchedules>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -216,15 +476,92 @@ pub mod pallet {
 		Withdrawn { who: T::AccountId, amount: BalanceOf<T> },
 		/// New staking era. Distribute era rewards to providers.
 		NewEra { era: EraIndex, first_block: T::BlockNumber },
-		/// Payout to provider or delegator.
+		/// Payout to provider.
 		Payout {
 			who: T::AccountId,
 			provider_id: T::ProviderId,
 			era: EraIndex,
 			amount: BalanceOf<T>,
 		},
+		/// Payout to delegator. `share` is the delegator's part of `total_pool`, the rewards of
+		/// all provider's delegators for the era.
+		DelegatorPayout {
+			who: T::AccountId,
+			provider_id: T::ProviderId,
+			era: EraIndex,
+			amount: BalanceOf<T>,
+			share: Perbill,
+			total_pool: BalanceOf<T>,
+		},
+		/// Payout to delegator locked under a vesting schedule which ends at `vest_end_block`.
+		PayoutVested {
+			who: T::AccountId,
+			provider_id: T::ProviderId,
+			era: EraIndex,
+			amount: BalanceOf<T>,
+			vest_end_block: T::BlockNumber,
+		},
+		/// Payout to delegator for several eras at once.
+		PayoutBatch {
+			who: T::AccountId,
+			provider_id: T::ProviderId,
+			eras: Vec<EraIndex>,
+			total_amount: BalanceOf<T>,
+		},
+		/// Delegator reward for `era` was delegated back to the provider.
+		Compounded {
+			who: T::AccountId,
+			provider_id: T::ProviderId,
+			era: EraIndex,
+			amount: BalanceOf<T>,
+		},
 		/// Set blocks per era
 		BlocksPerEraSet { current_round: EraIndex, first_block: T::BlockNumber, old: u32, new: u32 },
+		/// Max number of `EraStake` values per delegation has changed.
+		EraStakeValuesLimitChanged { old: u32, new: u32 },
+		/// New era was forced before the end of the previous one.
+		ForcedNewEra { era: EraIndex },
+		/// Provider registered through a batch registration.
+		ProviderRegistered { provider_id: T::ProviderId },
+		/// Provider from a batch registration was skipped because its registration failed.
+		ProviderRegistrationSkipped { provider_id: T::ProviderId, reason: DispatchError },
+		/// Total claimed rewards of a provider have been reset.
+		ProviderRewardStatsReset { provider_id: T::ProviderId },
+		/// Invalid leading stakes were removed from a delegation.
+		DelegationInfoFixed {
+			delegator: T::AccountId,
+			provider_id: T::ProviderId,
+			removed_stakes: u32,
+		},
+		/// Storage of an old era was removed. The era state is removed once all provider entries
+		/// are.
+		EraPruned { era: EraIndex, providers_cleaned: u32 },
+		/// Part of a provider's bond was slashed.
+		ProviderSlashed { provider_id: T::ProviderId, amount: BalanceOf<T> },
+		/// Provider was unregistered after having no stake for `MaxInactiveEras` eras.
+		ProviderAutoUnregistered { provider_id: T::ProviderId },
+		/// Unclaimed rewards of a provider and its delegators for an era expired.
+		RewardExpired { provider_id: T::ProviderId, era: EraIndex, amount: BalanceOf<T> },
+		/// Provider owner offered the ownership to `new_owner` until block `expiry`.
+		ProviderTransferInitiated {
+			provider_id: T::ProviderId,
+			new_owner: T::AccountId,
+			expiry: T::BlockNumber,
+		},
+		/// Pending provider ownership transfer was cancelled.
+		ProviderTransferCancelled { provider_id: T::ProviderId },
+		/// Provider ownership and bond moved to a new owner.
+		ProviderTransferred {
+			provider_id: T::ProviderId,
+			old_owner: T::AccountId,
+			new_owner: T::AccountId,
+		},
+		/// Provider commission will change to `commission` in `era`.
+		CommissionChangeScheduled { provider_id: T::ProviderId, commission: Perbill, era: EraIndex },
+		/// Provider commission changed.
+		CommissionChanged { provider_id: T::ProviderId, commission: Perbill },
+		/// Vested delegator rewards were unlocked, `still_locked` remains under vesting.
+		VestedRewardsUnlocked { who: T::AccountId, still_locked: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -249,32 +586,63 @@ pub mod pallet {
 		ProviderExists,
 		NoWritingSameValue,
 		CannotSetBelowMin,
+		TooManyProviderDelegations,
+		RedelegateToSameProvider,
+		RewardExpired,
+		NoPendingTransfer,
+		NotPendingOwner,
+		TransferExpired,
+		EraStartInPast,
+		ExceedsMaxProviderBond,
+		CorruptDelegation,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			let mut era = <Era<T>>::get();
-			if era.should_update(n) {
+			let scheduled = NextEraStartBlock::<T>::get().map_or(false, |start| n >= start);
+			let forced = ForceNewEra::<T>::get() || scheduled;
+			// Era rotation would write the state of the era after the last one
+			let weight = if (forced || era.should_update(n)) && !era.is_last() {
 				let previous_era = era.current;
-				if previous_era == 0 {
+				if !era.has_started() {
 					era.length = T::DefaultBlocksPerEra::get();
 				}
 				era.update(n);
 				<Era<T>>::put(era);
 
 				Self::snapshot_era_rewards(previous_era);
-				let consumed_weight = Self::rotate_provider_era_info(previous_era);
+				let rotated = Self::rotate_provider_era_info(previous_era);
 
 				Self::deposit_event(Event::<T>::NewEra {
 					era: era.current,
 					first_block: era.first_block,
 				});
+				if forced {
+					ForceNewEra::<T>::put(false);
+					if scheduled {
+						NextEraStartBlock::<T>::kill();
+					}
+					Self::deposit_event(Event::<T>::ForcedNewEra { era: era.current });
+				}
 
-				consumed_weight + T::DbWeight::get().reads_writes(2, 3)
+				T::WeightInfo::on_initialize_new_era(rotated)
+					// Including `on_finalize`
+					.saturating_add(T::DbWeight::get().reads_writes(2, 2))
 			} else {
-				T::DbWeight::get().reads(1)
-			}
+				T::DbWeight::get().reads_writes(4, 1)
+			};
+
+			let (checked, expired) = Self::expire_era_rewards(era.current);
+			weight.saturating_add(
+				T::DbWeight::get()
+					.reads_writes(3 * checked as Weight + 1, 2 * expired as Weight + 1),
+			)
+		}
+
+		fn on_finalize(n: BlockNumberFor<T>) {
+			Self::finalize_delegation_index(n);
 		}
 	}
 
@@ -298,6 +666,75 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Start a new era at the next block, regardless of the number of blocks per era.
+		#[pallet::weight(T::WeightInfo::force_new_era())]
+		pub fn force_new_era(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::ForceEraOrigin::ensure_origin(origin)?;
+			ForceNewEra::<T>::put(true);
+			Ok(().into())
+		}
+
+		/// Start a new era at `block_number`, regardless of the number of blocks per era. Replaces
+		/// a previously scheduled start block.
+		#[pallet::weight(T::WeightInfo::force_new_era_at())]
+		pub fn force_new_era_at(
+			origin: OriginFor<T>,
+			block_number: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::ForceEraOrigin::ensure_origin(origin)?;
+			ensure!(
+				block_number > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::EraStartInPast
+			);
+			NextEraStartBlock::<T>::put(block_number);
+			Ok(().into())
+		}
+
+		/// Change the max number of `EraStake` values per delegation. At least two values are
+		/// required, as one spot must remain for a compounding reward claim.
+		#[pallet::weight(T::WeightInfo::set_era_stake_values_limit())]
+		pub fn set_era_stake_values_limit(
+			origin: OriginFor<T>,
+			new_limit: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(new_limit >= MIN_ERA_STAKE_VALUES, Error::<T>::CannotSetBelowMin);
+			let old = <EraStakeValuesLimit<T>>::get();
+			ensure!(old != new_limit, Error::<T>::NoWritingSameValue);
+			<EraStakeValuesLimit<T>>::put(new_limit);
+			Self::deposit_event(Event::EraStakeValuesLimitChanged { old, new: new_limit });
+			Ok(().into())
+		}
+
+		/// Register a batch of providers, used to bootstrap a permissioned set of providers
+		/// through governance. Each entry goes through the same checks as a regular registration.
+		/// Entries which fail are skipped and don't affect the rest of the batch.
+		#[pallet::weight(T::WeightInfo::register_provider_batch(registrations.len() as u32))]
+		pub fn register_provider_batch(
+			origin: OriginFor<T>,
+			registrations: BoundedVec<
+				(T::AccountId, T::ProviderId, BalanceOf<T>),
+				T::MaxBatchRegistrations,
+			>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			for (account, provider_id, bond) in registrations {
+				match <Self as DapiStakingRegistration<_, _, _>>::register_provider(
+					account,
+					provider_id.clone(),
+					bond,
+					T::MinProviderStake::get(),
+				) {
+					Ok(_) => Self::deposit_event(Event::<T>::ProviderRegistered { provider_id }),
+					Err(e) => Self::deposit_event(Event::<T>::ProviderRegistrationSkipped {
+						provider_id,
+						reason: e.error,
+					}),
+				}
+			}
+			Ok(().into())
+		}
+
 		/// Operator stake some funds to his own provider
 		#[pallet::weight(T::WeightInfo::provider_bond_more())]
 		pub fn provider_bond_more(
@@ -321,6 +758,10 @@ pub mod pallet {
 				<ProviderEraInfo<T>>::get(&provider_id, era).unwrap_or_default();
 			provider_era_info.bond =
 				provider_era_info.bond.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				provider_era_info.bond <= T::MaxProviderBond::get(),
+				Error::<T>::ExceedsMaxProviderBond
+			);
 			provider_era_info.total =
 				provider_era_info.total.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
 
@@ -402,19 +843,25 @@ pub mod pallet {
 			let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
 			ensure!(
 				!delegation.latest_staked_value().is_zero() ||
-					provider_era_info.delegator_count <= T::MaxDelegatorsPerProvider::get(),
+					Self::has_delegator_slot(&provider_id),
 				Error::<T>::MaxNumberOfStakersExceeded
 			);
 			if delegation.latest_staked_value().is_zero() {
+				ensure!(
+					Self::delegator_provider_count(&delegator) <
+						T::MaxDelegationsPerDelegator::get(),
+					Error::<T>::TooManyProviderDelegations
+				);
 				provider_era_info.delegator_count =
 					provider_era_info.delegator_count.saturating_add(1);
 			}
 			delegation
 				.stake(era, amount)
 				.map_err(|_| Error::<T>::UnexpectedDelegationInfoEra)?;
+			delegation.delay_rewards(amount, era.saturating_add(T::StakeEffectDelay::get()));
 			ensure!(
 				// One spot should remain for compounding reward claim call
-				delegation.len() < T::MaxEraStakeValues::get(),
+				delegation.len() < Self::era_stake_values_limit(),
 				Error::<T>::TooManyEraStakeValues
 			);
 			ensure!(
@@ -433,6 +880,13 @@ pub mod pallet {
 			});
 			Self::update_delegation_info(&delegator, &provider_id, delegation);
 			ProviderEraInfo::<T>::insert(&provider_id, era, provider_era_info);
+			Self::index_delegation(
+				&delegator,
+				&provider_id,
+				DelegationAction::Delegate,
+				era,
+				amount,
+			);
 			Self::deposit_event(Event::<T>::Delegated { delegator, provider_id, amount });
 			Ok(().into())
 		}
@@ -472,7 +926,7 @@ pub mod pallet {
 				.map_err(|_| Error::<T>::UnexpectedDelegationInfoEra)?;
 			ensure!(
 				// One spot should remain for compounding reward claim call
-				delegation.len() < T::MaxEraStakeValues::get(),
+				delegation.len() < Self::era_stake_values_limit(),
 				Error::<T>::TooManyEraStakeValues
 			);
 
@@ -495,6 +949,13 @@ pub mod pallet {
 			});
 			Self::update_delegation_info(&delegator, &provider_id, delegation);
 			ProviderEraInfo::<T>::insert(&provider_id, era, provider_era_info);
+			Self::index_delegation(
+				&delegator,
+				&provider_id,
+				DelegationAction::Unstake,
+				era,
+				unstake_amount,
+			);
 			Self::deposit_event(Event::<T>::DelegatorUnstaked {
 				delegator,
 				provider_id,
@@ -503,6 +964,127 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Delegator unstake all funds from the provider, without having to query the staked
+		/// amount first.
+		#[pallet::weight(T::WeightInfo::delegator_unstake())]
+		pub fn delegator_unstake_all(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin.clone())?;
+			let staked_amount =
+				<DelegationInfo<T>>::get(&delegator, &provider_id).latest_staked_value();
+			ensure!(staked_amount > Zero::zero(), Error::<T>::NotStakedProvider);
+			Self::delegator_unstake(origin, provider_id, staked_amount)
+		}
+
+		/// Move delegated funds from one provider to another within the current era. Funds stay
+		/// reserved and skip the unbonding period. The remaining delegation on `from_provider_id`
+		/// must stay above minimum delegating amount, unless everything is moved. As with
+		/// `delegate`, moved funds earn rewards on `to_provider_id` after `StakeEffectDelay` eras.
+		#[pallet::weight(T::WeightInfo::redelegate())]
+		pub fn redelegate(
+			origin: OriginFor<T>,
+			from_provider_id: T::ProviderId,
+			to_provider_id: T::ProviderId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			ensure!(amount > Zero::zero(), Error::<T>::UnstakingWithNoValue);
+			ensure!(from_provider_id != to_provider_id, Error::<T>::RedelegateToSameProvider);
+			ensure!(Self::is_active_provider(&from_provider_id), Error::<T>::NotOperatedProvider);
+			ensure!(Self::is_active_provider(&to_provider_id), Error::<T>::NotOperatedProvider);
+
+			let era = Self::current_era();
+			let mut from_delegation = <DelegationInfo<T>>::get(&delegator, &from_provider_id);
+			let staked_amount = from_delegation.latest_staked_value();
+			ensure!(staked_amount > Zero::zero(), Error::<T>::NotStakedProvider);
+			let mut from_era_info =
+				<ProviderEraInfo<T>>::get(&from_provider_id, era).unwrap_or_default();
+			let remaining = staked_amount.saturating_sub(amount);
+			let fully_redelegated = remaining.is_zero();
+			ensure!(
+				fully_redelegated || remaining >= T::MinDelegatorStake::get(),
+				Error::<T>::InsufficientBond
+			);
+			let amount = staked_amount.min(amount);
+			if fully_redelegated {
+				from_era_info.delegator_count = from_era_info.delegator_count.saturating_sub(1);
+			}
+			from_era_info.total = from_era_info.total.saturating_sub(amount);
+			from_delegation
+				.unstake(era, amount)
+				.map_err(|_| Error::<T>::UnexpectedDelegationInfoEra)?;
+			ensure!(
+				from_delegation.len() < Self::era_stake_values_limit(),
+				Error::<T>::TooManyEraStakeValues
+			);
+
+			let mut to_era_info =
+				<ProviderEraInfo<T>>::get(&to_provider_id, era).unwrap_or_default();
+			let mut to_delegation = <DelegationInfo<T>>::get(&delegator, &to_provider_id);
+			ensure!(
+				!to_delegation.latest_staked_value().is_zero() ||
+					Self::has_delegator_slot(&to_provider_id),
+				Error::<T>::MaxNumberOfStakersExceeded
+			);
+			if to_delegation.latest_staked_value().is_zero() {
+				// A fully redelegated provider no longer counts towards the limit
+				let provider_count = Self::delegator_provider_count(&delegator)
+					.saturating_sub(fully_redelegated as u32);
+				ensure!(
+					provider_count < T::MaxDelegationsPerDelegator::get(),
+					Error::<T>::TooManyProviderDelegations
+				);
+				to_era_info.delegator_count = to_era_info.delegator_count.saturating_add(1);
+			}
+			to_delegation
+				.stake(era, amount)
+				.map_err(|_| Error::<T>::UnexpectedDelegationInfoEra)?;
+			to_delegation.delay_rewards(amount, era.saturating_add(T::StakeEffectDelay::get()));
+			ensure!(
+				to_delegation.len() < Self::era_stake_values_limit(),
+				Error::<T>::TooManyEraStakeValues
+			);
+			ensure!(
+				to_delegation.latest_staked_value() >= T::MinDelegatorStake::get(),
+				Error::<T>::InsufficientBond,
+			);
+			to_era_info.total =
+				to_era_info.total.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+
+			// Total staked in the era is unchanged as funds move between providers
+			Self::update_delegation_info(&delegator, &from_provider_id, from_delegation);
+			Self::update_delegation_info(&delegator, &to_provider_id, to_delegation);
+			ProviderEraInfo::<T>::insert(&from_provider_id, era, from_era_info);
+			ProviderEraInfo::<T>::insert(&to_provider_id, era, to_era_info);
+			Self::index_delegation(
+				&delegator,
+				&from_provider_id,
+				DelegationAction::Unstake,
+				era,
+				amount,
+			);
+			Self::index_delegation(
+				&delegator,
+				&to_provider_id,
+				DelegationAction::Delegate,
+				era,
+				amount,
+			);
+			Self::deposit_event(Event::<T>::DelegatorUnstaked {
+				delegator: delegator.clone(),
+				provider_id: from_provider_id,
+				amount,
+			});
+			Self::deposit_event(Event::<T>::Delegated {
+				delegator,
+				provider_id: to_provider_id,
+				amount,
+			});
+			Ok(().into())
+		}
+
 		/// Withdraw all funds that have completed the unbonding process.
 		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
 		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
@@ -534,6 +1116,7 @@ pub mod pallet {
 			}
 
 			let current_era = Self::current_era();
+			ensure!(!Self::is_expired(era, current_era), Error::<T>::RewardExpired);
 			ensure!(era < current_era, Error::<T>::EraOutOfBounds);
 			let mut provider_era_info =
 				<ProviderEraInfo<T>>::get(&provider_id, era).unwrap_or_default();
@@ -543,8 +1126,11 @@ pub mod pallet {
 			);
 			ensure!(provider_era_info.total > Zero::zero(), Error::<T>::NotStakedProvider);
 			let era_state = <EraState<T>>::get(era).ok_or(Error::<T>::UnknownEra)?;
-			let (provider_reward, _) =
-				Self::split_provider_delegators_rewards(&provider_era_info, &era_state);
+			let (provider_reward, _) = Self::split_provider_delegators_rewards(
+				provider_era_info.commission,
+				&provider_era_info,
+				&era_state,
+			);
 
 			let reward_imbalance = T::Currency::withdraw(
 				&Self::account_id(),
@@ -556,6 +1142,9 @@ pub mod pallet {
 
 			provider_era_info.provider_reward_claimed = true;
 			ProviderEraInfo::<T>::insert(&provider_id, era, provider_era_info);
+			ProviderTotalRewardsClaimed::<T>::mutate(&provider_id, |total| {
+				*total = total.saturating_add(provider_reward)
+			});
 			Self::deposit_event(Event::<T>::Payout {
 				who: provider_info.owner,
 				provider_id,
@@ -565,7 +1154,8 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// Claim earned delegator rewards for the oldest era.
+		/// Claim earned delegator rewards for the oldest era. If `VestingPeriod` is set, the reward
+		/// is locked under its own schedule, vesting linearly over `VestingPeriod` blocks.
 		#[pallet::weight(T::WeightInfo::claim_delegator())]
 		pub fn claim_delegator(
 			origin: OriginFor<T>,
@@ -573,6 +1163,10 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let delegator = ensure_signed(origin)?;
 			let mut delegator_info = <DelegationInfo<T>>::get(&delegator, &provider_id);
+			let current_era = Self::current_era();
+			// Corrupt delegations are repaired by root with `fix_delegation_info`
+			ensure!(delegator_info.verify_integrity(current_era), Error::<T>::CorruptDelegation);
+			let earning = delegator_info.oldest_earning_stake().unwrap_or_default();
 			let (era, staked) = delegator_info.claim();
 			ensure!(staked > Zero::zero(), Error::<T>::NotStakedProvider);
 
@@ -582,17 +1176,10 @@ pub mod pallet {
 				ensure!(era < unregistered_era, Error::<T>::NotOperatedProvider);
 			}
 
-			let current_era = Self::current_era();
+			ensure!(!Self::is_expired(era, current_era), Error::<T>::RewardExpired);
 			ensure!(era < current_era, Error::<T>::EraOutOfBounds);
-			let provider_era_info =
-				<ProviderEraInfo<T>>::get(&provider_id, era).unwrap_or_default();
-			let era_info = <EraState<T>>::get(era).ok_or(Error::<T>::UnknownEra)?;
-			let (_, delegators_reward) =
-				Self::split_provider_delegators_rewards(&provider_era_info, &era_info);
-			let reward = Perbill::from_rational(
-				staked,
-				provider_era_info.total.saturating_sub(provider_era_info.bond),
-			) * delegators_reward;
+			let (reward, share, delegators_reward) =
+				Self::delegator_era_reward(&provider_id, era, earning)?;
 
 			let reward_imbalance = T::Currency::withdraw(
 				&Self::account_id(),
@@ -601,52 +1188,352 @@ pub mod pallet {
 				ExistenceRequirement::AllowDeath,
 			)?;
 			T::Currency::resolve_creating(&delegator, reward_imbalance);
+			let vest_end_block = Self::vest_delegator_reward(&delegator, reward);
 
 			Self::update_delegation_info(&delegator, &provider_id, delegator_info);
-			Self::deposit_event(Event::<T>::Payout {
-				who: delegator,
-				provider_id,
-				era,
-				amount: reward,
+			ProviderEraInfo::<T>::mutate(&provider_id, era, |info| {
+				if let Some(info) = info {
+					info.note_delegator_reward_paid(reward, delegators_reward);
+				}
+			});
+			DelegatorTotalRewardsClaimed::<T>::mutate(&delegator, &provider_id, |total| {
+				*total = total.saturating_add(reward)
 			});
+			#[cfg(feature = "claim-history")]
+			Self::note_claims(&delegator, &provider_id, &[(era, reward)]);
+			Self::index_delegation(&delegator, &provider_id, DelegationAction::Claim, era, reward);
+			let event = match vest_end_block {
+				Some(vest_end_block) => Event::<T>::PayoutVested {
+					who: delegator,
+					provider_id,
+					era,
+					amount: reward,
+					vest_end_block,
+				},
+				None => Event::<T>::DelegatorPayout {
+					who: delegator,
+					provider_id,
+					era,
+					amount: reward,
+					share,
+					total_pool: delegators_reward,
+				},
+			};
+			Self::deposit_event(event);
 			Ok(().into())
 		}
 
-		/// Withdraw unregistered provider locked fund.
-		#[pallet::weight(T::WeightInfo::provider_withdraw_unregistered())]
-		pub fn provider_withdraw_unregistered(
+		/// Claim earned delegator rewards for up to `max_eras` of the oldest eras, paid out in a
+		/// single transfer. `max_eras` is capped at `EraStakeValuesLimit`. Eras whose rewards
+		/// expired are dropped first, so the delegator can move on to the claimable ones.
+		/// The total is vested as a single schedule if `VestingPeriod` is set.
+		#[pallet::weight(T::WeightInfo::claim_delegator_batch(
+			(*max_eras).min(Pallet::<T>::era_stake_values_limit())
+		))]
+		pub fn claim_delegator_batch(
 			origin: OriginFor<T>,
 			provider_id: T::ProviderId,
+			max_eras: u32,
 		) -> DispatchResultWithPostInfo {
-			let _ = ensure_signed(origin)?;
-
-			let mut provider_info =
+			let delegator = ensure_signed(origin)?;
+			let max_eras = max_eras.min(Self::era_stake_values_limit());
+			let mut delegator_info = <DelegationInfo<T>>::get(&delegator, &provider_id);
+			let current_era = Self::current_era();
+			ensure!(delegator_info.verify_integrity(current_era), Error::<T>::CorruptDelegation);
+			let provider_info =
 				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::NotOperatedProvider)?;
-			ensure!(!provider_info.bond_withdrawn, Error::<T>::NothingToWithdraw);
-			let unregistered_era = if let ProviderStatus::Inactive(e) = provider_info.status {
-				e
-			} else {
-				return Err(Error::<T>::NotUnregisteredProvider.into())
+			let unregistered_era = match provider_info.status {
+				ProviderStatus::Inactive(era) => Some(era),
+				_ => None,
 			};
-			let current_era = Self::current_era();
-			ensure!(
-				current_era >= unregistered_era + T::UnbondingPeriod::get(),
-				Error::<T>::NothingToWithdraw
+
+			let mut expired = false;
+			while delegator_info
+				.oldest_claimable_era()
+				.map_or(false, |era| Self::is_expired(era, current_era))
+			{
+				delegator_info.claim();
+				expired = true;
+			}
+
+			let mut payouts = Vec::new();
+			while (payouts.len() as u32) < max_eras {
+				let mut next_info = delegator_info.clone();
+				let earning = next_info.oldest_earning_stake().unwrap_or_default();
+				let (era, staked) = next_info.claim();
+				// The first era is checked as in `claim_delegator`, later ones end the batch
+				let claimable = if staked.is_zero() {
+					Err(Error::<T>::NotStakedProvider)
+				} else if unregistered_era.map_or(false, |e| era >= e) {
+					Err(Error::<T>::NotOperatedProvider)
+				} else if era >= current_era {
+					Err(Error::<T>::EraOutOfBounds)
+				} else {
+					Ok(())
+				};
+				match claimable {
+					Err(e) if payouts.is_empty() && !expired => return Err(e.into()),
+					Err(_) => break,
+					Ok(()) => {},
+				}
+				let (reward, _, delegators_reward) =
+					Self::delegator_era_reward(&provider_id, era, earning)?;
+				payouts.push((era, reward, delegators_reward));
+				delegator_info = next_info;
+			}
+			ensure!(!payouts.is_empty() || expired, Error::<T>::NotStakedProvider);
+
+			let total_amount =
+				payouts.iter().fold(Zero::zero(), |total: BalanceOf<T>, (_, reward, _)| {
+					total.saturating_add(*reward)
+				});
+			let reward_imbalance = T::Currency::withdraw(
+				&Self::account_id(),
+				total_amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			T::Currency::resolve_creating(&delegator, reward_imbalance);
+			Self::vest_delegator_reward(&delegator, total_amount);
+
+			Self::update_delegation_info(&delegator, &provider_id, delegator_info);
+			for (era, reward, delegators_reward) in payouts.iter() {
+				ProviderEraInfo::<T>::mutate(&provider_id, era, |info| {
+					if let Some(info) = info {
+						info.note_delegator_reward_paid(*reward, *delegators_reward);
+					}
+				});
+			}
+			DelegatorTotalRewardsClaimed::<T>::mutate(&delegator, &provider_id, |total| {
+				*total = total.saturating_add(total_amount)
+			});
+			#[cfg(feature = "claim-history")]
+			Self::note_claims(
+				&delegator,
+				&provider_id,
+				&payouts.iter().map(|(era, reward, _)| (*era, *reward)).collect::<Vec<_>>(),
 			);
+			for (era, reward, _) in payouts.iter() {
+				Self::index_delegation(
+					&delegator,
+					&provider_id,
+					DelegationAction::Claim,
+					*era,
+					*reward,
+				);
+			}
+			let claimed_eras = payouts.len() as u32;
+			Self::deposit_event(Event::<T>::PayoutBatch {
+				who: delegator,
+				provider_id,
+				eras: payouts.into_iter().map(|(era, _, _)| era).collect(),
+				total_amount,
+			});
+			Ok(Some(T::WeightInfo::claim_delegator_batch(claimed_eras)).into())
+		}
 
-			let provider_era_info =
-				<ProviderEraInfo<T>>::get(&provider_id, unregistered_era).unwrap_or_default();
-			let owner = provider_info.owner.clone();
-			let withdraw_amount = provider_era_info.bond;
+		/// Claim earned delegator rewards for the oldest era and delegate them back to the same
+		/// provider. Compounding may use the spare `EraStake` value kept by `delegate`. Compounded
+		/// rewards are bonded right away, so they don't vest.
+		#[pallet::weight(T::WeightInfo::compound_delegator_reward())]
+		pub fn compound_delegator_reward(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			let delegator = ensure_signed(origin)?;
+			ensure!(Self::is_active_provider(&provider_id), Error::<T>::NotOperatedProvider);
+			let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+			let current_era = Self::current_era();
+			ensure!(delegation.verify_integrity(current_era), Error::<T>::CorruptDelegation);
+			// Only current delegators compound, so the delegator count stays the same
+			ensure!(!delegation.latest_staked_value().is_zero(), Error::<T>::NotStakedProvider);
+			let earning = delegation.oldest_earning_stake().unwrap_or_default();
+			let (era, staked) = delegation.claim();
+			ensure!(staked > Zero::zero(), Error::<T>::NotStakedProvider);
 
-			T::Currency::unreserve(&owner, withdraw_amount);
+			ensure!(!Self::is_expired(era, current_era), Error::<T>::RewardExpired);
+			ensure!(era < current_era, Error::<T>::EraOutOfBounds);
+			let (reward, _, delegators_reward) =
+				Self::delegator_era_reward(&provider_id, era, earning)?;
+			ensure!(reward > Zero::zero(), Error::<T>::StakingWithNoValue);
 
-			provider_info.bond_withdrawn = true;
-			ProviderInfo::<T>::insert(&provider_id, provider_info);
-			Self::deposit_event(Event::<T>::Withdrawn { who: owner, amount: withdraw_amount });
+			delegation
+				.stake(current_era, reward)
+				.map_err(|_| Error::<T>::UnexpectedDelegationInfoEra)?;
+			ensure!(
+				delegation.len() <= Self::era_stake_values_limit(),
+				Error::<T>::TooManyEraStakeValues
+			);
+			ensure!(
+				delegation.latest_staked_value() >= T::MinDelegatorStake::get(),
+				Error::<T>::InsufficientBond,
+			);
+			let mut provider_era_info =
+				<ProviderEraInfo<T>>::get(&provider_id, current_era).unwrap_or_default();
+			provider_era_info.total =
+				provider_era_info.total.checked_add(&reward).ok_or(ArithmeticError::Overflow)?;
+
+			let reward_imbalance = T::Currency::withdraw(
+				&Self::account_id(),
+				reward,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			T::Currency::resolve_creating(&delegator, reward_imbalance);
+			T::Currency::reserve(&delegator, reward)?;
+
+			EraState::<T>::mutate(&current_era, |value| {
+				if let Some(x) = value {
+					x.staked = x.staked.saturating_add(reward);
+				}
+			});
+			Self::update_delegation_info(&delegator, &provider_id, delegation);
+			ProviderEraInfo::<T>::insert(&provider_id, current_era, provider_era_info);
+			ProviderEraInfo::<T>::mutate(&provider_id, era, |info| {
+				if let Some(info) = info {
+					info.note_delegator_reward_paid(reward, delegators_reward);
+				}
+			});
+			DelegatorTotalRewardsClaimed::<T>::mutate(&delegator, &provider_id, |total| {
+				*total = total.saturating_add(reward)
+			});
+			#[cfg(feature = "claim-history")]
+			Self::note_claims(&delegator, &provider_id, &[(era, reward)]);
+			Self::index_delegation(&delegator, &provider_id, DelegationAction::Claim, era, reward);
+			Self::index_delegation(
+				&delegator,
+				&provider_id,
+				DelegationAction::Delegate,
+				current_era,
+				reward,
+			);
+			Self::deposit_event(Event::<T>::Compounded {
+				who: delegator,
+				provider_id,
+				era,
+				amount: reward,
+			});
+			Ok(().into())
+		}
+
+		/// Reset the total claimed rewards of a provider. Delegator totals are kept as they're
+		/// keyed by delegator first.
+		#[pallet::weight(T::WeightInfo::reset_provider_reward_stats())]
+		pub fn reset_provider_reward_stats(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ProviderTotalRewardsClaimed::<T>::remove(&provider_id);
+			Self::deposit_event(Event::<T>::ProviderRewardStatsReset { provider_id });
 			Ok(().into())
 		}
 
+		/// Repair a corrupt delegation by removing the zero stakes it starts with, which would
+		/// otherwise be claimed as eras without reward.
+		#[pallet::weight(T::WeightInfo::fix_delegation_info())]
+		pub fn fix_delegation_info(
+			origin: OriginFor<T>,
+			delegator: T::AccountId,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+			let removed_stakes = delegation.trim_leading_zeros();
+			Self::update_delegation_info(&delegator, &provider_id, delegation);
+			Self::deposit_event(Event::<T>::DelegationInfoFixed {
+				delegator,
+				provider_id,
+				removed_stakes,
+			});
+			Ok(().into())
+		}
+
+		/// Remove the storage of an era whose rewards expired and whose unbonded funds can no
+		/// longer be pending. Provider entries with unclaimed rewards, or holding the bond of an
+		/// unregistered provider which wasn't withdrawn yet, are kept. The providers of the era are
+		/// read from `EraProviders`, at most `MaxProvidersPerEraClean` of them per call resuming
+		/// from `PruneCursor`. Once a pass over all of them completes, the era state is removed
+		/// unless rewards were unclaimed, and kept entries are checked again by the next pass.
+		#[pallet::weight(T::WeightInfo::prune_old_era(T::MaxProvidersPerEraClean::get()))]
+		pub fn prune_old_era(origin: OriginFor<T>, era: EraIndex) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let current_era = Self::current_era();
+			let count = EraProviderCount::<T>::get(era);
+			let era_info = EraState::<T>::get(era);
+			ensure!(count > 0 || era_info.is_some(), Error::<T>::UnknownEra);
+			// Unclaimed rewards of all the providers of the era must have been expired
+			let (expiring_era, expiry_index) = Self::expiry_cursor();
+			ensure!(
+				(era < expiring_era || (era == expiring_era && expiry_index >= count)) &&
+					era.saturating_add(T::UnbondingPeriod::get()) < current_era,
+				Error::<T>::EraOutOfBounds
+			);
+
+			let (start, mut unclaimed) = PruneCursor::<T>::get(era);
+			let end = count.min(start.saturating_add(T::MaxProvidersPerEraClean::get()));
+			let mut providers_cleaned = 0;
+			for index in start..end {
+				let provider_id = match EraProviders::<T>::get(era, index) {
+					Some(provider_id) => provider_id,
+					// Removed by a previous pass
+					None => continue,
+				};
+				let claimable = match ProviderInfo::<T>::get(&provider_id) {
+					Some(ProviderMetadata {
+						status: ProviderStatus::Inactive(unregistered_era),
+						bond_withdrawn,
+						..
+					}) => {
+						// The bond is withdrawn from the entry of the unregistration era
+						if unregistered_era == era && !bond_withdrawn {
+							continue
+						}
+						era < unregistered_era
+					},
+					_ => true,
+				};
+				let (provider_reward, delegators_reward) =
+					match (&era_info, ProviderEraInfo::<T>::get(&provider_id, era)) {
+						(Some(era_info), Some(provider_era_info)) if claimable =>
+							Self::unclaimed_era_rewards(&provider_era_info, era_info),
+						_ => Default::default(),
+					};
+				if !provider_reward.saturating_add(delegators_reward).is_zero() {
+					unclaimed = true;
+					continue
+				}
+				ProviderEraInfo::<T>::remove(&provider_id, era);
+				EraProviders::<T>::remove(era, index);
+				providers_cleaned += 1;
+			}
+
+			if end < count {
+				PruneCursor::<T>::insert(era, (end, unclaimed));
+			} else {
+				PruneCursor::<T>::remove(era);
+				if !unclaimed {
+					EraState::<T>::remove(era);
+					EraActiveProviderCount::<T>::remove(era);
+					if EraProviders::<T>::iter_key_prefix(era).next().is_none() {
+						EraProviderCount::<T>::remove(era);
+					}
+				}
+			}
+			Self::deposit_event(Event::<T>::EraPruned { era, providers_cleaned });
+			Ok(Some(T::WeightInfo::prune_old_era(end.saturating_sub(start))).into())
+		}
+
+		/// Withdraw unregistered provider locked fund.
+		#[pallet::weight(T::WeightInfo::provider_withdraw_unregistered())]
+		pub fn provider_withdraw_unregistered(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+			Self::do_provider_withdraw_unregistered(provider_id, false)
+		}
+
 		/// Withdraw delegator's locked fund from a provider that was unregistered.
 		#[pallet::weight(T::WeightInfo::delegator_withdraw_unregistered())]
 		pub fn delegator_withdraw_unregistered(
@@ -654,36 +1541,166 @@ pub mod pallet {
 			provider_id: T::ProviderId,
 		) -> DispatchResultWithPostInfo {
 			let delegator = ensure_signed(origin)?;
+			Self::do_delegator_withdraw_unregistered(delegator, provider_id, false)
+		}
+
+		/// Withdraw the bond of an unregistered provider to its owner without waiting for the
+		/// unbonding period, e.g. when the chain is wound down.
+		#[pallet::weight(T::WeightInfo::accelerate_provider_withdrawal())]
+		pub fn accelerate_provider_withdrawal(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Self::do_provider_withdraw_unregistered(provider_id, true)
+		}
+
+		/// Withdraw the stake of `delegator` from an unregistered provider without waiting for
+		/// the unbonding period. Its rewards still have to be claimed first.
+		#[pallet::weight(T::WeightInfo::accelerate_delegator_withdrawal())]
+		pub fn accelerate_delegator_withdrawal(
+			origin: OriginFor<T>,
+			delegator: T::AccountId,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Self::do_delegator_withdraw_unregistered(delegator, provider_id, true)
+		}
 
+		/// Offer the ownership of an active provider to `new_owner`, who has
+		/// `ProviderTransferPeriod` blocks to accept it. Replaces any pending transfer.
+		#[pallet::weight(T::WeightInfo::initiate_provider_transfer())]
+		pub fn initiate_provider_transfer(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+			new_owner: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 			let provider_info =
-				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::NotOperatedProvider)?;
-			let unregistered_era = if let ProviderStatus::Inactive(e) = provider_info.status {
-				e
-			} else {
-				return Err(Error::<T>::NotUnregisteredProvider.into())
-			};
-			let current_era = Self::current_era();
+				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
 			ensure!(
-				current_era >= unregistered_era + T::UnbondingPeriod::get(),
-				Error::<T>::NothingToWithdraw
+				provider_info.status == ProviderStatus::Active,
+				Error::<T>::NotOperatedProvider
 			);
+			ensure!(provider_info.owner == who, Error::<T>::NotOwnedProvider);
+			ensure!(new_owner != who, Error::<T>::NoWritingSameValue);
 
-			let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
-			let staked_value = delegation.latest_staked_value();
-			ensure!(staked_value > Zero::zero(), Error::<T>::NotStakedProvider);
-			// Don't allow withdrawal until all rewards have been claimed.
-			let (claimable_era, _) = delegation.claim();
+			let expiry =
+				<frame_system::Pallet<T>>::block_number() + T::ProviderTransferPeriod::get();
+			PendingTransfers::<T>::insert(&provider_id, (new_owner.clone(), expiry));
+			Self::deposit_event(Event::<T>::ProviderTransferInitiated {
+				provider_id,
+				new_owner,
+				expiry,
+			});
+			Ok(().into())
+		}
+
+		/// Accept a pending ownership transfer. The provider bond is moved from the old owner's
+		/// reserved balance to the new owner's.
+		#[pallet::weight(T::WeightInfo::accept_provider_transfer())]
+		pub fn accept_provider_transfer(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			let new_owner = ensure_signed(origin)?;
+			let (pending_owner, expiry) =
+				PendingTransfers::<T>::get(&provider_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(pending_owner == new_owner, Error::<T>::NotPendingOwner);
 			ensure!(
-				claimable_era >= unregistered_era || claimable_era.is_zero(),
-				Error::<T>::UnclaimedRewardsRemaining
+				<frame_system::Pallet<T>>::block_number() <= expiry,
+				Error::<T>::TransferExpired
+			);
+			let mut provider_info =
+				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(
+				provider_info.status == ProviderStatus::Active,
+				Error::<T>::NotOperatedProvider
 			);
 
-			T::Currency::unreserve(&delegator, staked_value);
+			let bond = <ProviderEraInfo<T>>::get(&provider_id, Self::current_era())
+				.map(|info| info.bond)
+				.unwrap_or_default();
+			let old_owner = provider_info.owner;
+			T::Currency::repatriate_reserved(
+				&old_owner,
+				&new_owner,
+				bond,
+				BalanceStatus::Reserved,
+			)?;
 
-			Self::update_delegation_info(&delegator, &provider_id, Default::default());
-			Self::deposit_event(Event::<T>::Withdrawn { who: delegator, amount: staked_value });
+			provider_info.owner = new_owner.clone();
+			ProviderInfo::<T>::insert(&provider_id, provider_info);
+			PendingTransfers::<T>::remove(&provider_id);
+			Self::deposit_event(Event::<T>::ProviderTransferred {
+				provider_id,
+				old_owner,
+				new_owner,
+			});
+			Ok(().into())
+		}
+
+		/// Cancel a pending ownership transfer of an owned provider.
+		#[pallet::weight(T::WeightInfo::cancel_provider_transfer())]
+		pub fn cancel_provider_transfer(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let provider_info =
+				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider_info.owner == who, Error::<T>::NotOwnedProvider);
+			ensure!(
+				PendingTransfers::<T>::contains_key(&provider_id),
+				Error::<T>::NoPendingTransfer
+			);
+
+			PendingTransfers::<T>::remove(&provider_id);
+			Self::deposit_event(Event::<T>::ProviderTransferCancelled { provider_id });
 			Ok(().into())
 		}
+
+		/// Change the commission of an owned provider. The new commission takes effect after
+		/// `CommissionChangeDelay` eras and replaces any pending change.
+		#[pallet::weight(T::WeightInfo::set_provider_commission())]
+		pub fn set_provider_commission(
+			origin: OriginFor<T>,
+			provider_id: T::ProviderId,
+			new_commission: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let provider_info =
+				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(
+				provider_info.status == ProviderStatus::Active,
+				Error::<T>::NotOperatedProvider
+			);
+			ensure!(provider_info.owner == who, Error::<T>::NotOwnedProvider);
+
+			let era = Self::current_era().saturating_add(T::CommissionChangeDelay::get());
+			PendingCommissions::<T>::insert(&provider_id, (new_commission, era));
+			Self::deposit_event(Event::<T>::CommissionChangeScheduled {
+				provider_id,
+				commission: new_commission,
+				era,
+			});
+			Ok(().into())
+		}
+
+		/// Unlock the claimed delegator rewards which vested so far.
+		#[pallet::weight(T::WeightInfo::unlock_vested_rewards(T::MaxVestingSchedules::get()))]
+		pub fn unlock_vested_rewards(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut schedules = RewardVesting::<T>::get(&who);
+			ensure!(!schedules.is_empty(), Error::<T>::NothingToWithdraw);
+			let read_schedules = schedules.len() as u32;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			schedules.retain(|vesting| !vesting.locked_at(now).is_zero());
+			let still_locked = Self::update_reward_vesting(&who, schedules, now);
+			Self::deposit_event(Event::<T>::VestedRewardsUnlocked { who, still_locked });
+			Ok(Some(T::WeightInfo::unlock_vested_rewards(read_schedules)).into())
+		}
 	}
 
 	impl<T: Config>
@@ -699,14 +1716,25 @@ pub mod pallet {
 			bond: <<T as Config>::Currency as Currency<
 				<T as frame_system::Config>::AccountId,
 			>>::Balance,
+			min_bond: <<T as Config>::Currency as Currency<
+				<T as frame_system::Config>::AccountId,
+			>>::Balance,
 		) -> DispatchResultWithPostInfo {
 			ensure!(!ProviderInfo::<T>::contains_key(&provider_id), Error::<T>::ProviderExists);
-			ensure!(bond >= T::MinProviderStake::get(), Error::<T>::InsufficientBond);
+			ensure!(bond >= min_bond, Error::<T>::InsufficientBond);
+			ensure!(bond <= T::MaxProviderBond::get(), Error::<T>::ExceedsMaxProviderBond);
 
 			T::Currency::reserve(&account, bond)?;
 
-			ProviderInfo::<T>::insert(&provider_id, ProviderMetadata::new(account.clone()));
+			let commission = T::ProviderRewardsPercentage::get();
+			ProviderInfo::<T>::insert(
+				&provider_id,
+				ProviderMetadata::new(account.clone(), commission),
+			);
+			ActiveProviders::<T>::mutate(|providers| providers.insert(provider_id.clone()));
 			let era = Self::current_era();
+			EraActiveProviderCount::<T>::mutate(era, |count| *count = count.saturating_add(1));
+			Self::add_era_provider(era, &provider_id);
 			ProviderEraInfo::<T>::insert(
 				&provider_id,
 				era,
@@ -715,6 +1743,10 @@ pub mod pallet {
 					total: bond,
 					delegator_count: 0,
 					provider_reward_claimed: false,
+					delegators_reward_paid: Zero::zero(),
+					delegators_fully_claimed: false,
+					commission,
+					score: T::ProviderScore::provider_score(&provider_id),
 				},
 			);
 			let mut era_state = <EraState<T>>::get(era).unwrap_or_default();
@@ -731,6 +1763,11 @@ pub mod pallet {
 			let current_era = Self::current_era();
 			provider.status = ProviderStatus::Inactive(current_era);
 			ProviderInfo::<T>::insert(&provider_id, provider);
+			ActiveProviders::<T>::mutate(|providers| providers.remove(&provider_id));
+			EraActiveProviderCount::<T>::mutate(current_era, |count| {
+				*count = count.saturating_sub(1)
+			});
+			PendingCommissions::<T>::remove(&provider_id);
 			let provider_era_info =
 				<ProviderEraInfo<T>>::get(&provider_id, current_era).unwrap_or_default();
 			EraState::<T>::mutate(&current_era, |value| {
@@ -740,6 +1777,49 @@ pub mod pallet {
 			});
 			Ok(().into())
 		}
+
+		fn slash_provider(
+			provider_id: T::ProviderId,
+			slash_ratio: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let provider = ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider.status == ProviderStatus::Active, Error::<T>::NotOperatedProvider);
+
+			let era = Self::current_era();
+			let mut provider_era_info =
+				<ProviderEraInfo<T>>::get(&provider_id, era).unwrap_or_default();
+			let (imbalance, _) =
+				T::Currency::slash_reserved(&provider.owner, slash_ratio * provider_era_info.bond);
+			let amount = imbalance.peek();
+			provider_era_info.bond = provider_era_info.bond.saturating_sub(amount);
+			provider_era_info.total = provider_era_info.total.saturating_sub(amount);
+			ProviderEraInfo::<T>::insert(&provider_id, era, provider_era_info);
+			EraState::<T>::mutate(&era, |value| {
+				if let Some(x) = value {
+					x.staked = x.staked.saturating_sub(amount);
+				}
+			});
+
+			T::SlashDestination::on_unbalanced(imbalance);
+			Self::deposit_event(Event::<T>::ProviderSlashed { provider_id, amount });
+			Ok(().into())
+		}
+
+		fn current_era() -> EraIndex {
+			<Era<T>>::get().current
+		}
+
+		fn delegated_stake(delegator: &T::AccountId) -> BalanceOf<T> {
+			Self::delegator_total_staked(delegator)
+		}
+
+		fn total_staked() -> BalanceOf<T> {
+			Self::total_tvl()
+		}
+
+		fn provider_stake(provider_id: &T::ProviderId) -> BalanceOf<T> {
+			Self::provider_tvl(provider_id.clone())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -762,18 +1842,335 @@ pub mod pallet {
 			}
 		}
 
+		/// Unreserve the bond of the unregistered `provider_id`. Unless `skip_unbonding` is set,
+		/// the unbonding period has to be over.
+		fn do_provider_withdraw_unregistered(
+			provider_id: T::ProviderId,
+			skip_unbonding: bool,
+		) -> DispatchResultWithPostInfo {
+			let mut provider_info =
+				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::NotOperatedProvider)?;
+			ensure!(!provider_info.bond_withdrawn, Error::<T>::NothingToWithdraw);
+			let unregistered_era = if let ProviderStatus::Inactive(e) = provider_info.status {
+				e
+			} else {
+				return Err(Error::<T>::NotUnregisteredProvider.into())
+			};
+			let current_era = Self::current_era();
+			ensure!(
+				skip_unbonding || current_era >= unregistered_era + T::UnbondingPeriod::get(),
+				Error::<T>::NothingToWithdraw
+			);
+
+			let provider_era_info = <ProviderEraInfo<T>>::get(&provider_id, unregistered_era)
+				.ok_or(Error::<T>::UnknownEra)?;
+			let owner = provider_info.owner.clone();
+			let withdraw_amount = provider_era_info.bond;
+
+			T::Currency::unreserve(&owner, withdraw_amount);
+
+			provider_info.bond_withdrawn = true;
+			ProviderInfo::<T>::insert(&provider_id, provider_info);
+			Self::deposit_event(Event::<T>::Withdrawn { who: owner, amount: withdraw_amount });
+			Ok(().into())
+		}
+
+		/// Unreserve the stake of `delegator` on the unregistered `provider_id`. Unless
+		/// `skip_unbonding` is set, the unbonding period has to be over.
+		fn do_delegator_withdraw_unregistered(
+			delegator: T::AccountId,
+			provider_id: T::ProviderId,
+			skip_unbonding: bool,
+		) -> DispatchResultWithPostInfo {
+			let provider_info =
+				ProviderInfo::<T>::get(&provider_id).ok_or(Error::<T>::NotOperatedProvider)?;
+			let unregistered_era = if let ProviderStatus::Inactive(e) = provider_info.status {
+				e
+			} else {
+				return Err(Error::<T>::NotUnregisteredProvider.into())
+			};
+			let current_era = Self::current_era();
+			ensure!(
+				skip_unbonding || current_era >= unregistered_era + T::UnbondingPeriod::get(),
+				Error::<T>::NothingToWithdraw
+			);
+
+			let mut delegation = <DelegationInfo<T>>::get(&delegator, &provider_id);
+			let staked_value = delegation.latest_staked_value();
+			ensure!(staked_value > Zero::zero(), Error::<T>::NotStakedProvider);
+			// Don't allow withdrawal until all rewards have been claimed.
+			let (claimable_era, _) = delegation.claim();
+			ensure!(
+				claimable_era >= unregistered_era || claimable_era.is_zero(),
+				Error::<T>::UnclaimedRewardsRemaining
+			);
+
+			T::Currency::unreserve(&delegator, staked_value);
+
+			Self::update_delegation_info(&delegator, &provider_id, Default::default());
+			Self::index_delegation(
+				&delegator,
+				&provider_id,
+				DelegationAction::Unstake,
+				current_era,
+				staked_value,
+			);
+			Self::deposit_event(Event::<T>::Withdrawn { who: delegator, amount: staked_value });
+			Ok(().into())
+		}
+
 		/// Update the delegator info for the `(delegator, provider_id)` pairing.
 		/// If delegator info is empty, remove it from the DB. Otherwise, store it.
+		///
+		/// Number of providers the delegator stakes on is updated accordingly.
 		fn update_delegation_info(
 			delegator: &T::AccountId,
 			provider_id: &T::ProviderId,
 			info: Delegation<BalanceOf<T>>,
 		) {
+			let was_staked =
+				!DelegationInfo::<T>::get(delegator, provider_id).latest_staked_value().is_zero();
+			let is_staked = !info.latest_staked_value().is_zero();
+
 			if info.is_empty() {
 				DelegationInfo::<T>::remove(delegator, provider_id)
 			} else {
 				DelegationInfo::<T>::insert(delegator, provider_id, info)
 			}
+
+			match (was_staked, is_staked) {
+				(false, true) => {
+					let first = DelegatorProviderCount::<T>::mutate(delegator, |count| {
+						*count = count.saturating_add(1);
+						*count == 1
+					});
+					if first {
+						DelegatorCount::<T>::mutate(|count| *count = count.saturating_add(1));
+					}
+					// Callers ensure there's a slot through `has_delegator_slot`
+					ProviderDelegators::<T>::mutate(provider_id, |delegators| {
+						let _ = delegators.try_push(delegator.clone());
+					});
+				},
+				(true, false) => {
+					let last = DelegatorProviderCount::<T>::mutate_exists(delegator, |count| {
+						*count = count.map(|c| c.saturating_sub(1)).filter(|c| !c.is_zero());
+						count.is_none()
+					});
+					if last {
+						DelegatorCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+					}
+					ProviderDelegators::<T>::mutate_exists(provider_id, |delegators| {
+						if let Some(list) = delegators {
+							list.retain(|d| d != delegator);
+							if list.is_empty() {
+								*delegators = None;
+							}
+						}
+					});
+				},
+				_ => {},
+			}
+		}
+
+		/// Whether a new delegator can stake on `provider_id`.
+		fn has_delegator_slot(provider_id: &T::ProviderId) -> bool {
+			(ProviderDelegators::<T>::decode_len(provider_id).unwrap_or_default() as u32) <
+				T::MaxDelegatorsPerProvider::get()
+		}
+
+		/// Get the accounts currently staking on `provider_id`.
+		pub fn delegators_of(provider_id: T::ProviderId) -> Vec<T::AccountId> {
+			ProviderDelegators::<T>::get(&provider_id).into_inner()
+		}
+
+		/// Get the total stake, bond included, locked on `provider_id` in the current era.
+		pub fn provider_tvl(provider_id: T::ProviderId) -> BalanceOf<T> {
+			<ProviderEraInfo<T>>::get(&provider_id, Self::current_era())
+				.map(|info| info.total)
+				.unwrap_or_default()
+		}
+
+		/// Get the total stake locked on all providers in the current era.
+		pub fn total_tvl() -> BalanceOf<T> {
+			<EraState<T>>::get(Self::current_era())
+				.map(|state| state.staked)
+				.unwrap_or_default()
+		}
+
+		/// Get the sum of the latest stakes of `delegator` over all providers.
+		pub fn delegator_total_staked(delegator: &T::AccountId) -> BalanceOf<T> {
+			DelegationInfo::<T>::iter_prefix_values(delegator).fold(
+				Zero::zero(),
+				|total: BalanceOf<T>, delegation| {
+					total.saturating_add(delegation.latest_staked_value())
+				},
+			)
+		}
+
+		/// Estimate the yearly return of delegating on `provider_id`, extrapolating the rewards
+		/// accumulated so far in the current era over the whole era and `blocks_per_year`.
+		pub fn estimated_delegator_apr(
+			provider_id: &T::ProviderId,
+			blocks_per_year: u32,
+		) -> Perbill {
+			let era = Self::era();
+			let now = frame_system::Pallet::<T>::block_number();
+			let elapsed: u128 = era.elapsed_blocks(now).saturated_into();
+			let provider_era_info = match ProviderEraInfo::<T>::get(provider_id, era.current) {
+				Some(info) if !elapsed.is_zero() => info,
+				_ => return Perbill::zero(),
+			};
+			let total_delegated = provider_era_info.total.saturating_sub(provider_era_info.bond);
+			let eras_per_year = blocks_per_year.checked_div(era.length).unwrap_or_default();
+			if total_delegated.is_zero() || eras_per_year.is_zero() {
+				return Perbill::zero()
+			}
+
+			let accumulated: u128 = RewardAccumulator::<T>::get().saturated_into();
+			let era_info = EraMetadata {
+				rewards: (accumulated.saturating_mul(era.length.into()) / elapsed).saturated_into(),
+				staked: Self::total_tvl(),
+			};
+			let (_, delegators_reward) = Self::split_provider_delegators_rewards(
+				provider_era_info.commission,
+				&provider_era_info,
+				&era_info,
+			);
+			let yearly_reward =
+				Self::score_adjusted_reward(provider_era_info.score, delegators_reward)
+					.saturating_mul(eras_per_year.into());
+			Perbill::from_rational(yearly_reward, total_delegated)
+		}
+
+		/// Estimate the delegator APR of all active providers, highest first.
+		pub fn all_providers_apr(blocks_per_year: u32) -> Vec<(T::ProviderId, Perbill)> {
+			let mut aprs: Vec<_> = Self::active_providers()
+				.into_iter()
+				.map(|provider_id| {
+					let apr = Self::estimated_delegator_apr(&provider_id, blocks_per_year);
+					(provider_id, apr)
+				})
+				.collect();
+			aprs.sort_by(|a, b| b.1.cmp(&a.1));
+			aprs
+		}
+
+		/// Get the staking totals of the eras from `from_era` to `to_era`, both included. At most
+		/// `MaxEraHistoryQuery` eras are queried, eras which were pruned or haven't started yet are
+		/// skipped.
+		pub fn era_history(
+			from_era: EraIndex,
+			to_era: EraIndex,
+		) -> Vec<EraHistoryEntry<BalanceOf<T>>> {
+			let providers: Vec<T::ProviderId> = ProviderInfo::<T>::iter_keys().collect();
+			Self::history_eras(from_era, to_era)
+				.filter_map(|era| {
+					let era_info = <EraState<T>>::get(era)?;
+					let provider_count = providers
+						.iter()
+						.filter(|provider_id| ProviderEraInfo::<T>::contains_key(provider_id, era))
+						.count() as u32;
+					Some(EraHistoryEntry {
+						era,
+						total_staked: era_info.staked,
+						total_rewards: era_info.rewards,
+						provider_count,
+					})
+				})
+				.collect()
+		}
+
+		/// Get the staking totals of `provider_id` in the eras from `from_era` to `to_era`, both
+		/// included. At most `MaxEraHistoryQuery` eras are queried, eras without provider info
+		/// are skipped.
+		pub fn provider_history(
+			provider_id: T::ProviderId,
+			from_era: EraIndex,
+			to_era: EraIndex,
+		) -> Vec<ProviderEraEntry<BalanceOf<T>>> {
+			Self::history_eras(from_era, to_era)
+				.filter_map(|era| {
+					let provider_era_info = <ProviderEraInfo<T>>::get(&provider_id, era)?;
+					let total_rewards = <EraState<T>>::get(era)
+						.filter(|era_info| !era_info.staked.is_zero())
+						.map(|era_info| {
+							Perbill::from_rational(provider_era_info.total, era_info.staked) *
+								era_info.rewards
+						})
+						.unwrap_or_default();
+					Some(ProviderEraEntry {
+						era,
+						bond: provider_era_info.bond,
+						total_staked: provider_era_info.total,
+						delegator_count: provider_era_info.delegator_count,
+						total_rewards,
+						provider_reward_claimed: provider_era_info.provider_reward_claimed,
+					})
+				})
+				.collect()
+		}
+
+		/// Eras covered by a history query, capped at `MaxEraHistoryQuery`.
+		fn history_eras(from_era: EraIndex, to_era: EraIndex) -> impl Iterator<Item = EraIndex> {
+			(from_era..=to_era).take(T::MaxEraHistoryQuery::get() as usize)
+		}
+
+		/// Get the rewards `delegator` could claim from `provider_id` over all claimable eras.
+		pub fn pending_rewards(
+			delegator: T::AccountId,
+			provider_id: T::ProviderId,
+		) -> BalanceOf<T> {
+			let unregistered_era = match ProviderInfo::<T>::get(&provider_id).map(|p| p.status) {
+				Some(ProviderStatus::Inactive(era)) => era,
+				Some(ProviderStatus::Active) => EraIndex::MAX,
+				None => return Zero::zero(),
+			};
+			let last_era = Self::current_era().min(unregistered_era);
+
+			let mut delegation_info = <DelegationInfo<T>>::get(&delegator, &provider_id);
+			let mut total: BalanceOf<T> = Zero::zero();
+			loop {
+				let earning = delegation_info.oldest_earning_stake().unwrap_or_default();
+				let (era, staked) = delegation_info.claim();
+				if staked.is_zero() || era >= last_era {
+					break
+				}
+				if Self::is_expired(era, Self::current_era()) {
+					continue
+				}
+				if let Ok((reward, _, _)) = Self::delegator_era_reward(&provider_id, era, earning) {
+					total = total.saturating_add(reward);
+				}
+			}
+			total
+		}
+
+		/// Get the reward the owner of `provider_id` could claim for `era`.
+		pub fn provider_pending_reward(provider_id: T::ProviderId, era: EraIndex) -> BalanceOf<T> {
+			let provider_info = match ProviderInfo::<T>::get(&provider_id) {
+				Some(provider_info) => provider_info,
+				None => return Zero::zero(),
+			};
+			let claimable = match provider_info.status {
+				ProviderStatus::Inactive(unregistered_era) => era < unregistered_era,
+				ProviderStatus::Active => true,
+			};
+			let current_era = Self::current_era();
+			if !claimable || era >= current_era || Self::is_expired(era, current_era) {
+				return Zero::zero()
+			}
+			match (<ProviderEraInfo<T>>::get(&provider_id, era), <EraState<T>>::get(era)) {
+				(Some(provider_era_info), Some(era_info))
+					if !provider_era_info.provider_reward_claimed =>
+					Self::split_provider_delegators_rewards(
+						provider_era_info.commission,
+						&provider_era_info,
+						&era_info,
+					)
+					.0,
+				_ => Zero::zero(),
+			}
 		}
 
 		fn snapshot_era_rewards(era: EraIndex) {
@@ -786,27 +2183,85 @@ pub mod pallet {
 			EraState::<T>::insert(era, state);
 		}
 
-		fn rotate_provider_era_info(era: EraIndex) -> u64 {
+		/// Carry the era info of active providers over to the next era and apply their commission
+		/// changes due by then, recording the commission of the next era. Providers without stake
+		/// for `MaxInactiveEras` consecutive eras are unregistered. Returns the number of active
+		/// providers.
+		fn rotate_provider_era_info(era: EraIndex) -> u32 {
 			let next_era = era + 1;
-			let mut consumed_weight = 0;
-			// TODO: iterate and rotate only active providers.
-			for (provider_id, provider) in ProviderInfo::<T>::iter() {
-				consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(1));
-				if let ProviderStatus::Inactive(_) = provider.status {
-					continue
+			let providers = ActiveProviders::<T>::get();
+			// Abandoned providers are deducted once unregistered below
+			EraActiveProviderCount::<T>::insert(next_era, providers.len() as u32);
+			let mut abandoned = Vec::new();
+			for provider_id in providers.iter() {
+				let new_commission = Self::apply_pending_commission(provider_id, next_era);
+				if let Some(mut info) = <ProviderEraInfo<T>>::get(provider_id, era) {
+					if info.total.is_zero() {
+						let inactive_eras =
+							ProviderInactiveEras::<T>::mutate(provider_id, |eras| {
+								*eras = eras.saturating_add(1);
+								*eras
+							});
+						if inactive_eras >= T::MaxInactiveEras::get() {
+							abandoned.push(provider_id.clone());
+						}
+					} else {
+						ProviderInactiveEras::<T>::remove(provider_id);
+					}
+					info.provider_reward_claimed = false;
+					info.delegators_reward_paid = Zero::zero();
+					info.delegators_fully_claimed = false;
+					if let Some(commission) = new_commission {
+						info.commission = commission;
+					}
+					info.score = T::ProviderScore::provider_score(provider_id);
+					Self::add_era_provider(next_era, provider_id);
+					ProviderEraInfo::<T>::insert(provider_id, next_era, info);
 				}
+			}
 
-				if let Some(mut info) = <ProviderEraInfo<T>>::get(&provider_id, era) {
-					info.provider_reward_claimed = false;
-					ProviderEraInfo::<T>::insert(&provider_id, next_era, info);
-					consumed_weight =
-						consumed_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-				} else {
-					consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(1));
+			for provider_id in abandoned {
+				if Self::unregister_provider(provider_id.clone()).is_ok() {
+					ProviderInactiveEras::<T>::remove(&provider_id);
+					Self::deposit_event(Event::<T>::ProviderAutoUnregistered { provider_id });
 				}
 			}
 
-			consumed_weight
+			providers.len() as u32
+		}
+
+		/// Record `provider_id` among the providers with an entry in `ProviderEraInfo` for `era`.
+		fn add_era_provider(era: EraIndex, provider_id: &T::ProviderId) {
+			let index = EraProviderCount::<T>::mutate(era, |count| {
+				*count = count.saturating_add(1);
+				*count - 1
+			});
+			EraProviders::<T>::insert(era, index, provider_id);
+		}
+
+		/// Apply the pending commission of `provider_id` if it takes effect by `era`. Returns the
+		/// applied commission.
+		fn apply_pending_commission(provider_id: &T::ProviderId, era: EraIndex) -> Option<Perbill> {
+			let commission = match PendingCommissions::<T>::get(provider_id) {
+				Some((commission, effective_era)) if effective_era <= era => commission,
+				_ => return None,
+			};
+			ProviderInfo::<T>::mutate(provider_id, |info| {
+				if let Some(info) = info {
+					info.commission = commission;
+				}
+			});
+			PendingCommissions::<T>::remove(provider_id);
+			Self::deposit_event(Event::<T>::CommissionChanged {
+				provider_id: provider_id.clone(),
+				commission,
+			});
+			Some(commission)
+		}
+
+		/// Number of currently active providers.
+		pub fn active_provider_count() -> u32 {
+			ActiveProviders::<T>::decode_len().unwrap_or_default() as u32
 		}
 
 		fn is_active_provider(provider_id: &T::ProviderId) -> bool {
@@ -814,14 +2269,236 @@ pub mod pallet {
 				.map_or(false, |provider_info| provider_info.status == ProviderStatus::Active)
 		}
 
-		/// Calculate reward split between provider and delegators.
+		/// Calculate the reward of a delegator whose stake on `provider_id` earning rewards in
+		/// `era` is `earning`, along with its share and the rewards of all provider's delegators
+		/// for the era.
+		fn delegator_era_reward(
+			provider_id: &T::ProviderId,
+			era: EraIndex,
+			earning: BalanceOf<T>,
+		) -> Result<(BalanceOf<T>, Perbill, BalanceOf<T>), DispatchError> {
+			let provider_era_info = <ProviderEraInfo<T>>::get(provider_id, era).unwrap_or_default();
+			let era_info = <EraState<T>>::get(era).ok_or(Error::<T>::UnknownEra)?;
+			let (_, delegators_reward) = Self::split_provider_delegators_rewards(
+				provider_era_info.commission,
+				&provider_era_info,
+				&era_info,
+			);
+			let delegators_reward =
+				Self::score_adjusted_reward(provider_era_info.score, delegators_reward);
+			let total_delegated = provider_era_info.total.saturating_sub(provider_era_info.bond);
+			let share = if total_delegated.is_zero() || delegators_reward.is_zero() {
+				Perbill::zero()
+			} else {
+				Perbill::from_rational(earning, total_delegated)
+			};
+			Ok((share * delegators_reward, share, delegators_reward))
+		}
+
+		/// Scale the `ScoreRewardMultiplier` part of `delegators_reward` by the performance `score`
+		/// recorded for the era. Rewards of providers without a score are left as they are.
+		fn score_adjusted_reward(
+			score: Option<u32>,
+			delegators_reward: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			let score = match score {
+				Some(score) => score.min(MAX_PROVIDER_SCORE),
+				None => return delegators_reward,
+			};
+			let scored_part = T::ScoreRewardMultiplier::get() * delegators_reward;
+			let score_ratio = Perbill::from_rational(score, MAX_PROVIDER_SCORE);
+			delegators_reward
+				.saturating_sub(scored_part)
+				.saturating_add(score_ratio * scored_part)
+		}
+
+		/// Append rewards claimed in this block to the delegator's `ClaimHistory`, dropping the
+		/// oldest records once `MaxClaimHistory` is reached.
+		#[cfg(feature = "claim-history")]
+		fn note_claims(
+			delegator: &T::AccountId,
+			provider_id: &T::ProviderId,
+			claims: &[(EraIndex, BalanceOf<T>)],
+		) {
+			let claimed_at = frame_system::Pallet::<T>::block_number();
+			ClaimHistory::<T>::mutate(delegator, provider_id, |history| {
+				for &(era, amount) in claims {
+					if !history.is_empty() && history.len() as u32 >= T::MaxClaimHistory::get() {
+						history.remove(0);
+					}
+					let _ = history.try_push(ClaimRecord { era, amount, claimed_at });
+				}
+			});
+		}
+
+		/// Lock `amount` just paid out to `delegator` under a new schedule vesting over
+		/// `VestingPeriod` blocks. Fully vested schedules are dropped first. If the delegator
+		/// still has `MaxVestingSchedules` schedules, the amount still locked by the newest one is
+		/// merged into the new schedule. Returns the block at which the reward is fully vested,
+		/// or `None` if vesting is disabled.
+		fn vest_delegator_reward(
+			delegator: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Option<T::BlockNumber> {
+			let period = T::VestingPeriod::get();
+			if period.is_zero() || amount.is_zero() {
+				return None
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut schedules = RewardVesting::<T>::get(delegator);
+			schedules.retain(|vesting| !vesting.locked_at(now).is_zero());
+			let mut locked = amount;
+			if !schedules.is_empty() && schedules.len() as u32 >= T::MaxVestingSchedules::get() {
+				let newest = schedules.remove(schedules.len() - 1);
+				locked = locked.saturating_add(newest.locked_at(now));
+			}
+			let vesting = RewardVestingInfo::new(locked, period, now);
+			// Only fails if `MaxVestingSchedules` is zero
+			let _ = schedules.try_push(vesting);
+			Self::update_reward_vesting(delegator, schedules, now);
+			Some(vesting.end_block())
+		}
+
+		/// Store the vesting `schedules` of `delegator` and lock the amount they still hold at
+		/// block `now`, which is returned.
+		fn update_reward_vesting(
+			delegator: &T::AccountId,
+			schedules: BoundedVec<
+				RewardVestingInfo<BalanceOf<T>, T::BlockNumber>,
+				T::MaxVestingSchedules,
+			>,
+			now: T::BlockNumber,
+		) -> BalanceOf<T> {
+			let locked = schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, vesting| {
+				total.saturating_add(vesting.locked_at(now))
+			});
+			if schedules.is_empty() {
+				T::Currency::remove_lock(REWARD_VESTING_ID, delegator);
+				RewardVesting::<T>::remove(delegator);
+			} else {
+				T::Currency::set_lock(
+					REWARD_VESTING_ID,
+					delegator,
+					locked,
+					WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE,
+				);
+				RewardVesting::<T>::insert(delegator, schedules);
+			}
+			locked
+		}
+
+		/// Whether rewards of `era` can no longer be claimed in `current_era`.
+		fn is_expired(era: EraIndex, current_era: EraIndex) -> bool {
+			current_era.saturating_sub(era) > T::MaxClaimableEraAge::get()
+		}
+
+		/// Send the rewards of providers and their delegators that were left unclaimed in expired
+		/// eras to `ExpiredRewardDestination`, resuming from `ExpiryCursor`. The providers of an
+		/// era are read from `EraProviders`, so those unregistered since the era are included. At
+		/// most `MaxProvidersExpiredPerBlock` providers are checked, moving on to the next era
+		/// counts as a check. Returns the number of providers checked and the number of providers
+		/// with expired rewards.
+		fn expire_era_rewards(current_era: EraIndex) -> (u32, u32) {
+			let (mut era, mut index) = ExpiryCursor::<T>::get();
+			let (mut checked, mut expired) = (0, 0);
+			while checked < T::MaxProvidersExpiredPerBlock::get() &&
+				Self::is_expired(era, current_era)
+			{
+				checked += 1;
+				let provider_id = match EraProviders::<T>::get(era, index) {
+					Some(provider_id) => provider_id,
+					None => {
+						era += 1;
+						index = 0;
+						continue
+					},
+				};
+				index += 1;
+				if Self::expire_provider_rewards(provider_id, era) {
+					expired += 1;
+				}
+			}
+			if checked > 0 {
+				ExpiryCursor::<T>::put((era, index));
+			}
+			(checked, expired)
+		}
+
+		/// Send the rewards of `provider_id` and its delegators that were left unclaimed in `era`
+		/// to `ExpiredRewardDestination`. Returns whether any rewards expired.
+		fn expire_provider_rewards(provider_id: T::ProviderId, era: EraIndex) -> bool {
+			let (era_info, mut provider_era_info) =
+				match (<EraState<T>>::get(era), <ProviderEraInfo<T>>::get(&provider_id, era)) {
+					(Some(era_info), Some(info)) => (era_info, info),
+					_ => return false,
+				};
+			let (provider_reward, delegators_reward) =
+				Self::unclaimed_era_rewards(&provider_era_info, &era_info);
+			let amount = provider_reward.saturating_add(delegators_reward);
+			if amount.is_zero() {
+				return false
+			}
+
+			let imbalance = match T::Currency::withdraw(
+				&Self::account_id(),
+				amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			) {
+				Ok(imbalance) => imbalance,
+				Err(_) => return false,
+			};
+			T::ExpiredRewardDestination::on_unbalanced(imbalance);
+			provider_era_info.provider_reward_claimed = true;
+			provider_era_info.delegators_reward_paid =
+				provider_era_info.delegators_reward_paid.saturating_add(delegators_reward);
+			provider_era_info.delegators_fully_claimed = true;
+			ProviderEraInfo::<T>::insert(&provider_id, era, provider_era_info);
+			Self::deposit_event(Event::<T>::RewardExpired { provider_id, era, amount });
+			true
+		}
+
+		/// Parts of the era rewards of a provider and of its delegators which were neither claimed
+		/// nor expired yet.
+		fn unclaimed_era_rewards(
+			provider_era_info: &ProviderEraMetadata<BalanceOf<T>>,
+			era_info: &EraMetadata<BalanceOf<T>>,
+		) -> (BalanceOf<T>, BalanceOf<T>) {
+			let (provider_reward, delegators_reward) = Self::split_provider_delegators_rewards(
+				provider_era_info.commission,
+				provider_era_info,
+				era_info,
+			);
+			let provider_reward = if provider_era_info.provider_reward_claimed {
+				Zero::zero()
+			} else {
+				provider_reward
+			};
+			(
+				provider_reward,
+				delegators_reward.saturating_sub(provider_era_info.delegators_reward_paid),
+			)
+		}
+
+		/// Calculate reward split between provider and delegators, the provider keeping
+		/// `commission` of the rewards. Rewards of an era are split with the commission recorded in
+		/// its `ProviderEraMetadata`.
 		pub(crate) fn split_provider_delegators_rewards(
+			commission: Perbill,
 			provider_era_info: &ProviderEraMetadata<BalanceOf<T>>,
 			era_info: &EraMetadata<BalanceOf<T>>,
 		) -> (BalanceOf<T>, BalanceOf<T>) {
+			// Nothing to split if the era has no rewards, e.g. when no imbalance was handled
+			if provider_era_info.total.is_zero() ||
+				era_info.staked.is_zero() ||
+				era_info.rewards.is_zero()
+			{
+				return (Zero::zero(), Zero::zero())
+			}
 			let provider_rewards =
 				Perbill::from_rational(provider_era_info.total, era_info.staked) * era_info.rewards;
-			let provider_reward_part = T::ProviderRewardsPercentage::get() * provider_rewards;
+			let provider_reward_part = commission * provider_rewards;
 			let delegators_reward_part = provider_rewards.saturating_sub(provider_reward_part);
 			(provider_reward_part, delegators_reward_part)
 		}
@@ -0,0 +1,363 @@
+//! Storage migrations for dAPI staking pallet.
+
+use crate::{
+	types::{
+		Delegation, EraIndex, EraStake, ProviderEraMetadata, ProviderMetadata, ProviderStatus,
+	},
+	ActiveProviders, BalanceOf, Config, DelegationInfo, DelegatorCount, DelegatorProviderCount,
+	Era, EraActiveProviderCount, EraProviderCount, EraProviders, ExpiryCursor, Pallet,
+	ProviderDelegators, ProviderEraInfo, ProviderInfo,
+};
+use codec::{Decode, Encode, HasCompact};
+use frame_support::{
+	log,
+	storage::unhashed,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::traits::Zero;
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	marker::PhantomData,
+	prelude::*,
+};
+
+/// Populates `DelegatorProviderCount` from existing `DelegationInfo` entries.
+pub struct PopulateDelegatorProviderCount<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for PopulateDelegatorProviderCount<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			log::info!(target: "dapi-staking", "DelegatorProviderCount already populated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut reads: Weight = 1;
+		let mut counts = BTreeMap::<T::AccountId, u32>::new();
+		for (delegator, provider_id) in DelegationInfo::<T>::iter_keys() {
+			reads += 1;
+			if !old_latest_staked_value::<T>(&delegator, &provider_id).is_zero() {
+				*counts.entry(delegator).or_default() += 1;
+			}
+		}
+
+		let writes = counts.len() as Weight + 1;
+		for (delegator, count) in counts {
+			DelegatorProviderCount::<T>::insert(delegator, count);
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "DelegatorProviderCount populated for {} delegators", writes - 1);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+/// Provider era metadata layout before delegator claims were tracked.
+#[derive(Encode, Decode)]
+struct OldProviderEraMetadata<Balance: HasCompact> {
+	#[codec(compact)]
+	bond: Balance,
+	#[codec(compact)]
+	total: Balance,
+	#[codec(compact)]
+	delegator_count: u32,
+	provider_reward_claimed: bool,
+}
+
+/// Provider era metadata layout from storage version 2, before the commission was recorded per
+/// era.
+#[derive(Encode, Decode)]
+struct ProviderEraMetadataV2<Balance: HasCompact> {
+	#[codec(compact)]
+	bond: Balance,
+	#[codec(compact)]
+	total: Balance,
+	#[codec(compact)]
+	delegator_count: u32,
+	provider_reward_claimed: bool,
+	#[codec(compact)]
+	delegators_reward_paid: Balance,
+	delegators_fully_claimed: bool,
+}
+
+/// Migrates `ProviderEraInfo` entries to the layout tracking delegator claims. Delegators are
+/// assumed to have claimed nothing from existing entries.
+pub struct MigrateProviderEraInfoToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProviderEraInfoToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			log::info!(target: "dapi-staking", "ProviderEraInfo already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		// Entries are written in the layout `MigrateProviderEraInfoToV8` migrates from
+		let mut translated: Weight = 0;
+		for (provider_id, era) in ProviderEraInfo::<T>::iter_keys() {
+			let key = ProviderEraInfo::<T>::hashed_key_for(&provider_id, era);
+			match unhashed::get::<OldProviderEraMetadata<BalanceOf<T>>>(&key) {
+				Some(old) => {
+					translated += 1;
+					unhashed::put(
+						&key,
+						&ProviderEraMetadataV2 {
+							bond: old.bond,
+							total: old.total,
+							delegator_count: old.delegator_count,
+							provider_reward_claimed: old.provider_reward_claimed,
+							delegators_reward_paid: Zero::zero(),
+							delegators_fully_claimed: false,
+						},
+					);
+				},
+				None => log::warn!(
+					target: "dapi-staking",
+					"Failed to decode provider era entry of era {}",
+					era
+				),
+			}
+		}
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "Migrated {} provider era entries", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+/// Populates `ActiveProviders` from existing `ProviderInfo` entries.
+pub struct PopulateActiveProviders<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for PopulateActiveProviders<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			log::info!(target: "dapi-staking", "ActiveProviders already populated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut reads: Weight = 1;
+		let mut providers = BTreeSet::<T::ProviderId>::new();
+		// Entries still have the layout without commission, see `MigrateProviderInfoToV5`
+		for provider_id in ProviderInfo::<T>::iter_keys() {
+			reads += 1;
+			let key = ProviderInfo::<T>::hashed_key_for(&provider_id);
+			if unhashed::get::<OldProviderMetadata<T::AccountId>>(&key)
+				.map_or(false, |provider| provider.status == ProviderStatus::Active)
+			{
+				providers.insert(provider_id);
+			}
+		}
+
+		log::info!(target: "dapi-staking", "ActiveProviders populated with {} providers", providers.len());
+		ActiveProviders::<T>::put(providers);
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, 2)
+	}
+}
+
+/// Populates `ProviderDelegators` from existing `DelegationInfo` entries. Delegators beyond the
+/// max number of delegators per provider are left out.
+pub struct PopulateProviderDelegators<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for PopulateProviderDelegators<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 4 {
+			log::info!(target: "dapi-staking", "ProviderDelegators already populated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut reads: Weight = 1;
+		let mut delegators = BTreeMap::<T::ProviderId, Vec<T::AccountId>>::new();
+		for (delegator, provider_id) in DelegationInfo::<T>::iter_keys() {
+			reads += 1;
+			if !old_latest_staked_value::<T>(&delegator, &provider_id).is_zero() {
+				delegators.entry(provider_id).or_default().push(delegator);
+			}
+		}
+
+		let writes = delegators.len() as Weight + 1;
+		for (provider_id, mut list) in delegators {
+			list.truncate(T::MaxDelegatorsPerProvider::get() as usize);
+			if let Ok(list) = BoundedVec::<_, T::MaxDelegatorsPerProvider>::try_from(list) {
+				ProviderDelegators::<T>::insert(provider_id, list);
+			}
+		}
+		StorageVersion::new(4).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "ProviderDelegators populated for {} providers", writes - 1);
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+#[derive(Encode, Decode)]
+struct OldProviderMetadata<AccountId> {
+	owner: AccountId,
+	status: ProviderStatus,
+	bond_withdrawn: bool,
+}
+
+/// Migrates `ProviderInfo` entries to the layout with a per provider commission. Existing
+/// providers get `ProviderRewardsPercentage` as commission.
+pub struct MigrateProviderInfoToV5<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProviderInfoToV5<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 5 {
+			log::info!(target: "dapi-staking", "ProviderInfo already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let commission = T::ProviderRewardsPercentage::get();
+		let mut translated: Weight = 0;
+		ProviderInfo::<T>::translate::<OldProviderMetadata<T::AccountId>, _>(|_, old| {
+			translated += 1;
+			Some(ProviderMetadata {
+				owner: old.owner,
+				status: old.status,
+				bond_withdrawn: old.bond_withdrawn,
+				commission,
+			})
+		});
+		StorageVersion::new(5).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "Migrated {} providers", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+/// Era stake layout before stakes had an effective era.
+#[derive(Encode, Decode)]
+struct OldEraStake<Balance: HasCompact> {
+	#[codec(compact)]
+	amount: Balance,
+	#[codec(compact)]
+	era: EraIndex,
+}
+
+/// Latest staked value of a delegation which still has the layout migrated by
+/// `MigrateDelegationInfoToV6`.
+fn old_latest_staked_value<T: Config>(
+	delegator: &T::AccountId,
+	provider_id: &T::ProviderId,
+) -> BalanceOf<T> {
+	let key = DelegationInfo::<T>::hashed_key_for(delegator, provider_id);
+	unhashed::get::<Vec<OldEraStake<BalanceOf<T>>>>(&key)
+		.and_then(|stakes| stakes.last().map(|stake| stake.amount))
+		.unwrap_or_default()
+}
+
+/// Migrates `DelegationInfo` entries to the layout with an effective era per stake. Existing
+/// stakes earn rewards from the era they were made in.
+pub struct MigrateDelegationInfoToV6<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateDelegationInfoToV6<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 6 {
+			log::info!(target: "dapi-staking", "DelegationInfo already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated: Weight = 0;
+		DelegationInfo::<T>::translate::<Vec<OldEraStake<BalanceOf<T>>>, _>(|_, _, old| {
+			translated += 1;
+			Some(Delegation::from_stakes(
+				old.into_iter().map(|stake| EraStake::new(stake.amount, stake.era)).collect(),
+			))
+		});
+		StorageVersion::new(6).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "Migrated {} delegations", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+/// Populates `DelegatorCount` from `DelegatorProviderCount` and the current era's
+/// `EraActiveProviderCount` from `ActiveProviders`. Counts of past eras are unknown.
+pub struct PopulateEraCountersToV7<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for PopulateEraCountersToV7<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 7 {
+			log::info!(target: "dapi-staking", "Era counters already populated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let delegators = DelegatorProviderCount::<T>::iter_keys().count() as u32;
+		DelegatorCount::<T>::put(delegators);
+		let current_era = Era::<T>::get().current;
+		EraActiveProviderCount::<T>::insert(current_era, Pallet::<T>::active_provider_count());
+		StorageVersion::new(7).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "DelegatorCount populated with {} delegators", delegators);
+		T::DbWeight::get().reads_writes(delegators as Weight + 3, 3)
+	}
+}
+
+/// Migrates `ProviderEraInfo` entries to the layout recording the commission and score of each
+/// era. Those of past eras are unknown, so existing entries get the current commission and score
+/// of their provider.
+pub struct MigrateProviderEraInfoToV8<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProviderEraInfoToV8<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 8 {
+			log::info!(target: "dapi-staking", "ProviderEraInfo commissions and scores already recorded, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated: Weight = 0;
+		ProviderEraInfo::<T>::translate::<ProviderEraMetadataV2<BalanceOf<T>>, _>(
+			|provider_id, _, old| {
+				translated += 1;
+				let commission = ProviderInfo::<T>::get(&provider_id)
+					.map_or_else(T::ProviderRewardsPercentage::get, |info| info.commission);
+				let score = T::ProviderScore::provider_score(&provider_id);
+				Some(ProviderEraMetadata {
+					bond: old.bond,
+					total: old.total,
+					delegator_count: old.delegator_count,
+					provider_reward_claimed: old.provider_reward_claimed,
+					delegators_reward_paid: old.delegators_reward_paid,
+					delegators_fully_claimed: old.delegators_fully_claimed,
+					commission,
+					score,
+				})
+			},
+		);
+		StorageVersion::new(8).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "Recorded commissions and scores of {} provider era entries", translated);
+		T::DbWeight::get().reads_writes(3 * translated + 1, translated + 1)
+	}
+}
+
+/// Populates `EraProviders` from existing `ProviderEraInfo` entries and starts `ExpiryCursor` at
+/// the first era whose rewards didn't expire yet, earlier ones having been expired at their era
+/// change.
+pub struct PopulateEraProvidersToV9<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for PopulateEraProvidersToV9<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 9 {
+			log::info!(target: "dapi-staking", "EraProviders already populated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut indexed: Weight = 0;
+		for (provider_id, era) in ProviderEraInfo::<T>::iter_keys() {
+			indexed += 1;
+			let index = EraProviderCount::<T>::mutate(era, |count| {
+				*count = count.saturating_add(1);
+				*count - 1
+			});
+			EraProviders::<T>::insert(era, index, provider_id);
+		}
+		let current_era = Era::<T>::get().current;
+		ExpiryCursor::<T>::put((current_era.saturating_sub(T::MaxClaimableEraAge::get()), 0));
+		StorageVersion::new(9).put::<Pallet<T>>();
+
+		log::info!(target: "dapi-staking", "EraProviders populated with {} provider era entries", indexed);
+		T::DbWeight::get().reads_writes(2 * indexed + 2, 2 * indexed + 2)
+	}
+}
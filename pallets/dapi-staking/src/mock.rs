@@ -4,6 +4,7 @@ use frame_support::{
 	traits::{Currency, OnFinalize, OnInitialize},
 	PalletId,
 };
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_io::TestExternalities;
 use sp_runtime::{
@@ -12,7 +13,7 @@ use sp_runtime::{
 	Perbill,
 };
 
-use crate::{self as pallet_dapi_staking, types::*, weights};
+use crate::{self as pallet_dapi_staking, traits::ProviderScoreInfo, types::*, weights};
 
 pub(crate) type AccountId = u64;
 pub(crate) type BlockNumber = u64;
@@ -23,10 +24,22 @@ type Block = frame_system::mocking::MockBlock<TestRuntime>;
 
 pub(crate) const EXISTENTIAL_DEPOSIT: Balance = 2;
 pub(crate) const MIN_PROVIDER_STAKE: Balance = 10;
+pub(crate) const MAX_PROVIDER_BOND: Balance = 1000;
 pub(crate) const PROVIDER_REWARD_PERCENTAGE: u32 = 80;
+pub(crate) const SCORE_REWARD_PERCENTAGE: u32 = 50;
 pub(crate) const MAX_NUMBER_OF_DELEGATORS: u32 = 5;
 pub(crate) const MIN_DELEGATOR_STAKE: Balance = 10;
+pub(crate) const MAX_DELEGATIONS_PER_DELEGATOR: u32 = 3;
 pub(crate) const MAX_UNLOCKING_CHUNKS: u32 = 4;
+pub(crate) const MAX_BATCH_REGISTRATIONS: u32 = 4;
+pub(crate) const MAX_PROVIDERS_PER_ERA_CLEAN: u32 = 2;
+pub(crate) const MAX_CLAIMABLE_ERA_AGE: u32 = 4;
+pub(crate) const MAX_PROVIDERS_EXPIRED_PER_BLOCK: u32 = 2;
+pub(crate) const MAX_CLAIM_HISTORY: u32 = 2;
+pub(crate) const MAX_VESTING_SCHEDULES: u32 = 2;
+pub(crate) const PROVIDER_TRANSFER_PERIOD: BlockNumber = 10;
+pub(crate) const COMMISSION_CHANGE_DELAY: u32 = 2;
+pub(crate) const MAX_INACTIVE_ERAS: u32 = 2;
 pub(crate) const UNBONDING_PERIOD: EraIndex = 3;
 pub(crate) const MAX_ERA_STAKE_VALUES: u32 = 8;
 pub(crate) const BLOCKS_PER_ERA: u32 = 3;
@@ -108,14 +121,41 @@ impl pallet_timestamp::Config for TestRuntime {
 
 parameter_types! {
 	pub const MinProviderStake: Balance = MIN_PROVIDER_STAKE;
+	pub const MaxProviderBond: Balance = MAX_PROVIDER_BOND;
 	pub const MaxDelegatorsPerProvider: u32 = MAX_NUMBER_OF_DELEGATORS;
 	pub const MinDelegatorStake: Balance = MIN_DELEGATOR_STAKE;
+	pub const MaxDelegationsPerDelegator: u32 = MAX_DELEGATIONS_PER_DELEGATOR;
 	pub const ProviderRewardsPercentage: Perbill = Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE);
 	pub const DapiStakingPalletId: PalletId = PalletId(*b"mokdpstk");
 	pub const MaxUnlockingChunks: u32 = MAX_UNLOCKING_CHUNKS;
+	pub const MaxBatchRegistrations: u32 = MAX_BATCH_REGISTRATIONS;
+	pub const MaxProvidersPerEraClean: u32 = MAX_PROVIDERS_PER_ERA_CLEAN;
+	pub const MaxInactiveEras: u32 = MAX_INACTIVE_ERAS;
+	pub const MaxClaimableEraAge: u32 = MAX_CLAIMABLE_ERA_AGE;
+	pub const MaxProvidersExpiredPerBlock: u32 = MAX_PROVIDERS_EXPIRED_PER_BLOCK;
+	pub const MaxClaimHistory: u32 = MAX_CLAIM_HISTORY;
+	pub const MaxEraHistoryQuery: u32 = 4;
+	pub const ProviderTransferPeriod: BlockNumber = PROVIDER_TRANSFER_PERIOD;
+	pub const CommissionChangeDelay: u32 = COMMISSION_CHANGE_DELAY;
+	pub static VestingPeriod: BlockNumber = 0;
+	pub const MaxVestingSchedules: u32 = MAX_VESTING_SCHEDULES;
+	pub static StakeEffectDelay: EraIndex = 0;
 	pub const UnbondingPeriod: EraIndex = UNBONDING_PERIOD;
-	pub const MaxEraStakeValues: u32 = MAX_ERA_STAKE_VALUES;
+	pub const DefaultMaxEraStakeValues: u32 = MAX_ERA_STAKE_VALUES;
 	pub const DefaultBlocksPerEra: u32 = BLOCKS_PER_ERA;
+	pub const ScoreRewardMultiplier: Perbill = Perbill::from_percent(SCORE_REWARD_PERCENTAGE);
+	pub static ProviderScores: Vec<(MockProvider, u32)> = Vec::new();
+}
+
+// Score of a provider out of the fixed `ProviderScores` list
+pub struct MockProviderScore;
+impl ProviderScoreInfo<MockProvider> for MockProviderScore {
+	fn provider_score(provider_id: &MockProvider) -> Option<u32> {
+		ProviderScores::get()
+			.into_iter()
+			.find(|(provider, _)| provider == provider_id)
+			.map(|(_, score)| score)
+	}
 }
 
 impl pallet_dapi_staking::Config for TestRuntime {
@@ -125,16 +165,37 @@ impl pallet_dapi_staking::Config for TestRuntime {
 	type ProviderId = MockProvider;
 	type ProviderRewardsPercentage = ProviderRewardsPercentage;
 	type MinProviderStake = MinProviderStake;
+	type MaxProviderBond = MaxProviderBond;
 	type MaxDelegatorsPerProvider = MaxDelegatorsPerProvider;
 	type MinDelegatorStake = MinDelegatorStake;
-	type MaxEraStakeValues = MaxEraStakeValues;
+	type MaxDelegationsPerDelegator = MaxDelegationsPerDelegator;
+	type DefaultMaxEraStakeValues = DefaultMaxEraStakeValues;
+	type StakeEffectDelay = StakeEffectDelay;
 	type UnbondingPeriod = UnbondingPeriod;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxBatchRegistrations = MaxBatchRegistrations;
+	type MaxProvidersPerEraClean = MaxProvidersPerEraClean;
+	type MaxEraHistoryQuery = MaxEraHistoryQuery;
+	type MaxInactiveEras = MaxInactiveEras;
+	type MaxClaimableEraAge = MaxClaimableEraAge;
+	type MaxClaimHistory = MaxClaimHistory;
+	type ProviderTransferPeriod = ProviderTransferPeriod;
+	type CommissionChangeDelay = CommissionChangeDelay;
+	type VestingPeriod = VestingPeriod;
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type ProviderScore = MockProviderScore;
+	type ScoreRewardMultiplier = ScoreRewardMultiplier;
 	type PalletId = DapiStakingPalletId;
+	type ForceEraOrigin = EnsureRoot<AccountId>;
+	type SlashDestination = ();
+	type ExpiredRewardDestination = ();
+	type MaxProvidersExpiredPerBlock = MaxProvidersExpiredPerBlock;
 	type WeightInfo = weights::SubstrateWeight<TestRuntime>;
 }
 
-#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug, scale_info::TypeInfo)]
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Encode, Decode, Debug, scale_info::TypeInfo,
+)]
 pub struct MockProvider([u8; 36]);
 
 impl Default for MockProvider {
@@ -0,0 +1,81 @@
+//! Offchain index of delegation actions.
+//!
+//! When the node runs with offchain indexing enabled (`--enable-offchain-indexing true`), every
+//! change of a delegation and every claimed delegator reward writes a [`DelegationIndexRecord`] to
+//! the persistent offchain database. Redelegations are recorded as an unstake and a delegation,
+//! compounded rewards as a claim and a delegation.
+//!
+//! All keys are SCALE encoded tuples and all values are SCALE encoded:
+//!
+//! - `(RECORD_PREFIX, block_number, index)`: the `index`-th record written in a block.
+//! - `(RECORD_COUNT_PREFIX, block_number)`: the number of records written in a block, as `u32`.
+//!
+//! They can be read with the `offchain_localStorageGet` RPC using the `PERSISTENT` storage kind.
+//!
+//! The index is not fork-safe. The hash of a block isn't known while it executes, so records are
+//! keyed by block number. Blocks of different forks at the same height write to the same keys, and
+//! the records of the block imported last are kept, finalized or not. Records of retracted blocks
+//! aren't removed either. Indexers must only read the records of finalized heights and check them
+//! against the events of the finalized block.
+
+use crate::{
+	pallet::*,
+	types::{DelegationAction, DelegationIndexRecord, EraIndex},
+};
+use codec::Encode;
+use sp_std::prelude::*;
+
+/// Prefix of the keys of records written in a block.
+pub const RECORD_PREFIX: &[u8] = b"dapi-staking/delegation-record";
+/// Prefix of the keys of the number of records written in a block.
+pub const RECORD_COUNT_PREFIX: &[u8] = b"dapi-staking/delegation-record-count";
+
+type IndexRecordOf<T> = DelegationIndexRecord<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::ProviderId,
+	BalanceOf<T>,
+>;
+
+/// Key of the `index`-th record written in `block`.
+pub fn record_key<BlockNumber: Encode>(block: BlockNumber, index: u32) -> Vec<u8> {
+	(RECORD_PREFIX, block, index).encode()
+}
+
+/// Key of the number of records written in `block`.
+pub fn record_count_key<BlockNumber: Encode>(block: BlockNumber) -> Vec<u8> {
+	(RECORD_COUNT_PREFIX, block).encode()
+}
+
+impl<T: Config> Pallet<T> {
+	/// Write a record of `action` to the offchain database.
+	pub(crate) fn index_delegation(
+		delegator: &T::AccountId,
+		provider_id: &T::ProviderId,
+		action: DelegationAction,
+		era: EraIndex,
+		amount: BalanceOf<T>,
+	) {
+		let index = DelegationRecordCount::<T>::mutate(|count| {
+			let index = *count;
+			*count = count.saturating_add(1);
+			index
+		});
+		let record: IndexRecordOf<T> = DelegationIndexRecord {
+			delegator: delegator.clone(),
+			provider_id: provider_id.clone(),
+			action,
+			era,
+			amount,
+		};
+		let block = frame_system::Pallet::<T>::block_number();
+		sp_io::offchain_index::set(&record_key(block, index), &record.encode());
+	}
+
+	/// Write the number of records of block `n` to the offchain database and reset the counter.
+	pub(crate) fn finalize_delegation_index(n: T::BlockNumber) {
+		let count = DelegationRecordCount::<T>::take();
+		if count > 0 {
+			sp_io::offchain_index::set(&record_count_key(n), &count.encode());
+		}
+	}
+}
@@ -0,0 +1,80 @@
+//! Runtime API definition for dAPI staking pallet.
+
+use crate::types::{ClaimRecord, EraHistoryEntry, EraIndex, ProviderEraEntry};
+use codec::Codec;
+use sp_runtime::Perbill;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DapiStakingApi<AccountId, ProviderId, Balance, BlockNumber> where
+		AccountId: Codec,
+		ProviderId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the number of providers `who` is currently staking on.
+		fn delegator_provider_count(who: AccountId) -> u32;
+
+		/// Returns the total rewards claimed by a provider over all time.
+		fn provider_total_rewards_claimed(provider_id: ProviderId) -> Balance;
+
+		/// Returns the total rewards claimed by `who` from a provider over all time.
+		fn delegator_total_rewards_claimed(who: AccountId, provider_id: ProviderId) -> Balance;
+
+		/// Returns the number of blocks left until the next era.
+		fn remaining_blocks() -> BlockNumber;
+
+		/// Returns the portion of the current era that has passed.
+		fn progress_perbill() -> Perbill;
+
+		/// Returns the accounts currently staking on a provider.
+		fn delegators_of(provider_id: ProviderId) -> Vec<AccountId>;
+
+		/// Returns the rewards `delegator` could claim from a provider over all claimable eras.
+		fn pending_rewards(delegator: AccountId, provider_id: ProviderId) -> Balance;
+
+		/// Returns the reward the provider owner could claim for `era`.
+		fn provider_pending_reward(provider_id: ProviderId, era: EraIndex) -> Balance;
+
+		/// Returns the total stake locked on a provider in the current era.
+		fn provider_tvl(provider_id: ProviderId) -> Balance;
+
+		/// Returns the total stake locked on all providers in the current era.
+		fn total_tvl() -> Balance;
+
+		/// Returns the sum of the latest stakes of `delegator` over all providers.
+		fn delegator_total_staked(delegator: AccountId) -> Balance;
+
+		/// Returns the most recent rewards `delegator` claimed from a provider, oldest first.
+		/// Empty unless the runtime enables the `claim-history` feature.
+		fn claim_history(
+			delegator: AccountId,
+			provider_id: ProviderId,
+		) -> Vec<ClaimRecord<Balance, BlockNumber>>;
+
+		/// Returns the estimated yearly return of delegating on a provider, extrapolated from the
+		/// rewards of the current era.
+		fn estimated_delegator_apr(provider_id: ProviderId) -> Perbill;
+
+		/// Returns the estimated delegator APR of all active providers, highest first.
+		fn all_providers_apr() -> Vec<(ProviderId, Perbill)>;
+
+		/// Returns the number of providers active in `era`.
+		fn era_active_providers(era: EraIndex) -> u32;
+
+		/// Returns the number of accounts currently staking on at least one provider.
+		fn total_delegators() -> u32;
+
+		/// Returns the staking totals of the eras from `from_era` to `to_era`, both included, up
+		/// to `MaxEraHistoryQuery` eras.
+		fn era_history(from_era: EraIndex, to_era: EraIndex) -> Vec<EraHistoryEntry<Balance>>;
+
+		/// Returns the staking totals of a provider in the eras from `from_era` to `to_era`, both
+		/// included, up to `MaxEraHistoryQuery` eras.
+		fn provider_history(
+			provider_id: ProviderId,
+			from_era: EraIndex,
+			to_era: EraIndex,
+		) -> Vec<ProviderEraEntry<Balance>>;
+	}
+}
@@ -0,0 +1,91 @@
+//! Implementation of `sp_staking::StakingInterface`, enabled by `impl-staking-interface` feature.
+//!
+//! dAPI staking has no notion of stash and controller accounts, nor of nominations, so only the
+//! read-only part of the interface is mapped. Staking operations must go through the pallet's
+//! extrinsics and are rejected here.
+
+use crate::{pallet::*, types::EraIndex};
+use frame_support::{dispatch::DispatchResult, traits::Get};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError,
+};
+use sp_staking::StakingInterface;
+use sp_std::vec::Vec;
+
+/// Error returned by staking operations which can't be mapped to dAPI staking.
+const UNSUPPORTED: DispatchError = DispatchError::Other("Unsupported by dAPI staking");
+
+impl<T: Config> StakingInterface for Pallet<T> {
+	type Balance = BalanceOf<T>;
+	type AccountId = T::AccountId;
+
+	fn minimum_bond() -> Self::Balance {
+		T::MinDelegatorStake::get()
+	}
+
+	fn bonding_duration() -> EraIndex {
+		T::UnbondingPeriod::get()
+	}
+
+	fn current_era() -> EraIndex {
+		Self::era().current
+	}
+
+	/// Sum of the latest staked values of all delegations of `who`.
+	fn active_stake(who: &Self::AccountId) -> Option<Self::Balance> {
+		let mut delegations = DelegationInfo::<T>::iter_prefix_values(who).peekable();
+		delegations.peek()?;
+
+		Some(delegations.fold(Zero::zero(), |total: Self::Balance, delegation| {
+			total.saturating_add(delegation.latest_staked_value())
+		}))
+	}
+
+	/// Active stake of `who` together with the funds which are still unbonding.
+	fn total_stake(who: &Self::AccountId) -> Option<Self::Balance> {
+		let unbonding = Self::unbonding_info(who).sum();
+		match Self::active_stake(who) {
+			Some(active) => Some(active.saturating_add(unbonding)),
+			None if !unbonding.is_zero() => Some(unbonding),
+			None => None,
+		}
+	}
+
+	fn bond(
+		_stash: Self::AccountId,
+		_controller: Self::AccountId,
+		_value: Self::Balance,
+		_payee: Self::AccountId,
+	) -> DispatchResult {
+		Err(UNSUPPORTED)
+	}
+
+	fn nominate(_controller: Self::AccountId, _validators: Vec<Self::AccountId>) -> DispatchResult {
+		Err(UNSUPPORTED)
+	}
+
+	fn chill(_controller: Self::AccountId) -> DispatchResult {
+		Err(UNSUPPORTED)
+	}
+
+	fn bond_extra(_stash: Self::AccountId, _extra: Self::Balance) -> DispatchResult {
+		Err(UNSUPPORTED)
+	}
+
+	fn unbond(_stash: Self::AccountId, _value: Self::Balance) -> DispatchResult {
+		Err(UNSUPPORTED)
+	}
+
+	fn withdraw_unbonded(
+		_stash: Self::AccountId,
+		_num_slashing_spans: u32,
+	) -> Result<bool, DispatchError> {
+		Err(UNSUPPORTED)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn nominations(_who: Self::AccountId) -> Option<Vec<Self::AccountId>> {
+		None
+	}
+}
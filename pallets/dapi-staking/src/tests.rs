@@ -1,12 +1,16 @@
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency, OnInitialize},
+	BoundedVec,
+};
+use sp_runtime::{
+	traits::{AccountIdConversion, BadOrigin, Zero},
+	Perbill,
 };
-use sp_runtime::traits::{AccountIdConversion, Zero};
 
 use crate::{
 	pallet::{Error, Event},
-	traits::DapiStakingRegistration,
+	traits::{DapiStakingRegistration, MAX_PROVIDER_SCORE},
 	types::*,
 	*,
 };
@@ -62,11 +66,17 @@ fn get_total_reward_per_era() -> Balance {
 
 fn assert_register(operator: AccountId, provider_id: &MockProvider, stake_amount: Balance) {
 	let init_reserved_balance = <TestRuntime as Config>::Currency::reserved_balance(&operator);
+	let init_active_provider_count = DapiStaking::active_provider_count();
 
 	assert!(!ProviderInfo::<TestRuntime>::contains_key(provider_id));
 
 	// Verify op is successfully
-	assert_ok!(DapiStaking::register_provider(operator, provider_id.clone(), stake_amount));
+	assert_ok!(DapiStaking::register_provider(
+		operator,
+		provider_id.clone(),
+		stake_amount,
+		MIN_PROVIDER_STAKE
+	));
 
 	let provider = ProviderInfo::<TestRuntime>::get(provider_id).unwrap();
 	assert_eq!(provider.status, ProviderStatus::Active);
@@ -74,15 +84,18 @@ fn assert_register(operator: AccountId, provider_id: &MockProvider, stake_amount
 
 	let final_reserved_balance = <TestRuntime as Config>::Currency::reserved_balance(&operator);
 	assert_eq!(final_reserved_balance, init_reserved_balance + stake_amount);
+	assert_eq!(DapiStaking::active_provider_count(), init_active_provider_count + 1);
 }
 
 fn assert_unregister(operator: AccountId, provider_id: &MockProvider) {
 	let current_era = DapiStaking::era().current;
 	let init_state = MemorySnapshot::provider(current_era, provider_id);
+	let init_active_provider_count = DapiStaking::active_provider_count();
 
 	assert_eq!(init_state.provider_info.status, ProviderStatus::Active);
 
 	assert_ok!(DapiStaking::unregister_provider(provider_id.clone()));
+	assert_eq!(DapiStaking::active_provider_count(), init_active_provider_count - 1);
 
 	let final_state = MemorySnapshot::provider(current_era, provider_id);
 	assert_eq!(
@@ -150,7 +163,7 @@ fn reward_is_ok() {
 #[test]
 fn on_initialize_is_ok() {
 	ExternalityBuilder::build().execute_with(|| {
-		assert!(DapiStaking::era().current.is_zero());
+		assert!(!DapiStaking::era().has_started());
 
 		// We initialize the first block and advance to second one. New era must be triggered.
 		initialize_first_block();
@@ -172,6 +185,278 @@ fn on_initialize_is_ok() {
 	})
 }
 
+#[test]
+fn only_active_providers_are_rotated() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let (active, inactive) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		assert_register(1, &active, 100);
+		assert_register(3, &inactive, 100);
+		assert_eq!(DapiStaking::active_providers().len(), 2);
+		assert_unregister(3, &inactive);
+		assert_eq!(DapiStaking::active_providers().into_iter().collect::<Vec<_>>(), vec![active]);
+
+		let era = DapiStaking::era().current;
+		advance_to_era(era + 1);
+		assert!(DapiStaking::provider_era_info(&active, era + 1).is_some());
+		assert!(DapiStaking::provider_era_info(&inactive, era + 1).is_none());
+	})
+}
+
+#[test]
+fn provider_without_stake_is_auto_unregistered() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let (staked, abandoned) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		assert_register(1, &staked, 100);
+		assert_register(3, &abandoned, 100);
+		assert_ok!(DapiStaking::slash_provider(abandoned, Perbill::one()));
+
+		let era = DapiStaking::era().current;
+		advance_to_era(era + 1);
+		assert_eq!(DapiStaking::provider_inactive_eras(&abandoned), 1);
+		assert!(DapiStaking::provider_inactive_eras(&staked).is_zero());
+		assert_eq!(DapiStaking::active_providers().len(), 2);
+
+		advance_to_era(era + MAX_INACTIVE_ERAS);
+		System::assert_has_event(mock::Event::DapiStaking(Event::ProviderAutoUnregistered {
+			provider_id: abandoned,
+		}));
+		assert_eq!(
+			DapiStaking::provider_info(&abandoned).unwrap().status,
+			ProviderStatus::Inactive(era + MAX_INACTIVE_ERAS)
+		);
+		assert_eq!(DapiStaking::active_providers().into_iter().collect::<Vec<_>>(), vec![staked]);
+		assert!(DapiStaking::provider_inactive_eras(&abandoned).is_zero());
+	})
+}
+
+#[test]
+fn prune_old_era_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let providers: Vec<_> = (1..=3).map(|seed| MockProvider([seed; 36])).collect();
+		for (operator, provider_id) in [1, 3, 4].into_iter().zip(providers.iter()) {
+			assert_register(operator, provider_id, 100);
+		}
+		let era = DapiStaking::era().current;
+		advance_to_era(era + MAX_CLAIMABLE_ERA_AGE);
+
+		assert_noop!(DapiStaking::prune_old_era(Origin::signed(1), era), BadOrigin);
+		// Claims and withdrawals might still be pending
+		assert_noop!(
+			DapiStaking::prune_old_era(Origin::root(), era),
+			Error::<TestRuntime>::EraOutOfBounds
+		);
+		advance_to_era(era + MAX_CLAIMABLE_ERA_AGE + 1);
+		// Rewards of the era are still being expired
+		assert_noop!(
+			DapiStaking::prune_old_era(Origin::root(), era),
+			Error::<TestRuntime>::EraOutOfBounds
+		);
+		run_for_blocks(1);
+
+		// Provider entries are removed in chunks, the era state with the last chunk
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: MAX_PROVIDERS_PER_ERA_CLEAN,
+		}));
+		assert!(DapiStaking::era_state(era).is_some());
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 1,
+		}));
+		assert!(DapiStaking::era_state(era).is_none());
+		for provider_id in providers.iter() {
+			assert!(DapiStaking::provider_era_info(provider_id, era).is_none());
+			assert!(DapiStaking::provider_era_info(provider_id, era + 1).is_some());
+		}
+
+		assert_noop!(
+			DapiStaking::prune_old_era(Origin::root(), era),
+			Error::<TestRuntime>::UnknownEra
+		);
+	})
+}
+
+#[test]
+fn prune_old_era_keeps_pending_entries() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let (active, unregistered) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		assert_register(1, &active, 100);
+		assert_register(3, &unregistered, 100);
+		let era = DapiStaking::era().current;
+		assert_unregister(3, &unregistered);
+		advance_to_era(era + MAX_CLAIMABLE_ERA_AGE + 1);
+
+		// Unclaimed rewards keep the provider entry and the era state
+		ProviderEraInfo::<TestRuntime>::mutate(&active, era, |info| {
+			info.as_mut().unwrap().provider_reward_claimed = false;
+		});
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 0,
+		}));
+		assert!(DapiStaking::provider_era_info(&active, era).is_some());
+		assert!(DapiStaking::era_state(era).is_some());
+
+		// The bond of the unregistered provider is read from its entry until withdrawn
+		ProviderEraInfo::<TestRuntime>::mutate(&active, era, |info| {
+			info.as_mut().unwrap().provider_reward_claimed = true;
+		});
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 1,
+		}));
+		assert!(DapiStaking::provider_era_info(&active, era).is_none());
+		assert!(DapiStaking::provider_era_info(&unregistered, era).is_some());
+		assert!(DapiStaking::era_state(era).is_none());
+
+		assert_ok!(DapiStaking::provider_withdraw_unregistered(Origin::signed(3), unregistered));
+		System::assert_last_event(mock::Event::DapiStaking(Event::Withdrawn {
+			who: 3,
+			amount: 100,
+		}));
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 1,
+		}));
+		assert!(DapiStaking::provider_era_info(&unregistered, era).is_none());
+	})
+}
+
+#[test]
+fn prune_old_era_resumes_from_cursor() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let providers: Vec<_> = (1..=3).map(|seed| MockProvider([seed; 36])).collect();
+		for (operator, provider_id) in [1, 3, 4].into_iter().zip(providers.iter()) {
+			assert_register(operator, provider_id, 100);
+		}
+		let era = DapiStaking::era().current;
+		advance_to_era(era + MAX_CLAIMABLE_ERA_AGE + 1);
+		run_for_blocks(1);
+		let set_claimed = |provider_id: &MockProvider, claimed: bool| {
+			ProviderEraInfo::<TestRuntime>::mutate(provider_id, era, |info| {
+				info.as_mut().unwrap().provider_reward_claimed = claimed;
+			});
+		};
+
+		// Entries kept for unclaimed rewards are scanned once per pass
+		set_claimed(&providers[0], false);
+		set_claimed(&providers[1], false);
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 0,
+		}));
+		assert_eq!(DapiStaking::prune_cursor(era), (MAX_PROVIDERS_PER_ERA_CLEAN, true));
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 1,
+		}));
+		assert_eq!(DapiStaking::prune_cursor(era), (0, false));
+		assert!(DapiStaking::era_state(era).is_some());
+
+		// The next pass starts over with the kept entries
+		set_claimed(&providers[0], true);
+		set_claimed(&providers[1], true);
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 2,
+		}));
+		assert!(DapiStaking::era_state(era).is_some());
+		assert_ok!(DapiStaking::prune_old_era(Origin::root(), era));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraPruned {
+			era,
+			providers_cleaned: 0,
+		}));
+		assert!(DapiStaking::era_state(era).is_none());
+		assert_eq!(DapiStaking::era_provider_count(era), 0);
+		for provider_id in providers.iter() {
+			assert!(DapiStaking::provider_era_info(provider_id, era).is_none());
+		}
+
+		assert_noop!(
+			DapiStaking::prune_old_era(Origin::root(), era),
+			Error::<TestRuntime>::UnknownEra
+		);
+	})
+}
+
+#[test]
+fn force_new_era_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+		let era = DapiStaking::era();
+
+		assert_noop!(DapiStaking::force_new_era(Origin::signed(1)), BadOrigin);
+		assert_ok!(DapiStaking::force_new_era(Origin::root()));
+		assert!(DapiStaking::force_new_era_scheduled());
+		assert_eq!(DapiStaking::era().current, era.current);
+
+		run_for_blocks(1);
+		let new_era = DapiStaking::era();
+		assert_eq!(new_era.current, era.current + 1);
+		assert_eq!(new_era.first_block, era.first_block + 2);
+		assert!(!DapiStaking::force_new_era_scheduled());
+		System::assert_last_event(mock::Event::DapiStaking(Event::ForcedNewEra {
+			era: new_era.current,
+		}));
+
+		// Short era only got rewards of its two blocks
+		let era_state = EraState::<TestRuntime>::get(era.current).unwrap();
+		assert_eq!(era_state.rewards, 2 * BLOCK_REWARD);
+
+		// Next era has the regular length
+		run_for_blocks(BLOCKS_PER_ERA as u64 - 1);
+		assert_eq!(DapiStaking::era().current, new_era.current);
+		run_for_blocks(1);
+		assert_eq!(DapiStaking::era().current, new_era.current + 1);
+	})
+}
+
+#[test]
+fn force_new_era_at_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+		let era = DapiStaking::era();
+		let now = System::block_number();
+
+		assert_noop!(DapiStaking::force_new_era_at(Origin::signed(1), now + 1), BadOrigin);
+		assert_noop!(
+			DapiStaking::force_new_era_at(Origin::root(), now),
+			Error::<TestRuntime>::EraStartInPast
+		);
+		assert_ok!(DapiStaking::force_new_era_at(Origin::root(), now + 2));
+		assert_eq!(DapiStaking::next_era_start_block(), Some(now + 2));
+
+		run_for_blocks(1);
+		assert_eq!(DapiStaking::era().current, era.current);
+		run_for_blocks(1);
+		let new_era = DapiStaking::era();
+		assert_eq!(new_era.current, era.current + 1);
+		assert_eq!(new_era.first_block, now + 2);
+		assert!(DapiStaking::next_era_start_block().is_none());
+		System::assert_last_event(mock::Event::DapiStaking(Event::ForcedNewEra {
+			era: new_era.current,
+		}));
+	})
+}
+
 #[test]
 fn register_is_ok() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -197,12 +482,90 @@ fn register_same_provider_twice_fails() {
 		assert_register(operator1, &provider, 100);
 
 		assert_noop!(
-			DapiStaking::register_provider(operator2, provider, 100),
+			DapiStaking::register_provider(operator2, provider, 100, MIN_PROVIDER_STAKE),
 			Error::<TestRuntime>::ProviderExists
 		);
 	})
 }
 
+#[test]
+fn provider_bond_is_capped() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 3;
+		let provider_id = MockProvider::default();
+		assert_noop!(
+			DapiStaking::register_provider(
+				operator,
+				provider_id,
+				MAX_PROVIDER_BOND + 1,
+				MIN_PROVIDER_STAKE
+			),
+			Error::<TestRuntime>::ExceedsMaxProviderBond
+		);
+
+		assert_register(operator, &provider_id, MAX_PROVIDER_BOND - 100);
+		assert_noop!(
+			DapiStaking::provider_bond_more(Origin::signed(operator), provider_id, 101),
+			Error::<TestRuntime>::ExceedsMaxProviderBond
+		);
+		assert_ok!(DapiStaking::provider_bond_more(Origin::signed(operator), provider_id, 100));
+		let era = DapiStaking::era().current;
+		assert_eq!(
+			DapiStaking::provider_era_info(&provider_id, era).unwrap().bond,
+			MAX_PROVIDER_BOND
+		);
+	})
+}
+
+#[test]
+fn register_provider_batch_skips_failed_registrations() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		let provider_3 = MockProvider([3; 36]);
+		let registrations: BoundedVec<_, MaxBatchRegistrations> = vec![
+			(1, provider_1, 100),
+			(2, provider_1, 100),
+			(3, provider_2, MIN_PROVIDER_STAKE - 1),
+			(3, provider_3, 100),
+		]
+		.try_into()
+		.unwrap();
+
+		assert_noop!(
+			DapiStaking::register_provider_batch(Origin::signed(1), registrations.clone()),
+			BadOrigin
+		);
+		assert_ok!(DapiStaking::register_provider_batch(Origin::root(), registrations));
+
+		assert_eq!(DapiStaking::active_provider_count(), 2);
+		assert_eq!(ProviderInfo::<TestRuntime>::get(&provider_1).unwrap().owner, 1);
+		assert_eq!(ProviderInfo::<TestRuntime>::get(&provider_3).unwrap().owner, 3);
+		assert!(!ProviderInfo::<TestRuntime>::contains_key(&provider_2));
+		assert!(<TestRuntime as Config>::Currency::reserved_balance(&2).is_zero());
+		assert_eq!(<TestRuntime as Config>::Currency::reserved_balance(&3), 100);
+
+		System::assert_has_event(mock::Event::DapiStaking(Event::ProviderRegistered {
+			provider_id: provider_1,
+		}));
+		System::assert_has_event(mock::Event::DapiStaking(Event::ProviderRegistrationSkipped {
+			provider_id: provider_1,
+			reason: Error::<TestRuntime>::ProviderExists.into(),
+		}));
+		System::assert_has_event(mock::Event::DapiStaking(Event::ProviderRegistrationSkipped {
+			provider_id: provider_2,
+			reason: Error::<TestRuntime>::InsufficientBond.into(),
+		}));
+		System::assert_last_event(mock::Event::DapiStaking(Event::ProviderRegistered {
+			provider_id: provider_3,
+		}));
+	})
+}
+
 #[test]
 fn unregister_after_register_is_ok() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -217,6 +580,162 @@ fn unregister_after_register_is_ok() {
 	})
 }
 
+#[test]
+fn accelerated_withdrawals_skip_unbonding_period() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+
+		assert_noop!(
+			DapiStaking::accelerate_provider_withdrawal(Origin::root(), provider_id),
+			Error::<TestRuntime>::NotUnregisteredProvider
+		);
+		assert_unregister(operator, &provider_id);
+
+		// The unbonding period isn't over for regular withdrawals
+		assert_noop!(
+			DapiStaking::provider_withdraw_unregistered(Origin::signed(operator), provider_id),
+			Error::<TestRuntime>::NothingToWithdraw
+		);
+		assert_noop!(
+			DapiStaking::accelerate_provider_withdrawal(Origin::signed(operator), provider_id),
+			BadOrigin
+		);
+		assert_ok!(DapiStaking::accelerate_provider_withdrawal(Origin::root(), provider_id));
+		System::assert_last_event(mock::Event::DapiStaking(Event::Withdrawn {
+			who: operator,
+			amount: 100,
+		}));
+		assert!(<TestRuntime as Config>::Currency::reserved_balance(&operator).is_zero());
+		assert_noop!(
+			DapiStaking::accelerate_provider_withdrawal(Origin::root(), provider_id),
+			Error::<TestRuntime>::NothingToWithdraw
+		);
+
+		assert_noop!(
+			DapiStaking::accelerate_delegator_withdrawal(
+				Origin::signed(delegator),
+				delegator,
+				provider_id
+			),
+			BadOrigin
+		);
+		assert_ok!(DapiStaking::accelerate_delegator_withdrawal(
+			Origin::root(),
+			delegator,
+			provider_id
+		));
+		System::assert_last_event(mock::Event::DapiStaking(Event::Withdrawn {
+			who: delegator,
+			amount: 100,
+		}));
+		assert!(<TestRuntime as Config>::Currency::reserved_balance(&delegator).is_zero());
+		assert_noop!(
+			DapiStaking::accelerate_delegator_withdrawal(Origin::root(), delegator, provider_id),
+			Error::<TestRuntime>::NotStakedProvider
+		);
+	})
+}
+
+#[test]
+fn slash_provider_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let provider_id = MockProvider::default();
+		assert_register(operator, &provider_id, 100);
+
+		let current_era = DapiStaking::era().current;
+		let init_state = MemorySnapshot::provider(current_era, &provider_id);
+		assert_ok!(DapiStaking::slash_provider(provider_id.clone(), Perbill::from_percent(20)));
+		System::assert_last_event(mock::Event::DapiStaking(Event::ProviderSlashed {
+			provider_id: provider_id.clone(),
+			amount: 20,
+		}));
+
+		let final_state = MemorySnapshot::provider(current_era, &provider_id);
+		assert_eq!(final_state.provider_era_info.bond, 80);
+		assert_eq!(final_state.provider_era_info.total, init_state.provider_era_info.total - 20);
+		assert_eq!(final_state.era_info.staked, init_state.era_info.staked - 20);
+		assert_eq!(<TestRuntime as Config>::Currency::reserved_balance(&operator), 80);
+
+		assert_unregister(operator, &provider_id);
+		assert_noop!(
+			DapiStaking::slash_provider(provider_id, Perbill::from_percent(20)),
+			Error::<TestRuntime>::NotOperatedProvider
+		);
+	})
+}
+
+#[test]
+fn provider_transfer_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let (old_owner, new_owner, other) = (1, 2, 3);
+		let provider_id = MockProvider::default();
+		assert_register(old_owner, &provider_id, 100);
+
+		assert_noop!(
+			DapiStaking::initiate_provider_transfer(Origin::signed(other), provider_id, new_owner),
+			Error::<TestRuntime>::NotOwnedProvider
+		);
+		assert_ok!(DapiStaking::initiate_provider_transfer(
+			Origin::signed(old_owner),
+			provider_id,
+			new_owner
+		));
+		assert_noop!(
+			DapiStaking::accept_provider_transfer(Origin::signed(other), provider_id),
+			Error::<TestRuntime>::NotPendingOwner
+		);
+		assert_ok!(DapiStaking::accept_provider_transfer(Origin::signed(new_owner), provider_id));
+		System::assert_last_event(mock::Event::DapiStaking(Event::ProviderTransferred {
+			provider_id,
+			old_owner,
+			new_owner,
+		}));
+		assert_eq!(DapiStaking::provider_info(&provider_id).unwrap().owner, new_owner);
+		assert!(<TestRuntime as Config>::Currency::reserved_balance(&old_owner).is_zero());
+		assert_eq!(<TestRuntime as Config>::Currency::reserved_balance(&new_owner), 100);
+		assert!(DapiStaking::pending_transfers(&provider_id).is_none());
+
+		// Cancelled transfers can't be accepted
+		assert_ok!(DapiStaking::initiate_provider_transfer(
+			Origin::signed(new_owner),
+			provider_id,
+			other
+		));
+		assert_noop!(
+			DapiStaking::cancel_provider_transfer(Origin::signed(old_owner), provider_id),
+			Error::<TestRuntime>::NotOwnedProvider
+		);
+		assert_ok!(DapiStaking::cancel_provider_transfer(Origin::signed(new_owner), provider_id));
+		assert_noop!(
+			DapiStaking::accept_provider_transfer(Origin::signed(other), provider_id),
+			Error::<TestRuntime>::NoPendingTransfer
+		);
+
+		// Nor can expired ones
+		assert_ok!(DapiStaking::initiate_provider_transfer(
+			Origin::signed(new_owner),
+			provider_id,
+			other
+		));
+		System::set_block_number(System::block_number() + PROVIDER_TRANSFER_PERIOD + 1);
+		assert_noop!(
+			DapiStaking::accept_provider_transfer(Origin::signed(other), provider_id),
+			Error::<TestRuntime>::TransferExpired
+		);
+	})
+}
+
 #[test]
 fn unregister_stake_and_unstake_is_not_ok() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -233,3 +752,1840 @@ fn unregister_stake_and_unstake_is_not_ok() {
 		assert_unregister(operator, &provider_id);
 	})
 }
+
+#[test]
+fn total_rewards_claimed_are_accumulated() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(start_era + 2);
+
+		let mut provider_total = 0;
+		let mut delegator_total = 0;
+		for era in start_era..start_era + 2 {
+			let operator_balance = <TestRuntime as Config>::Currency::free_balance(&operator);
+			assert_ok!(DapiStaking::claim_provider(Origin::signed(operator), provider_id, era));
+			provider_total +=
+				<TestRuntime as Config>::Currency::free_balance(&operator) - operator_balance;
+			assert_eq!(DapiStaking::provider_total_rewards_claimed(&provider_id), provider_total);
+
+			let delegator_balance = <TestRuntime as Config>::Currency::free_balance(&delegator);
+			assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+			delegator_total +=
+				<TestRuntime as Config>::Currency::free_balance(&delegator) - delegator_balance;
+			assert_eq!(
+				DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id),
+				delegator_total
+			);
+		}
+		assert!(!provider_total.is_zero());
+		assert!(!delegator_total.is_zero());
+
+		assert_noop!(
+			DapiStaking::reset_provider_reward_stats(Origin::signed(operator), provider_id),
+			BadOrigin
+		);
+		assert_ok!(DapiStaking::reset_provider_reward_stats(Origin::root(), provider_id));
+		System::assert_last_event(mock::Event::DapiStaking(Event::ProviderRewardStatsReset {
+			provider_id,
+		}));
+		assert!(DapiStaking::provider_total_rewards_claimed(&provider_id).is_zero());
+		assert_eq!(
+			DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id),
+			delegator_total
+		);
+	})
+}
+
+#[test]
+fn past_era_rewards_keep_their_commission() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+		let (old_commission, new_commission) =
+			(Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE), Perbill::from_percent(10));
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(2, &provider_id, 100);
+		assert_ok!(DapiStaking::set_provider_commission(
+			Origin::signed(operator),
+			provider_id,
+			new_commission
+		));
+		let new_era = start_era + COMMISSION_CHANGE_DELAY;
+		advance_to_era(new_era + 1);
+		assert_eq!(
+			DapiStaking::provider_era_info(&provider_id, start_era).unwrap().commission,
+			old_commission
+		);
+		assert_eq!(
+			DapiStaking::provider_era_info(&provider_id, new_era).unwrap().commission,
+			new_commission
+		);
+
+		// Rewards of the era before the change are still split with the old commission
+		let (provider_reward, _) = DapiStaking::split_provider_delegators_rewards(
+			old_commission,
+			&DapiStaking::provider_era_info(&provider_id, start_era).unwrap(),
+			&DapiStaking::era_state(start_era).unwrap(),
+		);
+		assert_eq!(DapiStaking::provider_pending_reward(provider_id, start_era), provider_reward);
+		let operator_balance = <TestRuntime as Config>::Currency::free_balance(&operator);
+		assert_ok!(DapiStaking::claim_provider(Origin::signed(operator), provider_id, start_era));
+		assert_eq!(
+			<TestRuntime as Config>::Currency::free_balance(&operator) - operator_balance,
+			provider_reward
+		);
+
+		let (provider_reward, _) = DapiStaking::split_provider_delegators_rewards(
+			new_commission,
+			&DapiStaking::provider_era_info(&provider_id, new_era).unwrap(),
+			&DapiStaking::era_state(new_era).unwrap(),
+		);
+		assert_eq!(DapiStaking::provider_pending_reward(provider_id, new_era), provider_reward);
+	})
+}
+
+#[test]
+fn pending_rewards_match_claimed_rewards() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		assert!(DapiStaking::pending_rewards(delegator, provider_id).is_zero());
+		assert!(DapiStaking::provider_pending_reward(provider_id, start_era).is_zero());
+		advance_to_era(start_era + 3);
+
+		let pending = DapiStaking::pending_rewards(delegator, provider_id);
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		let claimed = DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id);
+		assert_eq!(DapiStaking::pending_rewards(delegator, provider_id), pending - claimed);
+		assert_ok!(DapiStaking::claim_delegator_batch(Origin::signed(delegator), provider_id, 5));
+		assert_eq!(DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id), pending);
+		assert!(DapiStaking::pending_rewards(delegator, provider_id).is_zero());
+
+		let provider_pending = DapiStaking::provider_pending_reward(provider_id, start_era);
+		assert!(!provider_pending.is_zero());
+		assert_ok!(DapiStaking::claim_provider(Origin::signed(operator), provider_id, start_era));
+		assert_eq!(DapiStaking::provider_total_rewards_claimed(&provider_id), provider_pending);
+		assert!(DapiStaking::provider_pending_reward(provider_id, start_era).is_zero());
+		assert!(DapiStaking::provider_pending_reward(provider_id, start_era + 3).is_zero());
+	})
+}
+
+#[test]
+fn tvl_queries_are_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let (first_provider, second_provider) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		assert_register(operator, &first_provider, 100);
+		assert_register(operator, &second_provider, 100);
+		assert_delegate(delegator, &first_provider, 50);
+		assert_delegate(delegator, &second_provider, 30);
+
+		assert_eq!(DapiStaking::provider_tvl(first_provider), 150);
+		assert_eq!(DapiStaking::provider_tvl(second_provider), 130);
+		assert_eq!(DapiStaking::total_tvl(), 280);
+		assert_eq!(DapiStaking::delegator_total_staked(&delegator), 80);
+		assert!(DapiStaking::delegator_total_staked(&operator).is_zero());
+	})
+}
+
+#[test]
+fn era_provider_and_delegator_counts_are_tracked() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		let start_era = DapiStaking::era().current;
+		assert_register(1, &provider_1, 100);
+		assert_register(3, &provider_2, 100);
+		assert_eq!(DapiStaking::era_active_provider_count(start_era), 2);
+
+		assert_delegate(9, &provider_1, 100);
+		assert_delegate(9, &provider_2, 100);
+		assert_delegate(7, &provider_1, 100);
+		assert_eq!(DapiStaking::delegator_count(), 2);
+
+		advance_to_era(start_era + 1);
+		assert_eq!(DapiStaking::era_active_provider_count(start_era + 1), 2);
+
+		// Only the current era count changes
+		assert_unregister(3, &provider_2);
+		assert_eq!(DapiStaking::era_active_provider_count(start_era), 2);
+		assert_eq!(DapiStaking::era_active_provider_count(start_era + 1), 1);
+
+		// Delegator 9 still stakes on the unregistered provider
+		assert_ok!(DapiStaking::delegator_unstake_all(Origin::signed(9), provider_1));
+		assert_eq!(DapiStaking::delegator_count(), 2);
+		assert_ok!(DapiStaking::delegator_unstake_all(Origin::signed(7), provider_1));
+		assert_eq!(DapiStaking::delegator_count(), 1);
+	})
+}
+
+#[test]
+fn era_and_provider_history_are_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(start_era + 6);
+
+		// Queries are capped at `MaxEraHistoryQuery` eras
+		let history = DapiStaking::era_history(start_era, start_era + 10);
+		assert_eq!(history.len() as u32, MaxEraHistoryQuery::get());
+		let era_info = DapiStaking::era_state(start_era + 1).unwrap();
+		assert_eq!(
+			history[1],
+			EraHistoryEntry {
+				era: start_era + 1,
+				total_staked: era_info.staked,
+				total_rewards: era_info.rewards,
+				provider_count: 1,
+			}
+		);
+		assert!(!history[1].total_rewards.is_zero());
+
+		// The only provider holds the whole stake and earns all rewards
+		let provider_history = DapiStaking::provider_history(provider_id, start_era, start_era + 1);
+		assert_eq!(provider_history.len(), 2);
+		let provider_era_info =
+			DapiStaking::provider_era_info(&provider_id, start_era + 1).unwrap();
+		assert_eq!(
+			provider_history[1],
+			ProviderEraEntry {
+				era: start_era + 1,
+				bond: provider_era_info.bond,
+				total_staked: provider_era_info.total,
+				delegator_count: 1,
+				total_rewards: era_info.rewards,
+				provider_reward_claimed: false,
+			}
+		);
+
+		assert!(DapiStaking::era_history(start_era + 7, start_era + 10).is_empty());
+		assert!(DapiStaking::provider_history(MockProvider([9; 36]), start_era, start_era + 3)
+			.is_empty());
+	})
+}
+
+#[test]
+fn unclaimed_rewards_expire() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(start_era + MAX_CLAIMABLE_ERA_AGE + 1);
+
+		let (provider_reward, delegators_reward) = DapiStaking::split_provider_delegators_rewards(
+			Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE),
+			&DapiStaking::provider_era_info(&provider_id, start_era).unwrap(),
+			&DapiStaking::era_state(start_era).unwrap(),
+		);
+		System::assert_has_event(mock::Event::DapiStaking(Event::RewardExpired {
+			provider_id,
+			era: start_era,
+			amount: provider_reward + delegators_reward,
+		}));
+		assert_noop!(
+			DapiStaking::claim_provider(Origin::signed(operator), provider_id, start_era),
+			Error::<TestRuntime>::RewardExpired
+		);
+		assert_noop!(
+			DapiStaking::claim_delegator(Origin::signed(delegator), provider_id),
+			Error::<TestRuntime>::RewardExpired
+		);
+		assert_ok!(DapiStaking::claim_provider(
+			Origin::signed(operator),
+			provider_id,
+			start_era + 1
+		));
+
+		// Batch claim drops the expired era and pays the next one
+		assert_ok!(DapiStaking::claim_delegator_batch(Origin::signed(delegator), provider_id, 1));
+		let total_amount = DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id);
+		assert!(!total_amount.is_zero());
+		System::assert_last_event(mock::Event::DapiStaking(Event::PayoutBatch {
+			who: delegator,
+			provider_id,
+			eras: vec![start_era + 1],
+			total_amount,
+		}));
+	})
+}
+
+#[test]
+fn unclaimed_rewards_of_unregistered_provider_expire() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(start_era + 1);
+		assert_unregister(operator, &provider_id);
+		assert!(!DapiStaking::active_providers().contains(&provider_id));
+
+		advance_to_era(start_era + MAX_CLAIMABLE_ERA_AGE + 1);
+		let (provider_reward, delegators_reward) = DapiStaking::split_provider_delegators_rewards(
+			Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE),
+			&DapiStaking::provider_era_info(&provider_id, start_era).unwrap(),
+			&DapiStaking::era_state(start_era).unwrap(),
+		);
+		let amount = provider_reward + delegators_reward;
+		assert!(!amount.is_zero());
+		System::assert_has_event(mock::Event::DapiStaking(Event::RewardExpired {
+			provider_id,
+			era: start_era,
+			amount,
+		}));
+		let info = DapiStaking::provider_era_info(&provider_id, start_era).unwrap();
+		assert!(info.provider_reward_claimed && info.delegators_fully_claimed);
+	})
+}
+
+#[test]
+fn expired_rewards_are_checked_in_chunks() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let providers: Vec<_> = (1..=3).map(|seed| MockProvider([seed; 36])).collect();
+		for (operator, provider_id) in [1, 3, 4].into_iter().zip(providers.iter()) {
+			assert_register(operator, provider_id, 100);
+		}
+		let era = DapiStaking::era().current;
+		assert_eq!(DapiStaking::era_provider_count(era), 3);
+		advance_to_era(era + MAX_CLAIMABLE_ERA_AGE + 1);
+		let expired = |provider_id: &MockProvider| {
+			DapiStaking::provider_era_info(provider_id, era)
+				.unwrap()
+				.provider_reward_claimed
+		};
+
+		// Only the first chunk of providers is checked at the era change
+		assert_eq!(DapiStaking::expiry_cursor(), (era, MAX_PROVIDERS_EXPIRED_PER_BLOCK));
+		assert!(expired(&providers[0]) && expired(&providers[1]));
+		assert!(!expired(&providers[2]));
+
+		// The next block resumes with the remaining ones and moves on to the next era
+		run_for_blocks(1);
+		assert!(expired(&providers[2]));
+		assert_eq!(DapiStaking::expiry_cursor(), (era + 1, 0));
+	})
+}
+
+#[cfg(feature = "claim-history")]
+#[test]
+fn claim_history_keeps_latest_claims() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(start_era + 3);
+
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		let first_amount = DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id);
+		let history = DapiStaking::claim_history(&delegator, &provider_id);
+		assert_eq!(history.len(), 1);
+		assert_eq!(
+			history[0],
+			ClaimRecord {
+				era: start_era,
+				amount: first_amount,
+				claimed_at: System::block_number()
+			}
+		);
+
+		// The oldest record is dropped once the history is full
+		assert_ok!(DapiStaking::claim_delegator_batch(Origin::signed(delegator), provider_id, 2));
+		let history = DapiStaking::claim_history(&delegator, &provider_id);
+		assert_eq!(history.len() as u32, MAX_CLAIM_HISTORY);
+		assert_eq!(
+			history.iter().map(|record| record.era).collect::<Vec<_>>(),
+			vec![start_era + 1, start_era + 2]
+		);
+		assert_eq!(
+			history.iter().map(|record| record.amount).sum::<Balance>(),
+			DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id) - first_amount
+		);
+	})
+}
+
+#[test]
+fn claim_delegator_batch_is_capped_by_era_stake_values_limit() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 3;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+		assert_register(1, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(start_era + 4);
+
+		assert_ok!(DapiStaking::set_era_stake_values_limit(Origin::root(), MIN_ERA_STAKE_VALUES));
+		assert_ok!(DapiStaking::claim_delegator_batch(Origin::signed(delegator), provider_id, 4));
+		let mut delegation = DapiStaking::delegation_info(&delegator, &provider_id);
+		assert_eq!(delegation.claim().0, start_era + MIN_ERA_STAKE_VALUES);
+	})
+}
+
+#[test]
+fn claim_delegator_batch_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let (batch_delegator, single_delegator) = (3, 4);
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(batch_delegator, &provider_id, 100);
+		assert_delegate(single_delegator, &provider_id, 100);
+		assert_noop!(
+			DapiStaking::claim_delegator_batch(Origin::signed(batch_delegator), provider_id, 5),
+			Error::<TestRuntime>::EraOutOfBounds
+		);
+		advance_to_era(start_era + 3);
+
+		let init_balance = <TestRuntime as Config>::Currency::free_balance(&batch_delegator);
+		assert_ok!(DapiStaking::claim_delegator_batch(
+			Origin::signed(batch_delegator),
+			provider_id,
+			2
+		));
+		let first_amount =
+			<TestRuntime as Config>::Currency::free_balance(&batch_delegator) - init_balance;
+		System::assert_last_event(mock::Event::DapiStaking(Event::PayoutBatch {
+			who: batch_delegator,
+			provider_id,
+			eras: vec![start_era, start_era + 1],
+			total_amount: first_amount,
+		}));
+
+		// Only the remaining era is claimed
+		assert_ok!(DapiStaking::claim_delegator_batch(
+			Origin::signed(batch_delegator),
+			provider_id,
+			5
+		));
+		let batch_total =
+			<TestRuntime as Config>::Currency::free_balance(&batch_delegator) - init_balance;
+		System::assert_last_event(mock::Event::DapiStaking(Event::PayoutBatch {
+			who: batch_delegator,
+			provider_id,
+			eras: vec![start_era + 2],
+			total_amount: batch_total - first_amount,
+		}));
+		assert_noop!(
+			DapiStaking::claim_delegator_batch(Origin::signed(batch_delegator), provider_id, 5),
+			Error::<TestRuntime>::EraOutOfBounds
+		);
+
+		// Same rewards as claiming era by era
+		let init_balance = <TestRuntime as Config>::Currency::free_balance(&single_delegator);
+		for _ in 0..3 {
+			assert_ok!(DapiStaking::claim_delegator(Origin::signed(single_delegator), provider_id));
+		}
+		let single_total =
+			<TestRuntime as Config>::Currency::free_balance(&single_delegator) - init_balance;
+		assert!(!batch_total.is_zero());
+		assert_eq!(batch_total, single_total);
+		assert_eq!(
+			DapiStaking::delegator_total_rewards_claimed(&batch_delegator, &provider_id),
+			batch_total
+		);
+	})
+}
+
+#[test]
+fn compound_delegator_reward_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 3;
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		assert_noop!(
+			DapiStaking::compound_delegator_reward(Origin::signed(delegator), provider_id),
+			Error::<TestRuntime>::EraOutOfBounds
+		);
+		assert_noop!(
+			DapiStaking::compound_delegator_reward(Origin::signed(4), provider_id),
+			Error::<TestRuntime>::NotStakedProvider
+		);
+		advance_to_era(era + 1);
+
+		let current_era = DapiStaking::era().current;
+		let init_free = <TestRuntime as Config>::Currency::free_balance(&delegator);
+		let init_reserved = <TestRuntime as Config>::Currency::reserved_balance(&delegator);
+		let init_provider_total =
+			DapiStaking::provider_era_info(&provider_id, current_era).unwrap().total;
+		let init_era_staked = DapiStaking::era_state(current_era).unwrap().staked;
+		assert_ok!(DapiStaking::compound_delegator_reward(Origin::signed(delegator), provider_id));
+
+		let amount =
+			<TestRuntime as Config>::Currency::reserved_balance(&delegator) - init_reserved;
+		assert!(!amount.is_zero());
+		System::assert_last_event(mock::Event::DapiStaking(Event::Compounded {
+			who: delegator,
+			provider_id,
+			era,
+			amount,
+		}));
+		assert_eq!(<TestRuntime as Config>::Currency::free_balance(&delegator), init_free);
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &provider_id).latest_staked_value(),
+			100 + amount
+		);
+		let provider_era_info = DapiStaking::provider_era_info(&provider_id, current_era).unwrap();
+		assert_eq!(provider_era_info.total, init_provider_total + amount);
+		assert_eq!(provider_era_info.delegator_count, 1);
+		assert_eq!(DapiStaking::era_state(current_era).unwrap().staked, init_era_staked + amount);
+		assert_eq!(DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id), amount);
+
+		// The compounded era can't be claimed again
+		assert_noop!(
+			DapiStaking::compound_delegator_reward(Origin::signed(delegator), provider_id),
+			Error::<TestRuntime>::EraOutOfBounds
+		);
+	})
+}
+
+#[test]
+fn claim_delegator_reports_reward_breakdown() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let (delegator_1, delegator_2) = (3, 4);
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator_1, &provider_id, 100);
+		assert_delegate(delegator_2, &provider_id, 300);
+		advance_to_era(era + 1);
+
+		let mut payouts = Vec::new();
+		for (delegator, expected_share) in
+			[(delegator_1, Perbill::from_percent(25)), (delegator_2, Perbill::from_percent(75))]
+		{
+			let init_balance = <TestRuntime as Config>::Currency::free_balance(&delegator);
+			assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+			let amount = <TestRuntime as Config>::Currency::free_balance(&delegator) - init_balance;
+
+			let total_pool = match System::events().last().map(|r| r.event.clone()) {
+				Some(mock::Event::DapiStaking(Event::DelegatorPayout {
+					who,
+					era: payout_era,
+					amount: payout_amount,
+					share,
+					total_pool,
+					..
+				})) => {
+					assert_eq!(who, delegator);
+					assert_eq!(payout_era, era);
+					assert_eq!(payout_amount, amount);
+					assert_eq!(share, expected_share);
+					assert_eq!(share * total_pool, amount);
+					total_pool
+				},
+				_ => panic!("DelegatorPayout event expected"),
+			};
+			payouts.push(total_pool);
+		}
+		// Both delegators get their share of the same pool
+		assert_eq!(payouts[0], payouts[1]);
+	})
+}
+
+#[test]
+fn delegators_fully_claimed_after_last_claim() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		assert_register(1, &provider_id, 100);
+		assert_delegate(3, &provider_id, 100);
+		assert_delegate(4, &provider_id, 300);
+		advance_to_era(era + 1);
+
+		// 1000 for the only provider, delegators share 20% of it.
+		EraState::<TestRuntime>::mutate(era, |info| {
+			info.as_mut().unwrap().rewards = 1000;
+			info.as_mut().unwrap().staked = 500;
+		});
+		assert_ok!(DapiStaking::claim_provider(Origin::signed(1), provider_id, era));
+
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(3), provider_id));
+		let info = DapiStaking::provider_era_info(&provider_id, era).unwrap();
+		assert_eq!(info.delegators_reward_paid, 50);
+		assert!(!info.delegators_fully_claimed);
+		assert!(!info.is_fully_claimed());
+
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(4), provider_id));
+		let info = DapiStaking::provider_era_info(&provider_id, era).unwrap();
+		assert_eq!(info.delegators_reward_paid, 200);
+		assert!(info.delegators_fully_claimed);
+		assert!(info.is_fully_claimed());
+
+		// next era starts with nothing claimed
+		let info = DapiStaking::provider_era_info(&provider_id, era + 1).unwrap();
+		assert_eq!(info.delegators_reward_paid, 0);
+		assert!(!info.delegators_fully_claimed);
+	})
+}
+
+#[test]
+fn delegator_reward_is_scaled_by_provider_score() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		// Half of the delegators' reward depends on the score, which is at 60% during the era.
+		ProviderScores::set(vec![(provider_id, MAX_PROVIDER_SCORE * 3 / 5)]);
+		assert_register(1, &provider_id, 100);
+		assert_delegate(3, &provider_id, 100);
+		assert_delegate(4, &provider_id, 300);
+		advance_to_era(era + 1);
+
+		// 1000 for the only provider, delegators share 200 of it.
+		EraState::<TestRuntime>::mutate(era, |info| {
+			info.as_mut().unwrap().rewards = 1000;
+			info.as_mut().unwrap().staked = 500;
+		});
+		// A later score doesn't change the rewards of the era.
+		ProviderScores::set(vec![(provider_id, MAX_PROVIDER_SCORE)]);
+		assert_eq!(
+			DapiStaking::provider_era_info(&provider_id, era).unwrap().score,
+			Some(MAX_PROVIDER_SCORE * 3 / 5)
+		);
+		assert_eq!(
+			DapiStaking::provider_era_info(&provider_id, era + 1).unwrap().score,
+			Some(MAX_PROVIDER_SCORE * 3 / 5)
+		);
+
+		let init_balance = <TestRuntime as Config>::Currency::free_balance(&3);
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(3), provider_id));
+		assert_eq!(<TestRuntime as Config>::Currency::free_balance(&3) - init_balance, 40);
+
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(4), provider_id));
+		let info = DapiStaking::provider_era_info(&provider_id, era).unwrap();
+		assert_eq!(info.delegators_reward_paid, 40 + 120);
+		assert!(info.delegators_fully_claimed);
+	})
+}
+
+#[test]
+fn split_rewards_of_empty_era_is_zero() {
+	ExternalityBuilder::build().execute_with(|| {
+		let provider_era_info = ProviderEraMetadata { bond: 100, total: 200, ..Default::default() };
+		let era_info = EraMetadata { rewards: 1000, staked: 400 };
+
+		for (provider_era_info, era_info) in [
+			(ProviderEraMetadata { total: 0, ..provider_era_info.clone() }, era_info.clone()),
+			(provider_era_info.clone(), EraMetadata { staked: 0, ..era_info.clone() }),
+			(provider_era_info.clone(), EraMetadata { rewards: 0, ..era_info.clone() }),
+		] {
+			assert_eq!(
+				DapiStaking::split_provider_delegators_rewards(
+					Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE),
+					&provider_era_info,
+					&era_info
+				),
+				(0, 0)
+			);
+		}
+	})
+}
+
+#[test]
+fn provider_commission_change_is_delayed() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let provider_id = MockProvider::default();
+		assert_register(operator, &provider_id, 100);
+		assert_eq!(
+			DapiStaking::provider_info(&provider_id).unwrap().commission,
+			Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE)
+		);
+
+		let commission = Perbill::from_percent(10);
+		assert_noop!(
+			DapiStaking::set_provider_commission(Origin::signed(2), provider_id, commission),
+			Error::<TestRuntime>::NotOwnedProvider
+		);
+		let era = DapiStaking::era().current;
+		assert_ok!(DapiStaking::set_provider_commission(
+			Origin::signed(operator),
+			provider_id,
+			commission
+		));
+		System::assert_last_event(mock::Event::DapiStaking(Event::CommissionChangeScheduled {
+			provider_id,
+			commission,
+			era: era + COMMISSION_CHANGE_DELAY,
+		}));
+
+		advance_to_era(era + COMMISSION_CHANGE_DELAY - 1);
+		assert_eq!(
+			DapiStaking::provider_info(&provider_id).unwrap().commission,
+			Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE)
+		);
+		advance_to_era(era + COMMISSION_CHANGE_DELAY);
+		System::assert_has_event(mock::Event::DapiStaking(Event::CommissionChanged {
+			provider_id,
+			commission,
+		}));
+		assert_eq!(DapiStaking::provider_info(&provider_id).unwrap().commission, commission);
+		assert!(DapiStaking::pending_commissions(&provider_id).is_none());
+
+		// The provider keeps its new commission of the rewards
+		let provider_era_info = ProviderEraMetadata { bond: 100, total: 200, ..Default::default() };
+		let era_info = EraMetadata { rewards: 1000, staked: 400 };
+		assert_eq!(
+			DapiStaking::split_provider_delegators_rewards(
+				commission,
+				&provider_era_info,
+				&era_info
+			),
+			(50, 450)
+		);
+	})
+}
+
+#[test]
+fn delegator_rewards_vest_over_vesting_period() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(era + 2);
+
+		let vesting_period = 10;
+		VestingPeriod::set(vesting_period);
+		let usable_balance = Balances::usable_balance(&delegator);
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		let reward = DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id);
+		assert!(!reward.is_zero());
+
+		let now = System::block_number();
+		let vest_end_block = match System::events().last().map(|record| record.event.clone()) {
+			Some(mock::Event::DapiStaking(Event::PayoutVested {
+				who,
+				provider_id: paid_provider_id,
+				era: paid_era,
+				amount,
+				vest_end_block,
+			})) => {
+				assert_eq!(
+					(who, paid_provider_id, paid_era, amount),
+					(delegator, provider_id, era, reward)
+				);
+				vest_end_block
+			},
+			event => panic!("Unexpected event {:?}", event),
+		};
+		assert!(vest_end_block > now && vest_end_block <= now + vesting_period);
+		assert_eq!(DapiStaking::reward_vesting(&delegator)[0].locked_at(now), reward);
+		assert_eq!(Balances::usable_balance(&delegator), usable_balance);
+
+		run_to_block(vest_end_block);
+		assert_ok!(DapiStaking::unlock_vested_rewards(Origin::signed(delegator)));
+		System::assert_last_event(mock::Event::DapiStaking(Event::VestedRewardsUnlocked {
+			who: delegator,
+			still_locked: 0,
+		}));
+		assert!(DapiStaking::reward_vesting(&delegator).is_empty());
+		assert_eq!(Balances::usable_balance(&delegator), usable_balance + reward);
+		assert_noop!(
+			DapiStaking::unlock_vested_rewards(Origin::signed(delegator)),
+			Error::<TestRuntime>::NothingToWithdraw
+		);
+	})
+}
+
+#[test]
+fn each_vested_reward_has_its_own_schedule() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(era + 5);
+
+		let vesting_period = 10;
+		VestingPeriod::set(vesting_period);
+		let claimed = || DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id);
+		let locked = || Balances::locks(&delegator)[0].amount;
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		let first_reward = claimed();
+		let first = DapiStaking::reward_vesting(&delegator)[0];
+
+		run_for_blocks(4);
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		let second_reward = claimed() - first_reward;
+		let now = System::block_number();
+		let second = RewardVestingInfo::new(second_reward, vesting_period, now);
+		// The first reward keeps vesting on its own schedule
+		assert_eq!(DapiStaking::reward_vesting(&delegator).into_inner(), vec![first, second]);
+		assert_eq!(locked(), first.locked_at(now) + second_reward);
+
+		// Compounded rewards are bonded, so they neither vest nor lock free balance
+		assert_ok!(DapiStaking::compound_delegator_reward(Origin::signed(delegator), provider_id));
+		assert_eq!(DapiStaking::reward_vesting(&delegator).into_inner(), vec![first, second]);
+		assert_eq!(locked(), first.locked_at(now) + second_reward);
+
+		// Beyond `MaxVestingSchedules`, the newest schedule is merged into the new one
+		run_for_blocks(2);
+		let claimed_before = claimed();
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		let third_reward = claimed() - claimed_before;
+		let now = System::block_number();
+		let merged =
+			RewardVestingInfo::new(third_reward + second.locked_at(now), vesting_period, now);
+		assert_eq!(MAX_VESTING_SCHEDULES, 2);
+		assert_eq!(DapiStaking::reward_vesting(&delegator).into_inner(), vec![first, merged]);
+
+		run_to_block(first.end_block());
+		assert_ok!(DapiStaking::unlock_vested_rewards(Origin::signed(delegator)));
+		assert_eq!(DapiStaking::reward_vesting(&delegator).into_inner(), vec![merged]);
+		assert_eq!(locked(), merged.locked_at(first.end_block()));
+	})
+}
+
+#[test]
+fn claim_in_era_without_rewards_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+
+		assert_register(operator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+
+		// Advance to the next era without paying out any block rewards
+		RewardAccumulator::<TestRuntime>::kill();
+		while DapiStaking::era().current == era {
+			System::set_block_number(System::block_number() + 1);
+			DapiStaking::on_initialize(System::block_number());
+		}
+		assert!(DapiStaking::era_state(era).unwrap().rewards.is_zero());
+
+		let init_operator_balance = <TestRuntime as Config>::Currency::free_balance(&operator);
+		let init_delegator_balance = <TestRuntime as Config>::Currency::free_balance(&delegator);
+		assert_ok!(DapiStaking::claim_provider(Origin::signed(operator), provider_id, era));
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		System::assert_last_event(mock::Event::DapiStaking(Event::DelegatorPayout {
+			who: delegator,
+			provider_id,
+			era,
+			amount: 0,
+			share: Perbill::zero(),
+			total_pool: 0,
+		}));
+		assert_eq!(
+			<TestRuntime as Config>::Currency::free_balance(&operator),
+			init_operator_balance
+		);
+		assert_eq!(
+			<TestRuntime as Config>::Currency::free_balance(&delegator),
+			init_delegator_balance
+		);
+	})
+}
+
+#[test]
+fn era_info_update_is_ok() {
+	let mut era = EraInfo::<u64>::default();
+	assert!(!era.has_started());
+	assert!(era.should_update(0));
+
+	era.update(5);
+	assert_eq!(era, EraInfo::new(1, 5, 0));
+	assert!(era.has_started());
+
+	let last = EraInfo::<u64>::new(EraIndex::MAX, 100, 10);
+	assert!(last.is_last());
+	assert!(!era.is_last());
+}
+
+#[test]
+fn era_info_block_helpers_are_ok() {
+	let era = EraInfo::<u64>::new(3, 100, 10);
+	assert_eq!(era.elapsed_blocks(100), 0);
+	assert_eq!(era.remaining_blocks(100), 10);
+	assert_eq!(era.progress_perbill(100), Perbill::zero());
+
+	assert_eq!(era.elapsed_blocks(105), 5);
+	assert_eq!(era.remaining_blocks(105), 5);
+	assert_eq!(era.progress_perbill(105), Perbill::from_percent(50));
+
+	assert_eq!(era.remaining_blocks(110), 0);
+	assert_eq!(era.progress_perbill(110), Perbill::one());
+
+	// overdue era transition and blocks before the era start saturate.
+	assert_eq!(era.remaining_blocks(120), 0);
+	assert_eq!(era.progress_perbill(120), Perbill::one());
+	assert_eq!(era.elapsed_blocks(90), 0);
+
+	assert_eq!(EraInfo::<u64>::era_start_block(0, 1, 10), 1);
+	assert_eq!(EraInfo::<u64>::era_start_block(4, 1, 10), 41);
+}
+
+#[test]
+fn delegation_claimable_eras_are_ok() {
+	let mut delegation = Delegation::<Balance>::default();
+	assert_eq!(delegation.total_unclaimed_eras(), 0);
+	assert_eq!(delegation.oldest_claimable_era(), None);
+	assert_eq!(delegation.newest_stake_era(), None);
+
+	assert_ok!(delegation.stake(5, 1000));
+	assert_ok!(delegation.stake(7, 300));
+	assert_ok!(delegation.unstake(8, 1300));
+	assert_ok!(delegation.stake(15, 3000));
+	assert_eq!(delegation.oldest_claimable_era(), Some(5));
+	assert_eq!(delegation.newest_stake_era(), Some(15));
+
+	let unclaimed_eras = delegation.total_unclaimed_eras();
+	assert_eq!(unclaimed_eras, 4);
+	let claimed_eras = (0..unclaimed_eras).map(|_| delegation.claim().0).collect::<Vec<_>>();
+	assert_eq!(claimed_eras, vec![5, 6, 7, 15]);
+}
+
+#[test]
+fn delegation_delays_only_new_funds() {
+	let mut delegation = Delegation::<Balance>::default();
+	assert_ok!(delegation.stake(5, 1000));
+	delegation.delay_rewards(1000, 6);
+	assert_eq!(delegation.oldest_earning_stake(), Some(0));
+
+	// Topping up only delays the added funds, unstaked funds are taken from them first
+	assert_ok!(delegation.stake(7, 500));
+	delegation.delay_rewards(500, 8);
+	assert_ok!(delegation.unstake(7, 200));
+
+	let earning = (0..3)
+		.map(|_| {
+			let earning = delegation.oldest_earning_stake().unwrap();
+			(delegation.claim().0, earning)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(earning, vec![(5, 0), (6, 1000), (7, 1000)]);
+	assert_eq!(delegation.oldest_earning_stake(), Some(1300));
+}
+
+#[test]
+fn delegation_integrity_is_verified() {
+	let mut delegation = Delegation::<Balance>::default();
+	assert!(delegation.verify_integrity(0));
+
+	assert_ok!(delegation.stake(5, 1000));
+	assert_ok!(delegation.unstake(7, 1000));
+	assert_ok!(delegation.stake(9, 500));
+	assert!(delegation.verify_integrity(9));
+	assert!(!delegation.verify_integrity(8));
+
+	let stake = |amount, era| EraStake::new(amount, era);
+	let unordered = Delegation::<Balance>::from_stakes(vec![stake(10, 5), stake(20, 5)]);
+	assert!(!unordered.verify_integrity(10));
+
+	let mut corrupt =
+		Delegation::<Balance>::from_stakes(vec![stake(0, 3), stake(0, 4), stake(10, 6)]);
+	assert!(!corrupt.verify_integrity(10));
+	assert_eq!(corrupt.trim_leading_zeros(), 2);
+	assert!(corrupt.verify_integrity(10));
+	assert_eq!(corrupt.claim(), (6, 10));
+	assert_eq!(corrupt.trim_leading_zeros(), 0);
+}
+
+#[test]
+fn unbonding_metadata_accessors_are_ok() {
+	let chunk = |amount, unlock_era| UnlockingChunk { amount, unlock_era };
+	assert!(UnbondingMetadata::<Balance>::from_vec(vec![chunk(10, 5), chunk(20, 3)]).is_err());
+	assert!(UnbondingMetadata::<Balance>::from_vec(vec![chunk(10, 3), chunk(20, 3)]).is_err());
+
+	let mut unbonding_info =
+		UnbondingMetadata::<Balance>::from_vec(vec![chunk(10, 3), chunk(20, 7)]).unwrap();
+	unbonding_info.add(chunk(5, 5));
+	assert_eq!(unbonding_info.vec(), &[chunk(10, 3), chunk(5, 5), chunk(20, 7)]);
+	assert_eq!(unbonding_info.iter().map(|chunk| chunk.amount).sum::<Balance>(), 35);
+	assert_eq!(unbonding_info.max_unlock_era(), Some(7));
+	assert_eq!(unbonding_info.vec().binary_search_by(|x| x.unlock_era.cmp(&5)), Ok(1));
+	assert_eq!(UnbondingMetadata::<Balance>::default().max_unlock_era(), None);
+}
+
+#[test]
+fn corrupt_delegation_is_neither_batch_claimed_nor_compounded() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		assert_register(1, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(DapiStaking::era().current + 3);
+
+		let stake = |amount, era| EraStake::new(amount, era);
+		let corrupt = Delegation::<Balance>::from_stakes(vec![stake(0, 1), stake(100, 2)]);
+		DelegationInfo::<TestRuntime>::insert(&delegator, &provider_id, corrupt);
+		assert_noop!(
+			DapiStaking::claim_delegator_batch(Origin::signed(delegator), provider_id, 2),
+			Error::<TestRuntime>::CorruptDelegation
+		);
+		assert_noop!(
+			DapiStaking::compound_delegator_reward(Origin::signed(delegator), provider_id),
+			Error::<TestRuntime>::CorruptDelegation
+		);
+	})
+}
+
+#[test]
+fn fix_delegation_info_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let stake = |amount, era| EraStake::new(amount, era);
+		let corrupt = Delegation::<Balance>::from_stakes(vec![stake(0, 1), stake(100, 2)]);
+		DelegationInfo::<TestRuntime>::insert(&delegator, &provider_id, corrupt);
+		assert_noop!(
+			DapiStaking::claim_delegator(Origin::signed(delegator), provider_id),
+			Error::<TestRuntime>::CorruptDelegation
+		);
+
+		assert_noop!(
+			DapiStaking::fix_delegation_info(Origin::signed(1), delegator, provider_id),
+			BadOrigin
+		);
+		assert_ok!(DapiStaking::fix_delegation_info(Origin::root(), delegator, provider_id));
+		System::assert_last_event(mock::Event::DapiStaking(Event::DelegationInfoFixed {
+			delegator,
+			provider_id,
+			removed_stakes: 1,
+		}));
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &provider_id),
+			Delegation::from_stakes(vec![stake(100, 2)])
+		);
+	})
+}
+
+#[test]
+fn set_era_stake_values_limit_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(DapiStaking::era_stake_values_limit(), MAX_ERA_STAKE_VALUES);
+
+		assert_noop!(
+			DapiStaking::set_era_stake_values_limit(Origin::signed(1), 10),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			DapiStaking::set_era_stake_values_limit(Origin::root(), MIN_ERA_STAKE_VALUES - 1),
+			Error::<TestRuntime>::CannotSetBelowMin
+		);
+		assert_noop!(
+			DapiStaking::set_era_stake_values_limit(Origin::root(), MAX_ERA_STAKE_VALUES),
+			Error::<TestRuntime>::NoWritingSameValue
+		);
+
+		assert_ok!(DapiStaking::set_era_stake_values_limit(Origin::root(), 10));
+		System::assert_last_event(mock::Event::DapiStaking(Event::EraStakeValuesLimitChanged {
+			old: MAX_ERA_STAKE_VALUES,
+			new: 10,
+		}));
+		assert_eq!(DapiStaking::era_stake_values_limit(), 10);
+	})
+}
+
+#[test]
+fn increasing_era_stake_values_limit_allows_delegation() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		assert_register(1, &provider_id, 100);
+		assert_ok!(DapiStaking::set_era_stake_values_limit(Origin::root(), MIN_ERA_STAKE_VALUES));
+
+		assert_delegate(delegator, &provider_id, 100);
+		advance_to_era(DapiStaking::era().current + 1);
+		assert_noop!(
+			DapiStaking::delegate(Origin::signed(delegator), provider_id.clone(), 100),
+			Error::<TestRuntime>::TooManyEraStakeValues
+		);
+
+		assert_ok!(DapiStaking::set_era_stake_values_limit(
+			Origin::root(),
+			MIN_ERA_STAKE_VALUES + 1
+		));
+		assert_delegate(delegator, &provider_id, 100);
+	})
+}
+
+#[test]
+fn delegations_per_delegator_are_limited() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 9;
+		let providers: Vec<_> = (1..=MAX_DELEGATIONS_PER_DELEGATOR as u8 + 1)
+			.map(|seed| MockProvider([seed; 36]))
+			.collect();
+		for (operator, provider_id) in [1, 3, 4, 5].into_iter().zip(providers.iter()) {
+			assert_register(operator, provider_id, 100);
+		}
+
+		// Delegating up to the max is fine
+		for provider_id in providers.iter().take(MAX_DELEGATIONS_PER_DELEGATOR as usize) {
+			assert_delegate(delegator, provider_id, 100);
+		}
+		assert_eq!(
+			DapiStaking::delegator_provider_count(&delegator),
+			MAX_DELEGATIONS_PER_DELEGATOR
+		);
+
+		// Staking more on an already delegated provider is fine as well
+		assert_delegate(delegator, &providers[0], 100);
+
+		let last_provider = providers.last().unwrap().clone();
+		assert_noop!(
+			DapiStaking::delegate(Origin::signed(delegator), last_provider.clone(), 100),
+			Error::<TestRuntime>::TooManyProviderDelegations
+		);
+
+		// Fully unstaking frees a spot
+		assert_ok!(DapiStaking::delegator_unstake(
+			Origin::signed(delegator),
+			providers[1].clone(),
+			100
+		));
+		assert_eq!(
+			DapiStaking::delegator_provider_count(&delegator),
+			MAX_DELEGATIONS_PER_DELEGATOR - 1
+		);
+		assert_delegate(delegator, &last_provider, 100);
+	})
+}
+
+#[test]
+fn stake_effect_delay_skips_rewards_of_new_stake() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+		StakeEffectDelay::set(1);
+
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let start_era = DapiStaking::era().current;
+		assert_register(1, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &provider_id).oldest_earning_stake(),
+			Some(0)
+		);
+		advance_to_era(start_era + 2);
+		let pending = DapiStaking::pending_rewards(delegator, provider_id);
+		assert!(!pending.is_zero());
+
+		// The era of the delegation is claimed without reward
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		assert!(DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id).is_zero());
+
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), provider_id));
+		assert_eq!(DapiStaking::delegator_total_rewards_claimed(&delegator, &provider_id), pending);
+	})
+}
+
+#[test]
+fn stake_effect_delay_skips_rewards_of_redelegated_stake() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+		StakeEffectDelay::set(1);
+
+		let delegator = 2;
+		let (from_provider, to_provider) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		assert_register(1, &from_provider, 100);
+		assert_register(3, &to_provider, 100);
+		assert_delegate(delegator, &from_provider, 100);
+		advance_to_era(DapiStaking::era().current + 2);
+
+		let redelegation_era = DapiStaking::era().current;
+		assert_ok!(DapiStaking::redelegate(
+			Origin::signed(delegator),
+			from_provider,
+			to_provider,
+			100
+		));
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &to_provider).oldest_earning_stake(),
+			Some(0)
+		);
+		advance_to_era(redelegation_era + 2);
+
+		// The era of the redelegation is claimed without reward
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), to_provider));
+		assert!(DapiStaking::delegator_total_rewards_claimed(&delegator, &to_provider).is_zero());
+		assert_ok!(DapiStaking::claim_delegator(Origin::signed(delegator), to_provider));
+		assert!(!DapiStaking::delegator_total_rewards_claimed(&delegator, &to_provider).is_zero());
+	})
+}
+
+#[test]
+fn delegation_records_are_indexed_offchain() {
+	use frame_support::traits::OnFinalize;
+	use sp_core::offchain::{testing::TestOffchainExt, OffchainDbExt};
+	use sp_runtime::offchain::storage::StorageValueRef;
+
+	let delegator = 2;
+	let provider_id = MockProvider::default();
+	let mut ext = ExternalityBuilder::build();
+	let (era, block) = ext.execute_with(|| {
+		initialize_first_block();
+		assert_register(1, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		assert_ok!(DapiStaking::delegator_unstake(Origin::signed(delegator), provider_id, 20));
+
+		let block = System::block_number();
+		DapiStaking::on_finalize(block);
+		(DapiStaking::era().current, block)
+	});
+
+	ext.persist_offchain_overlay();
+	let (offchain, _) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.execute_with(|| {
+		let count = StorageValueRef::persistent(&offchain::record_count_key(block)).get::<u32>();
+		assert_eq!(count, Ok(Some(2)));
+
+		let records = (0..2)
+			.map(|index| {
+				StorageValueRef::persistent(&offchain::record_key(block, index))
+					.get::<DelegationIndexRecord<AccountId, MockProvider, Balance>>()
+					.unwrap()
+					.unwrap()
+			})
+			.collect::<Vec<_>>();
+		let record =
+			|action, amount| DelegationIndexRecord { delegator, provider_id, action, era, amount };
+		assert_eq!(
+			records,
+			vec![record(DelegationAction::Delegate, 100), record(DelegationAction::Unstake, 20)]
+		);
+	});
+}
+
+#[test]
+fn delegator_unstake_all_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 3;
+		let provider_id = MockProvider::default();
+		assert_register(1, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 150);
+
+		assert_ok!(DapiStaking::delegator_unstake_all(Origin::signed(delegator), provider_id));
+		System::assert_last_event(mock::Event::DapiStaking(Event::DelegatorUnstaked {
+			delegator,
+			provider_id,
+			amount: 250,
+		}));
+		assert!(DapiStaking::delegation_info(&delegator, &provider_id)
+			.latest_staked_value()
+			.is_zero());
+		assert_eq!(DapiStaking::unbonding_info(&delegator).sum(), 250);
+
+		assert_noop!(
+			DapiStaking::delegator_unstake_all(Origin::signed(delegator), provider_id),
+			Error::<TestRuntime>::NotStakedProvider
+		);
+	})
+}
+
+#[test]
+fn redelegate_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 9;
+		let from_provider = MockProvider([1; 36]);
+		let to_provider = MockProvider([2; 36]);
+		assert_register(1, &from_provider, 100);
+		assert_register(3, &to_provider, 100);
+		assert_delegate(delegator, &from_provider, 100);
+		let era = DapiStaking::era().current;
+		let init_reserved = <TestRuntime as Config>::Currency::reserved_balance(&delegator);
+		let init_era_staked = DapiStaking::era_state(era).unwrap().staked;
+
+		assert_noop!(
+			DapiStaking::redelegate(Origin::signed(delegator), from_provider, from_provider, 50),
+			Error::<TestRuntime>::RedelegateToSameProvider
+		);
+		// Remaining delegation would be below minimum
+		assert_noop!(
+			DapiStaking::redelegate(Origin::signed(delegator), from_provider, to_provider, 95),
+			Error::<TestRuntime>::InsufficientBond
+		);
+
+		assert_ok!(DapiStaking::redelegate(
+			Origin::signed(delegator),
+			from_provider,
+			to_provider,
+			40
+		));
+		System::assert_has_event(mock::Event::DapiStaking(Event::DelegatorUnstaked {
+			delegator,
+			provider_id: from_provider,
+			amount: 40,
+		}));
+		System::assert_last_event(mock::Event::DapiStaking(Event::Delegated {
+			delegator,
+			provider_id: to_provider,
+			amount: 40,
+		}));
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &from_provider).latest_staked_value(),
+			60
+		);
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &to_provider).latest_staked_value(),
+			40
+		);
+		assert_eq!(DapiStaking::provider_era_info(&from_provider, era).unwrap().total, 160);
+		assert_eq!(DapiStaking::provider_era_info(&to_provider, era).unwrap().total, 140);
+		assert_eq!(DapiStaking::provider_era_info(&to_provider, era).unwrap().delegator_count, 1);
+		assert_eq!(DapiStaking::delegator_provider_count(&delegator), 2);
+
+		// Moving everything that is left frees the delegation on the first provider
+		assert_ok!(DapiStaking::redelegate(
+			Origin::signed(delegator),
+			from_provider,
+			to_provider,
+			1000
+		));
+		assert!(DapiStaking::delegation_info(&delegator, &from_provider)
+			.latest_staked_value()
+			.is_zero());
+		assert_eq!(
+			DapiStaking::delegation_info(&delegator, &to_provider).latest_staked_value(),
+			100
+		);
+		assert_eq!(DapiStaking::provider_era_info(&from_provider, era).unwrap().delegator_count, 0);
+		assert_eq!(DapiStaking::delegator_provider_count(&delegator), 1);
+
+		// Funds stay reserved and nothing is unbonding
+		assert_eq!(<TestRuntime as Config>::Currency::reserved_balance(&delegator), init_reserved);
+		assert!(DapiStaking::unbonding_info(&delegator).is_empty());
+		assert_eq!(DapiStaking::era_state(era).unwrap().staked, init_era_staked);
+
+		assert_unregister(3, &to_provider);
+		assert_noop!(
+			DapiStaking::redelegate(Origin::signed(delegator), to_provider, from_provider, 100),
+			Error::<TestRuntime>::NotOperatedProvider
+		);
+	})
+}
+
+#[test]
+fn provider_delegators_are_tracked() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_id = MockProvider::default();
+		assert_register(1, &provider_id, 100);
+		let delegators = [3, 4, 5, 7, 8];
+		for delegator in delegators {
+			assert_delegate(delegator, &provider_id, 100);
+		}
+		assert_eq!(DapiStaking::delegators_of(provider_id), delegators.to_vec());
+
+		// Staking more doesn't list a delegator twice, while new delegators don't fit
+		assert_delegate(3, &provider_id, 100);
+		assert_eq!(
+			DapiStaking::delegators_of(provider_id).len(),
+			MAX_NUMBER_OF_DELEGATORS as usize
+		);
+		assert_noop!(
+			DapiStaking::delegate(Origin::signed(9), provider_id, 100),
+			Error::<TestRuntime>::MaxNumberOfStakersExceeded
+		);
+
+		// Partially unstaking keeps the delegator, fully unstaking frees its slot
+		assert_ok!(DapiStaking::delegator_unstake(Origin::signed(3), provider_id, 100));
+		assert_ok!(DapiStaking::delegator_unstake(Origin::signed(4), provider_id, 100));
+		assert_eq!(DapiStaking::delegators_of(provider_id), vec![3, 5, 7, 8]);
+		assert_delegate(9, &provider_id, 100);
+		assert_eq!(DapiStaking::delegators_of(provider_id), vec![3, 5, 7, 8, 9]);
+	})
+}
+
+/// Rewrites all delegations in the layout without effective eras.
+fn put_delegations_without_effective_eras() {
+	use codec::Encode;
+	use frame_support::storage::unhashed;
+
+	let era = DapiStaking::era().current;
+	for (delegator, provider_id, delegation) in
+		DelegationInfo::<TestRuntime>::iter().collect::<Vec<_>>()
+	{
+		let key = DelegationInfo::<TestRuntime>::hashed_key_for(&delegator, &provider_id);
+		let old = vec![(codec::Compact(delegation.latest_staked_value()), codec::Compact(era))];
+		unhashed::put_raw(&key, &old.encode());
+	}
+}
+
+#[test]
+fn populate_provider_delegators_migration_is_ok() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		assert_register(1, &provider_1, 100);
+		assert_register(3, &provider_2, 100);
+		assert_delegate(9, &provider_1, 100);
+		assert_delegate(9, &provider_2, 100);
+		assert_delegate(7, &provider_2, 100);
+
+		// Simulate state before delegators were listed
+		ProviderDelegators::<TestRuntime>::remove_all(None);
+		put_delegations_without_effective_eras();
+		StorageVersion::new(3).put::<DapiStaking>();
+
+		migrations::PopulateProviderDelegators::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(DapiStaking::delegators_of(provider_1), vec![9]);
+		let mut delegators = DapiStaking::delegators_of(provider_2);
+		delegators.sort();
+		assert_eq!(delegators, vec![7, 9]);
+		assert_eq!(DapiStaking::on_chain_storage_version(), 4);
+	})
+}
+
+#[test]
+fn populate_era_counters_migration_is_ok() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		let era = DapiStaking::era().current;
+		assert_register(1, &provider_1, 100);
+		assert_register(3, &provider_2, 100);
+		assert_delegate(9, &provider_1, 100);
+		assert_delegate(9, &provider_2, 100);
+		assert_delegate(7, &provider_2, 100);
+
+		// Simulate state before the counters were introduced
+		DelegatorCount::<TestRuntime>::kill();
+		EraActiveProviderCount::<TestRuntime>::remove(era);
+		StorageVersion::new(6).put::<DapiStaking>();
+
+		migrations::PopulateEraCountersToV7::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(DapiStaking::delegator_count(), 2);
+		assert_eq!(DapiStaking::era_active_provider_count(era), 2);
+		assert_eq!(DapiStaking::on_chain_storage_version(), 7);
+	})
+}
+
+#[test]
+fn populate_delegator_provider_count_migration_is_ok() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		assert_register(1, &provider_1, 100);
+		assert_register(3, &provider_2, 100);
+		assert_delegate(9, &provider_1, 100);
+		assert_delegate(9, &provider_2, 100);
+		assert_delegate(7, &provider_2, 100);
+
+		// Simulate state before the counter was introduced
+		DelegatorProviderCount::<TestRuntime>::remove(&9);
+		DelegatorProviderCount::<TestRuntime>::remove(&7);
+		put_delegations_without_effective_eras();
+		StorageVersion::new(0).put::<DapiStaking>();
+
+		migrations::PopulateDelegatorProviderCount::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(DapiStaking::delegator_provider_count(&9), 2);
+		assert_eq!(DapiStaking::delegator_provider_count(&7), 1);
+		assert_eq!(DapiStaking::on_chain_storage_version(), 1);
+	})
+}
+
+#[test]
+fn migrate_provider_era_info_to_v2_is_ok() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		assert_register(1, &provider_id, 100);
+
+		// Simulate the layout without delegator claim tracking
+		let key = ProviderEraInfo::<TestRuntime>::hashed_key_for(&provider_id, era);
+		let old = (
+			codec::Compact(100 as Balance),
+			codec::Compact(100 as Balance),
+			codec::Compact(0u32),
+			true,
+		);
+		unhashed::put_raw(&key, &old.encode());
+		StorageVersion::new(1).put::<DapiStaking>();
+
+		migrations::MigrateProviderEraInfoToV2::<TestRuntime>::on_runtime_upgrade();
+		assert_eq!(DapiStaking::on_chain_storage_version(), 2);
+		migrations::MigrateProviderEraInfoToV8::<TestRuntime>::on_runtime_upgrade();
+
+		let info = DapiStaking::provider_era_info(&provider_id, era).unwrap();
+		assert_eq!(info.total, 100);
+		assert!(info.provider_reward_claimed);
+		assert_eq!(info.delegators_reward_paid, 0);
+		assert!(!info.delegators_fully_claimed);
+		assert_eq!(info.commission, Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE));
+		assert_eq!(DapiStaking::on_chain_storage_version(), 8);
+	})
+}
+
+#[test]
+fn migrate_provider_era_info_to_v8_is_ok() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		let commission = Perbill::from_percent(10);
+		assert_register(1, &provider_id, 100);
+		ProviderInfo::<TestRuntime>::mutate(&provider_id, |info| {
+			info.as_mut().unwrap().commission = commission;
+		});
+		ProviderScores::set(vec![(provider_id, MAX_PROVIDER_SCORE / 2)]);
+
+		// Simulate the layout without commission
+		let key = ProviderEraInfo::<TestRuntime>::hashed_key_for(&provider_id, era);
+		let old = (
+			codec::Compact(100 as Balance),
+			codec::Compact(150 as Balance),
+			codec::Compact(1u32),
+			false,
+			codec::Compact(20 as Balance),
+			false,
+		);
+		unhashed::put_raw(&key, &old.encode());
+		StorageVersion::new(7).put::<DapiStaking>();
+
+		migrations::MigrateProviderEraInfoToV8::<TestRuntime>::on_runtime_upgrade();
+
+		let info = DapiStaking::provider_era_info(&provider_id, era).unwrap();
+		assert_eq!(info.total, 150);
+		assert_eq!(info.delegator_count, 1);
+		assert_eq!(info.delegators_reward_paid, 20);
+		assert_eq!(info.commission, commission);
+		assert_eq!(info.score, Some(MAX_PROVIDER_SCORE / 2));
+		assert_eq!(DapiStaking::on_chain_storage_version(), 8);
+	})
+}
+
+#[test]
+fn migrate_provider_info_to_v5_is_ok() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let operator = 1;
+		let provider_id = MockProvider::default();
+		assert_register(operator, &provider_id, 100);
+
+		// Simulate the layout without commission
+		let key = ProviderInfo::<TestRuntime>::hashed_key_for(&provider_id);
+		unhashed::put_raw(&key, &(operator, ProviderStatus::Active, false).encode());
+		StorageVersion::new(4).put::<DapiStaking>();
+
+		migrations::MigrateProviderInfoToV5::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(
+			DapiStaking::provider_info(&provider_id),
+			Some(ProviderMetadata::new(
+				operator,
+				Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE)
+			))
+		);
+		assert_eq!(DapiStaking::on_chain_storage_version(), 5);
+	})
+}
+
+#[test]
+fn migrate_delegation_info_to_v6_is_ok() {
+	use codec::Encode;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 2;
+		let provider_id = MockProvider::default();
+		let era = DapiStaking::era().current;
+		assert_register(1, &provider_id, 100);
+		assert_delegate(delegator, &provider_id, 100);
+
+		// Simulate the layout without effective eras
+		let key = DelegationInfo::<TestRuntime>::hashed_key_for(&delegator, &provider_id);
+		let old = vec![(codec::Compact(100 as Balance), codec::Compact(era))];
+		unhashed::put_raw(&key, &old.encode());
+		StorageVersion::new(5).put::<DapiStaking>();
+
+		migrations::MigrateDelegationInfoToV6::<TestRuntime>::on_runtime_upgrade();
+
+		let delegation = DapiStaking::delegation_info(&delegator, &provider_id);
+		assert_eq!(delegation.latest_staked_value(), 100);
+		assert_eq!(delegation.oldest_claimable_era(), Some(era));
+		assert_eq!(delegation.oldest_earning_stake(), Some(100));
+		assert_eq!(DapiStaking::on_chain_storage_version(), 6);
+	})
+}
+
+#[test]
+fn populate_active_providers_migration_is_ok() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let (active, inactive) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		assert_register(1, &active, 100);
+		assert_register(3, &inactive, 100);
+		assert_unregister(3, &inactive);
+
+		// Simulate the state before active providers were indexed
+		ActiveProviders::<TestRuntime>::kill();
+		StorageVersion::new(2).put::<DapiStaking>();
+
+		migrations::PopulateActiveProviders::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(DapiStaking::active_providers().into_iter().collect::<Vec<_>>(), vec![active]);
+		assert_eq!(DapiStaking::active_provider_count(), 1);
+		assert_eq!(DapiStaking::on_chain_storage_version(), 3);
+	})
+}
+
+#[cfg(feature = "impl-staking-interface")]
+#[test]
+fn staking_interface_constants_are_ok() {
+	use sp_staking::StakingInterface;
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		assert_eq!(<DapiStaking as StakingInterface>::minimum_bond(), MIN_DELEGATOR_STAKE);
+		assert_eq!(<DapiStaking as StakingInterface>::bonding_duration(), UNBONDING_PERIOD);
+		assert_eq!(<DapiStaking as StakingInterface>::current_era(), DapiStaking::era().current);
+
+		advance_to_era(DapiStaking::era().current + 2);
+		assert_eq!(<DapiStaking as StakingInterface>::current_era(), DapiStaking::era().current);
+	})
+}
+
+#[cfg(feature = "impl-staking-interface")]
+#[test]
+fn staking_interface_stake_is_ok() {
+	use sp_staking::StakingInterface;
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let delegator = 2;
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		assert_register(1, &provider_1, 100);
+		assert_register(3, &provider_2, 100);
+
+		assert_eq!(<DapiStaking as StakingInterface>::active_stake(&delegator), None);
+		assert_eq!(<DapiStaking as StakingInterface>::total_stake(&delegator), None);
+
+		assert_delegate(delegator, &provider_1, 100);
+		assert_delegate(delegator, &provider_2, 50);
+		assert_eq!(<DapiStaking as StakingInterface>::active_stake(&delegator), Some(150));
+		assert_eq!(<DapiStaking as StakingInterface>::total_stake(&delegator), Some(150));
+
+		assert_ok!(DapiStaking::delegator_unstake(Origin::signed(delegator), provider_2, 20));
+		assert_eq!(<DapiStaking as StakingInterface>::active_stake(&delegator), Some(130));
+		assert_eq!(<DapiStaking as StakingInterface>::total_stake(&delegator), Some(150));
+
+		assert!(<DapiStaking as StakingInterface>::unbond(delegator, 10).is_err());
+	})
+}
+
+#[test]
+fn delegator_apr_is_estimated_from_current_era() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let (delegated, bonded_only) = (MockProvider([1; 36]), MockProvider([2; 36]));
+		let delegator = 1337;
+		let delegated_amount = 100_000_000_000;
+		let start_era = DapiStaking::era().current;
+
+		assert_register(1, &delegated, 100);
+		assert_register(3, &bonded_only, 100);
+		assert_delegate(delegator, &delegated, delegated_amount);
+		advance_to_era(start_era + 1);
+
+		// Nothing to extrapolate from at the first block of an era
+		assert!(DapiStaking::estimated_delegator_apr(&delegated, BLOCKS_PER_ERA).is_zero());
+
+		run_for_blocks(1);
+		let era_info = EraMetadata {
+			rewards: BLOCK_REWARD * BLOCKS_PER_ERA as Balance,
+			staked: DapiStaking::total_tvl(),
+		};
+		let provider_era_info = DapiStaking::provider_era_info(&delegated, start_era + 1).unwrap();
+		let (_, delegators_reward) = DapiStaking::split_provider_delegators_rewards(
+			<TestRuntime as Config>::ProviderRewardsPercentage::get(),
+			&provider_era_info,
+			&era_info,
+		);
+		let apr = DapiStaking::estimated_delegator_apr(&delegated, 10 * BLOCKS_PER_ERA);
+		assert!(!apr.is_zero());
+		assert_eq!(apr, Perbill::from_rational(10 * delegators_reward, delegated_amount));
+
+		// Years shorter than an era, unknown providers and undelegated providers yield nothing
+		assert!(DapiStaking::estimated_delegator_apr(&delegated, BLOCKS_PER_ERA - 1).is_zero());
+		assert!(
+			DapiStaking::estimated_delegator_apr(&MockProvider([3; 36]), BLOCKS_PER_ERA).is_zero()
+		);
+		assert_eq!(
+			DapiStaking::all_providers_apr(10 * BLOCKS_PER_ERA),
+			vec![(delegated, apr), (bonded_only, Perbill::zero())]
+		);
+	})
+}
+
+#[test]
+fn populate_era_providers_to_v9_is_ok() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_first_block();
+
+		let provider_1 = MockProvider([1; 36]);
+		let provider_2 = MockProvider([2; 36]);
+		assert_register(1, &provider_1, 100);
+		assert_register(3, &provider_2, 100);
+		let era = DapiStaking::era().current;
+		advance_to_era(era + MAX_CLAIMABLE_ERA_AGE + 2);
+
+		// Simulate state before the index was introduced
+		let _ = EraProviders::<TestRuntime>::remove_all(None);
+		let _ = EraProviderCount::<TestRuntime>::remove_all(None);
+		ExpiryCursor::<TestRuntime>::kill();
+		StorageVersion::new(8).put::<DapiStaking>();
+
+		migrations::PopulateEraProvidersToV9::<TestRuntime>::on_runtime_upgrade();
+
+		for era in era..=era + MAX_CLAIMABLE_ERA_AGE + 2 {
+			assert_eq!(DapiStaking::era_provider_count(era), 2);
+			let mut providers: Vec<_> =
+				EraProviders::<TestRuntime>::iter_prefix_values(era).collect();
+			providers.sort();
+			assert_eq!(providers, vec![provider_1, provider_2]);
+		}
+		// Eras which expired before the upgrade aren't checked again
+		assert_eq!(DapiStaking::expiry_cursor(), (era + 2, 0));
+		assert_eq!(DapiStaking::on_chain_storage_version(), 9);
+	})
+}
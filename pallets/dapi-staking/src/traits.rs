@@ -1,11 +1,46 @@
 use frame_support::pallet_prelude::DispatchResultWithPostInfo;
+use sp_runtime::Perbill;
+
+use crate::types::EraIndex;
+
+/// Highest performance score of a provider.
+pub const MAX_PROVIDER_SCORE: u32 = 10_000;
 
 pub trait DapiStakingRegistration<AccountId, Provider, Balance> {
+	/// Register a provider bonding `deposit`, which must be at least `min_bond`.
 	fn register_provider(
 		origin: AccountId,
 		provider_id: Provider,
 		deposit: Balance,
+		min_bond: Balance,
 	) -> DispatchResultWithPostInfo;
 
 	fn unregister_provider(provider_id: Provider) -> DispatchResultWithPostInfo;
+
+	/// Slash `slash_ratio` of the bond of an active provider.
+	fn slash_provider(provider_id: Provider, slash_ratio: Perbill) -> DispatchResultWithPostInfo;
+
+	/// Get the current staking era.
+	fn current_era() -> EraIndex;
+
+	/// Get the amount currently delegated by `delegator` over all providers.
+	fn delegated_stake(delegator: &AccountId) -> Balance;
+
+	/// Get the total amount staked in the current era.
+	fn total_staked() -> Balance;
+
+	/// Get the total amount staked on a provider in the current era.
+	fn provider_stake(provider_id: &Provider) -> Balance;
+}
+
+pub trait ProviderScoreInfo<Provider> {
+	/// Performance score of a provider, from 0 to `MAX_PROVIDER_SCORE`. `None` if it hasn't
+	/// been scored yet.
+	fn provider_score(provider_id: &Provider) -> Option<u32>;
+}
+
+impl<Provider> ProviderScoreInfo<Provider> for () {
+	fn provider_score(_: &Provider) -> Option<u32> {
+		None
+	}
 }
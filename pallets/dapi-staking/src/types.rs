@@ -1,8 +1,8 @@
 use codec::{Decode, Encode, HasCompact};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Zero},
-	RuntimeDebug,
+	traits::{AtLeast32BitUnsigned, Saturating, UniqueSaturatedInto, Zero},
+	Perbill, RuntimeDebug,
 };
 use sp_std::{ops::Add, prelude::*};
 
@@ -19,14 +19,71 @@ pub struct ProviderMetadata<AccountId> {
 	pub owner: AccountId,
 	pub status: ProviderStatus,
 	pub bond_withdrawn: bool,
+	/// Part of the provider's era rewards kept by the provider, the rest goes to delegators.
+	pub commission: Perbill,
 }
 
 impl<AccountId> ProviderMetadata<AccountId> {
-	pub fn new(owner: AccountId) -> Self {
-		Self { owner, status: ProviderStatus::Active, bond_withdrawn: false }
+	pub fn new(owner: AccountId, commission: Perbill) -> Self {
+		Self { owner, status: ProviderStatus::Active, bond_withdrawn: false, commission }
 	}
 }
 
+/// A delegator reward claimed for a single era, kept for auditing past payouts.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ClaimRecord<Balance, BlockNumber> {
+	/// Era the reward was earned in.
+	pub era: EraIndex,
+	/// Claimed reward.
+	pub amount: Balance,
+	/// Block in which the reward was claimed.
+	pub claimed_at: BlockNumber,
+}
+
+/// Staking totals of an era, returned for governance analysis.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EraHistoryEntry<Balance> {
+	pub era: EraIndex,
+	pub total_staked: Balance,
+	/// Rewards of all providers, only known once the era has ended.
+	pub total_rewards: Balance,
+	/// Number of providers with stake info for the era.
+	pub provider_count: u32,
+}
+
+/// Staking totals of a provider in an era, returned for governance analysis.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ProviderEraEntry<Balance> {
+	pub era: EraIndex,
+	pub bond: Balance,
+	/// Provider bond plus the stake of its delegators.
+	pub total_staked: Balance,
+	pub delegator_count: u32,
+	/// Rewards of the provider and its delegators, only known once the era has ended.
+	pub total_rewards: Balance,
+	pub provider_reward_claimed: bool,
+}
+
+/// Delegation action recorded in the offchain index.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum DelegationAction {
+	Delegate,
+	Unstake,
+	Claim,
+}
+
+/// A delegation action written to the offchain database, see [`crate::offchain`].
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DelegationIndexRecord<AccountId, ProviderId, Balance> {
+	pub delegator: AccountId,
+	pub provider_id: ProviderId,
+	pub action: DelegationAction,
+	/// Era the action happened in, or the rewarded era for claims.
+	pub era: EraIndex,
+	/// Delegated, unstaked or claimed amount.
+	pub amount: Balance,
+}
+
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct RewardInfo<Balance: HasCompact> {
 	#[codec(compact)]
@@ -57,6 +114,33 @@ pub struct ProviderEraMetadata<Balance: HasCompact> {
 	pub delegator_count: u32,
 	/// Indicates whether rewards were claimed by provider for this era or not.
 	pub provider_reward_claimed: bool,
+	/// Rewards paid to delegators for this era so far.
+	#[codec(compact)]
+	pub delegators_reward_paid: Balance,
+	/// Indicates whether the whole delegators reward was paid for this era or not.
+	pub delegators_fully_claimed: bool,
+	/// Provider commission in effect during this era.
+	pub commission: Perbill,
+	/// Provider performance score in effect during this era, `None` if it wasn't scored.
+	pub score: Option<u32>,
+}
+
+impl<Balance: HasCompact + AtLeast32BitUnsigned + Copy> ProviderEraMetadata<Balance> {
+	/// Record `amount` paid to a delegator out of the era's `total_reward` for all delegators.
+	///
+	/// Rounding of the delegator shares may leave dust in the pool, in which case the era is
+	/// never flagged as fully claimed.
+	pub fn note_delegator_reward_paid(&mut self, amount: Balance, total_reward: Balance) {
+		self.delegators_reward_paid = self.delegators_reward_paid.saturating_add(amount);
+		if self.delegators_reward_paid >= total_reward {
+			self.delegators_fully_claimed = true;
+		}
+	}
+
+	/// Whether both provider and delegators claimed their rewards, making the entry prunable.
+	pub fn is_fully_claimed(&self) -> bool {
+		self.provider_reward_claimed && self.delegators_fully_claimed
+	}
 }
 
 /// Used to represent how much was staked in a particular era.
@@ -67,11 +151,37 @@ pub struct EraStake<Balance: AtLeast32BitUnsigned + Copy> {
 	pub amount: Balance,
 	#[codec(compact)]
 	pub era: EraIndex,
+	/// Part of the stake which only earns rewards from `effective_era`.
+	#[codec(compact)]
+	pub delayed: Balance,
+	/// First era in which the delayed part of the stake earns rewards.
+	#[codec(compact)]
+	pub effective_era: EraIndex,
 }
 
 impl<Balance: AtLeast32BitUnsigned + Copy> EraStake<Balance> {
-	fn new(amount: Balance, era: EraIndex) -> Self {
-		Self { amount, era }
+	pub(crate) fn new(amount: Balance, era: EraIndex) -> Self {
+		Self { amount, era, delayed: Zero::zero(), effective_era: era }
+	}
+
+	/// Stake of `amount` in `era`, keeping the reward delay of the `previous` stake. Unstaked
+	/// funds are taken from the delayed part first.
+	fn following(previous: &Self, amount: Balance, era: EraIndex) -> Self {
+		let delayed = if era < previous.effective_era {
+			previous.delayed.saturating_sub(previous.amount.saturating_sub(amount))
+		} else {
+			Zero::zero()
+		};
+		Self { amount, era, delayed, effective_era: previous.effective_era.max(era) }
+	}
+
+	/// Part of the stake which earns rewards in `era`.
+	fn earning(&self) -> Balance {
+		if self.era < self.effective_era {
+			self.amount.saturating_sub(self.delayed)
+		} else {
+			self.amount
+		}
 	}
 }
 
@@ -105,6 +215,10 @@ pub struct Delegation<Balance: AtLeast32BitUnsigned + Copy> {
 }
 
 impl<Balance: AtLeast32BitUnsigned + Copy> Delegation<Balance> {
+	pub(crate) fn from_stakes(stakes: Vec<EraStake<Balance>>) -> Self {
+		Self { stakes }
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.stakes.is_empty()
 	}
@@ -132,10 +246,11 @@ impl<Balance: AtLeast32BitUnsigned + Copy> Delegation<Balance> {
 			}
 
 			let new_stake_amount = stake.amount.saturating_add(amount);
+			let new_stake = EraStake::following(stake, new_stake_amount, current_era);
 			if current_era == stake.era {
-				*stake = EraStake::new(new_stake_amount, current_era)
+				*stake = new_stake
 			} else {
-				self.stakes.push(EraStake::new(new_stake_amount, current_era))
+				self.stakes.push(new_stake)
 			}
 		} else {
 			self.stakes.push(EraStake::new(amount, current_era))
@@ -169,10 +284,11 @@ impl<Balance: AtLeast32BitUnsigned + Copy> Delegation<Balance> {
 			}
 
 			let new_stake_amount = stake.amount.saturating_sub(amount);
+			let new_stake = EraStake::following(stake, new_stake_amount, current_era);
 			if current_era == stake.era {
-				*stake = EraStake::new(new_stake_amount, current_era)
+				*stake = new_stake
 			} else {
-				self.stakes.push(EraStake::new(new_stake_amount, current_era))
+				self.stakes.push(new_stake)
 			}
 
 			// Removes unstaked values if they're no longer valid for comprehension
@@ -214,8 +330,7 @@ impl<Balance: AtLeast32BitUnsigned + Copy> Delegation<Balance> {
 			let delegation = *stake;
 
 			if self.stakes.len() == 1 || self.stakes[1].era > delegation.era + 1 {
-				self.stakes[0] =
-					EraStake { amount: delegation.amount, era: delegation.era.saturating_add(1) }
+				self.stakes[0] = EraStake { era: delegation.era.saturating_add(1), ..delegation }
 			} else {
 				// in case: self.delegations[1].era == delegation.era + 1
 				self.stakes.remove(0);
@@ -232,6 +347,65 @@ impl<Balance: AtLeast32BitUnsigned + Copy> Delegation<Balance> {
 		}
 	}
 
+	/// Number of eras that can be claimed up to, and including, the newest stake era.
+	///
+	/// Eras with zero stake are not counted. E.g. for
+	/// `stakes: [<5, 1000>, <7, 1300>, <8, 0>, <15, 3000>]` this returns `4` (eras 5, 6, 7 and 15).
+	pub fn total_unclaimed_eras(&self) -> u32 {
+		let spans = self
+			.stakes
+			.windows(2)
+			.filter(|pair| !pair[0].amount.is_zero())
+			.map(|pair| pair[1].era.saturating_sub(pair[0].era))
+			.fold(0u32, |acc, span| acc.saturating_add(span));
+		let newest = self.stakes.last().map_or(0, |stake| (!stake.amount.is_zero()) as u32);
+		spans.saturating_add(newest)
+	}
+
+	/// Oldest era available for claiming.
+	pub fn oldest_claimable_era(&self) -> Option<EraIndex> {
+		self.stakes.first().map(|stake| stake.era)
+	}
+
+	/// Part of the stake of the oldest claimable era which earns rewards.
+	pub fn oldest_earning_stake(&self) -> Option<Balance> {
+		self.stakes.first().map(|stake| stake.earning())
+	}
+
+	/// Postpone the rewards of `amount` of the latest stake until `effective_era`.
+	///
+	/// Funds staked before keep earning rewards, unless they are themselves still delayed, in
+	/// which case they are postponed along.
+	pub fn delay_rewards(&mut self, amount: Balance, effective_era: EraIndex) {
+		if let Some(stake) = self.stakes.last_mut() {
+			if stake.era < effective_era {
+				stake.delayed = stake.delayed.saturating_add(amount).min(stake.amount);
+				stake.effective_era = stake.effective_era.max(effective_era);
+			}
+		}
+	}
+
+	/// Era of the newest stake entry.
+	pub fn newest_stake_era(&self) -> Option<EraIndex> {
+		self.stakes.last().map(|stake| stake.era)
+	}
+
+	/// Whether the stakes are consistent, i.e. their eras are strictly increasing and not newer
+	/// than `current_era`, and the oldest stake isn't zero.
+	pub fn verify_integrity(&self, current_era: EraIndex) -> bool {
+		self.stakes.first().map_or(true, |stake| !stake.amount.is_zero()) &&
+			self.stakes.iter().all(|stake| stake.era <= current_era) &&
+			self.stakes.windows(2).all(|pair| pair[0].era < pair[1].era)
+	}
+
+	/// Remove the zero stakes at the start, which can't be claimed. Returns the number of
+	/// removed stakes.
+	pub fn trim_leading_zeros(&mut self) -> u32 {
+		let zeros = self.stakes.iter().take_while(|stake| stake.amount.is_zero()).count();
+		self.stakes.drain(..zeros);
+		zeros as u32
+	}
+
 	/// Latest staked value.
 	/// E.g. if delegator is fully unstaked, this will return `Zero`.
 	/// Otherwise returns a non-zero balance.
@@ -271,6 +445,28 @@ impl<Balance> UnbondingMetadata<Balance>
 where
 	Balance: AtLeast32BitUnsigned + Default + Copy,
 {
+	/// Create unbonding metadata from `chunks`, which must be sorted by strictly ascending
+	/// unlock era.
+	pub fn from_vec(chunks: Vec<UnlockingChunk<Balance>>) -> Result<Self, &'static str> {
+		if chunks.windows(2).any(|pair| pair[0].unlock_era >= pair[1].unlock_era) {
+			return Err("Unlocking chunks must be sorted by unlock era.")
+		}
+		Ok(Self { unlocking_chunks: chunks })
+	}
+
+	pub fn vec(&self) -> &[UnlockingChunk<Balance>] {
+		&self.unlocking_chunks
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &UnlockingChunk<Balance>> {
+		self.unlocking_chunks.iter()
+	}
+
+	/// Unlock era of the latest chunk, if any.
+	pub fn max_unlock_era(&self) -> Option<EraIndex> {
+		self.unlocking_chunks.last().map(|chunk| chunk.unlock_era)
+	}
+
 	pub fn len(&self) -> u32 {
 		self.unlocking_chunks.len() as u32
 	}
@@ -313,10 +509,49 @@ where
 	}
 }
 
+/// Linear vesting of claimed delegator rewards. `locked` is released by `per_block` every block
+/// from `starting_block` on.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RewardVestingInfo<Balance, BlockNumber> {
+	#[codec(compact)]
+	pub locked: Balance,
+	#[codec(compact)]
+	pub per_block: Balance,
+	pub starting_block: BlockNumber,
+}
+
+impl<Balance, BlockNumber> RewardVestingInfo<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Vesting of `locked` over `period` blocks, starting at block `now`.
+	pub fn new(locked: Balance, period: BlockNumber, now: BlockNumber) -> Self {
+		let period: u32 = period.unique_saturated_into();
+		let blocks = Balance::from(period.max(1));
+		let per_block = locked.saturating_add(blocks.saturating_sub(1u32.into())) / blocks;
+		Self { locked, per_block: per_block.max(1u32.into()), starting_block: now }
+	}
+
+	/// Amount which is still locked at block `now`.
+	pub fn locked_at(&self, now: BlockNumber) -> Balance {
+		let elapsed: u32 = now.saturating_sub(self.starting_block).unique_saturated_into();
+		self.locked.saturating_sub(self.per_block.saturating_mul(elapsed.into()))
+	}
+
+	/// Block at which the whole amount is vested.
+	pub fn end_block(&self) -> BlockNumber {
+		let duration =
+			self.locked.saturating_add(self.per_block.saturating_sub(1u32.into())) / self.per_block;
+		let duration: u32 = duration.unique_saturated_into();
+		self.starting_block.saturating_add(duration.into())
+	}
+}
+
 /// The current era index and transition information
 #[derive(Default, Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct EraInfo<BlockNumber> {
-	/// Current era index
+	/// Current era index, `0` before the first era started
 	pub current: EraIndex,
 	/// The first block of the current era
 	pub first_block: BlockNumber,
@@ -332,12 +567,51 @@ impl<
 		EraInfo { current, first_block, length }
 	}
 
+	/// Whether the first era started, eras are numbered from `1`.
+	pub fn has_started(&self) -> bool {
+		self.current != 0
+	}
+
+	/// Whether the era index can't be increased anymore.
+	pub fn is_last(&self) -> bool {
+		self.current == EraIndex::MAX
+	}
+
 	pub fn should_update(&self, current_block: B) -> bool {
-		current_block - self.first_block >= self.length.into() || self.current == 0
+		current_block - self.first_block >= self.length.into() || !self.has_started()
 	}
 
+	/// Move to the next era starting at `current_block`. The last era is kept once reached, so
+	/// that the era index never wraps to `0`.
 	pub fn update(&mut self, current_block: B) {
-		self.current = self.current.saturating_add(1u32);
+		debug_assert!(!self.is_last(), "era index must not overflow");
+		if self.is_last() {
+			return
+		}
+		self.current += 1;
 		self.first_block = current_block;
 	}
 }
+
+impl<B: AtLeast32BitUnsigned + Copy> EraInfo<B> {
+	/// Number of blocks left until the next era is due at block `current`.
+	pub fn remaining_blocks(&self, current: B) -> B {
+		self.first_block.saturating_add(B::from(self.length)).saturating_sub(current)
+	}
+
+	/// Number of blocks passed since the current era started, at block `current`.
+	pub fn elapsed_blocks(&self, current: B) -> B {
+		current.saturating_sub(self.first_block)
+	}
+
+	/// Portion of the current era that has passed at block `current`.
+	pub fn progress_perbill(&self, current: B) -> Perbill {
+		let elapsed: u128 = self.elapsed_blocks(current).unique_saturated_into();
+		Perbill::from_rational(elapsed, self.length as u128)
+	}
+
+	/// First block of `era`, assuming all eras since `genesis_block` lasted `length` blocks.
+	pub fn era_start_block(era: EraIndex, genesis_block: B, length: u32) -> B {
+		genesis_block.saturating_add(B::from(era.saturating_mul(length)))
+	}
+}
@@ -38,9 +38,19 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_dapi_staking.
 pub trait WeightInfo {
+	#[rustfmt::skip]
+	fn on_initialize_new_era(n: u32, ) -> Weight;
 	#[rustfmt::skip]
 	fn set_blocks_per_era() -> Weight;
 	#[rustfmt::skip]
+	fn force_new_era() -> Weight;
+	#[rustfmt::skip]
+	fn force_new_era_at() -> Weight;
+	#[rustfmt::skip]
+	fn set_era_stake_values_limit() -> Weight;
+	#[rustfmt::skip]
+	fn register_provider_batch(n: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn provider_bond_more() -> Weight;
 	#[rustfmt::skip]
 	fn provider_bond_less() -> Weight;
@@ -49,20 +59,67 @@ pub trait WeightInfo {
 	#[rustfmt::skip]
 	fn delegator_unstake() -> Weight;
 	#[rustfmt::skip]
+	fn redelegate() -> Weight;
+	#[rustfmt::skip]
 	fn withdraw_unbonded() -> Weight;
 	#[rustfmt::skip]
 	fn claim_provider() -> Weight;
 	#[rustfmt::skip]
 	fn claim_delegator() -> Weight;
 	#[rustfmt::skip]
+	fn claim_delegator_batch(n: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn compound_delegator_reward() -> Weight;
+	#[rustfmt::skip]
+	fn reset_provider_reward_stats() -> Weight;
+	#[rustfmt::skip]
+	fn fix_delegation_info() -> Weight;
+	#[rustfmt::skip]
+	fn prune_old_era(n: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn provider_withdraw_unregistered() -> Weight;
 	#[rustfmt::skip]
 	fn delegator_withdraw_unregistered() -> Weight;
+	#[rustfmt::skip]
+	fn accelerate_provider_withdrawal() -> Weight;
+	#[rustfmt::skip]
+	fn accelerate_delegator_withdrawal() -> Weight;
+	#[rustfmt::skip]
+	fn initiate_provider_transfer() -> Weight;
+	#[rustfmt::skip]
+	fn accept_provider_transfer() -> Weight;
+	#[rustfmt::skip]
+	fn cancel_provider_transfer() -> Weight;
+	#[rustfmt::skip]
+	fn set_provider_commission() -> Weight;
+	#[rustfmt::skip]
+	fn unlock_vested_rewards(s: u32, ) -> Weight;
 }
 
 /// Weights for pallet_dapi_staking using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: DapiStaking Era (r:1 w:1)
+	// Storage: DapiStaking ForceNewEra (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:2)
+	// Storage: DapiStaking RewardAccumulator (r:1 w:1)
+	// Storage: DapiStaking ActiveProviders (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking ProviderInactiveEras (r:1 w:1)
+	// Storage: DapiStaking PendingCommissions (r:1 w:0)
+	// Storage: Dapi ProviderScore (r:1 w:0)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:0 w:1)
+	#[rustfmt::skip]
+	fn on_initialize_new_era(n: u32, ) -> Weight {
+		(9_310_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((5_874_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: DapiStaking Era (r:1 w:1)
 	#[rustfmt::skip]
 	fn set_blocks_per_era() -> Weight {
@@ -70,6 +127,41 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: DapiStaking ForceNewEra (r:0 w:1)
+	#[rustfmt::skip]
+	fn force_new_era() -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking NextEraStartBlock (r:0 w:1)
+	#[rustfmt::skip]
+	fn force_new_era_at() -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking EraStakeValuesLimit (r:1 w:1)
+	#[rustfmt::skip]
+	fn set_era_stake_values_limit() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking ActiveProviders (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:0 w:1)
+	// Storage: DapiStaking ProviderEraInfo (r:0 w:1)
+	// Storage: DapiStaking EraState (r:1 w:1)
+	#[rustfmt::skip]
+	fn register_provider_batch(n: u32, ) -> Weight {
+		(2_136_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((34_867_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: DapiStaking ProviderInfo (r:1 w:0)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
@@ -97,11 +189,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn delegate() -> Weight {
-		(32_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: DapiStaking ProviderInfo (r:1 w:0)
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
@@ -109,11 +204,28 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking UnbondingInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn delegator_unstake() -> Weight {
-		(27_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:2 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking DelegationInfo (r:2 w:2)
+	// Storage: DapiStaking ProviderEraInfo (r:2 w:2)
+	// Storage: DapiStaking EraStakeValuesLimit (r:1 w:0)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:2 w:2)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn redelegate() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	// Storage: DapiStaking UnbondingInfo (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
@@ -127,23 +239,97 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: DapiStaking ProviderTotalRewardsClaimed (r:1 w:1)
 	#[rustfmt::skip]
 	fn claim_provider() -> Weight {
 		(20_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
 	// Storage: DapiStaking ProviderInfo (r:1 w:0)
 	// Storage: DapiStaking Era (r:1 w:0)
-	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: DapiStaking DelegatorTotalRewardsClaimed (r:1 w:1)
+	// Storage: DapiStaking ClaimHistory (r:1 w:1)
+	// Storage: DapiStaking RewardVesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn claim_delegator() -> Weight {
-		(23_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: DapiStaking DelegatorTotalRewardsClaimed (r:1 w:1)
+	// Storage: DapiStaking ClaimHistory (r:1 w:1)
+	// Storage: DapiStaking RewardVesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn claim_delegator_batch(n: u32, ) -> Weight {
+		(18_612_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((9_874_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:2 w:2)
+	// Storage: DapiStaking EraState (r:2 w:1)
+	// Storage: DapiStaking EraStakeValuesLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: DapiStaking DelegatorTotalRewardsClaimed (r:1 w:1)
+	// Storage: DapiStaking ClaimHistory (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn compound_delegator_reward() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: DapiStaking ProviderTotalRewardsClaimed (r:0 w:1)
+	#[rustfmt::skip]
+	fn reset_provider_reward_stats() -> Weight {
+		(8_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	#[rustfmt::skip]
+	fn fix_delegation_info() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: DapiStaking ExpiryCursor (r:1 w:0)
+	// Storage: DapiStaking PruneCursor (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:2 w:1)
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking EraActiveProviderCount (r:0 w:1)
+	#[rustfmt::skip]
+	fn prune_old_era(n: u32, ) -> Weight {
+		(16_243_000 as Weight)
+			// Standard Error: 6_000
+			.saturating_add((8_127_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: DapiStaking ProviderInfo (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
@@ -158,16 +344,112 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn delegator_withdraw_unregistered() -> Weight {
 		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn accelerate_provider_withdrawal() -> Weight {
+		(26_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking DelegatorCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn accelerate_delegator_withdrawal() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: DapiStaking PendingTransfers (r:0 w:1)
+	#[rustfmt::skip]
+	fn initiate_provider_transfer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking PendingTransfers (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	#[rustfmt::skip]
+	fn accept_provider_transfer() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking PendingTransfers (r:1 w:1)
+	#[rustfmt::skip]
+	fn cancel_provider_transfer() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking PendingCommissions (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_provider_commission() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking RewardVesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	#[rustfmt::skip]
+	fn unlock_vested_rewards(s: u32, ) -> Weight {
+		(17_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((312_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// Storage: DapiStaking Era (r:1 w:1)
+	// Storage: DapiStaking ForceNewEra (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:2)
+	// Storage: DapiStaking RewardAccumulator (r:1 w:1)
+	// Storage: DapiStaking ActiveProviders (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking ProviderInactiveEras (r:1 w:1)
+	// Storage: DapiStaking PendingCommissions (r:1 w:0)
+	// Storage: Dapi ProviderScore (r:1 w:0)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:0 w:1)
+	#[rustfmt::skip]
+	fn on_initialize_new_era(n: u32, ) -> Weight {
+		(9_310_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((5_874_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: DapiStaking Era (r:1 w:1)
 	#[rustfmt::skip]
 	fn set_blocks_per_era() -> Weight {
@@ -175,6 +457,41 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: DapiStaking ForceNewEra (r:0 w:1)
+	#[rustfmt::skip]
+	fn force_new_era() -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking NextEraStartBlock (r:0 w:1)
+	#[rustfmt::skip]
+	fn force_new_era_at() -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking EraStakeValuesLimit (r:1 w:1)
+	#[rustfmt::skip]
+	fn set_era_stake_values_limit() -> Weight {
+		(9_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking ActiveProviders (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:0 w:1)
+	// Storage: DapiStaking ProviderEraInfo (r:0 w:1)
+	// Storage: DapiStaking EraState (r:1 w:1)
+	#[rustfmt::skip]
+	fn register_provider_batch(n: u32, ) -> Weight {
+		(2_136_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((34_867_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: DapiStaking ProviderInfo (r:1 w:0)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
@@ -202,11 +519,14 @@ impl WeightInfo for () {
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn delegate() -> Weight {
-		(32_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
 	// Storage: DapiStaking ProviderInfo (r:1 w:0)
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
@@ -214,11 +534,28 @@ impl WeightInfo for () {
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking UnbondingInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn delegator_unstake() -> Weight {
-		(27_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:2 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking DelegationInfo (r:2 w:2)
+	// Storage: DapiStaking ProviderEraInfo (r:2 w:2)
+	// Storage: DapiStaking EraStakeValuesLimit (r:1 w:0)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:2 w:2)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn redelegate() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	// Storage: DapiStaking UnbondingInfo (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
@@ -232,23 +569,97 @@ impl WeightInfo for () {
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: DapiStaking ProviderTotalRewardsClaimed (r:1 w:1)
 	#[rustfmt::skip]
 	fn claim_provider() -> Weight {
 		(20_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
 	// Storage: DapiStaking ProviderInfo (r:1 w:0)
 	// Storage: DapiStaking Era (r:1 w:0)
-	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
 	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: DapiStaking DelegatorTotalRewardsClaimed (r:1 w:1)
+	// Storage: DapiStaking ClaimHistory (r:1 w:1)
+	// Storage: DapiStaking RewardVesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn claim_delegator() -> Weight {
-		(23_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	// Storage: DapiStaking DelegatorTotalRewardsClaimed (r:1 w:1)
+	// Storage: DapiStaking ClaimHistory (r:1 w:1)
+	// Storage: DapiStaking RewardVesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn claim_delegator_batch(n: u32, ) -> Weight {
+		(18_612_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((9_874_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:2 w:2)
+	// Storage: DapiStaking EraState (r:2 w:1)
+	// Storage: DapiStaking EraStakeValuesLimit (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: DapiStaking DelegatorTotalRewardsClaimed (r:1 w:1)
+	// Storage: DapiStaking ClaimHistory (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn compound_delegator_reward() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: DapiStaking ProviderTotalRewardsClaimed (r:0 w:1)
+	#[rustfmt::skip]
+	fn reset_provider_reward_stats() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	#[rustfmt::skip]
+	fn fix_delegation_info() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: DapiStaking ExpiryCursor (r:1 w:0)
+	// Storage: DapiStaking PruneCursor (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:2 w:1)
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking EraActiveProviderCount (r:0 w:1)
+	#[rustfmt::skip]
+	fn prune_old_era(n: u32, ) -> Weight {
+		(16_243_000 as Weight)
+			// Standard Error: 6_000
+			.saturating_add((8_127_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: DapiStaking ProviderInfo (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
@@ -263,10 +674,85 @@ impl WeightInfo for () {
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking DelegationInfo (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
 	#[rustfmt::skip]
 	fn delegator_withdraw_unregistered() -> Weight {
 		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn accelerate_provider_withdrawal() -> Weight {
+		(26_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking DelegationInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking DelegatorProviderCount (r:1 w:1)
+	// Storage: DapiStaking DelegatorCount (r:1 w:1)
+	// Storage: DapiStaking ProviderDelegators (r:1 w:1)
+	// Storage: DapiStaking DelegationRecordCount (r:1 w:1)
+	#[rustfmt::skip]
+	fn accelerate_delegator_withdrawal() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: DapiStaking PendingTransfers (r:0 w:1)
+	#[rustfmt::skip]
+	fn initiate_provider_transfer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking PendingTransfers (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	#[rustfmt::skip]
+	fn accept_provider_transfer() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking PendingTransfers (r:1 w:1)
+	#[rustfmt::skip]
+	fn cancel_provider_transfer() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking ProviderInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking PendingCommissions (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_provider_commission() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: DapiStaking RewardVesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	#[rustfmt::skip]
+	fn unlock_vested_rewards(s: u32, ) -> Weight {
+		(17_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((312_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
\ No newline at end of file
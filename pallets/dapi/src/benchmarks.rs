@@ -5,18 +5,99 @@ use crate::Pallet;
 
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_system::RawOrigin;
+use pallet_dapi_staking::traits::MAX_PROVIDER_SCORE;
 use sp_runtime::traits::Bounded;
+use sp_std::vec;
 
 const SEED: u32 = 9000;
 
+/// Massbit Id with the UUID format, derived from `index`.
+fn massbit_id<T: Config>(index: u32) -> Result<T::MassbitId, &'static str> {
+	const HEX: &[u8; 16] = b"0123456789abcdef";
+	let mut uuid = *b"00000000-0000-0000-0000-000000000000";
+	for (i, byte) in index.to_be_bytes().iter().enumerate() {
+		uuid[2 * i] = HEX[(byte >> 4) as usize];
+		uuid[2 * i + 1] = HEX[(byte & 0x0f) as usize];
+	}
+	T::MassbitId::decode(&mut &uuid[..]).map_err(|_| "massbit id can't be created")
+}
+
 fn initialize<T: Config>() {
 	// Remove everything from storage
 	Projects::<T>::remove_all(None);
 	Providers::<T>::remove_all(None);
+	ProviderChains::<T>::remove_all(None);
+	IndexedProviderStake::<T>::remove_all(None);
+	ChainStake::<T>::remove_all(None);
+	MostStakedChain::<T>::kill();
+	ProviderScore::<T>::remove_all(None);
+	ProviderRatings::<T>::remove_all(None);
+	ProviderRatingAggregate::<T>::remove_all(None);
+	RatingLastEra::<T>::remove_all(None);
+	PendingProjectTransfers::<T>::remove_all(None);
+	PendingProviderRegistrations::<T>::remove_all(None);
 	Regulators::<T>::kill();
-	ChainIds::<T>::kill();
+	MonthlyResetInterval::<T>::kill();
+	ChainIds::<T>::remove_all(None);
 
-	Pallet::<T>::add_chain_id(RawOrigin::Root.into(), "eth.mainnet".into()).unwrap();
+	Pallet::<T>::add_chain_id(RawOrigin::Root.into(), "eth.mainnet".into(), Default::default())
+		.unwrap();
+}
+
+/// Build a distinct chain Id from the given index.
+fn chain_id(index: u32) -> Vec<u8> {
+	[&b"chain."[..], &index.to_le_bytes()[..]].concat()
+}
+
+/// Build chain metadata of the max length.
+fn chain_metadata<T: Config>() -> ChainMetadata<Vec<u8>, Vec<u8>> {
+	ChainMetadata {
+		display_name: vec![b'n'; T::MaxChainMetadataLength::get() as usize],
+		explorer_url: vec![b'e'; T::MaxUrlLength::get() as usize],
+		rpc_endpoint: vec![b'r'; T::MaxUrlLength::get() as usize],
+	}
+}
+
+/// Reserve the minimum regulator bond for `regulator`.
+fn bond_regulator<T: Config>(regulator: &T::AccountId) -> Result<(), &'static str> {
+	let _ = T::Currency::make_free_balance_be(regulator, BalanceOf::<T>::max_value());
+	Pallet::<T>::bond_as_regulator(
+		RawOrigin::Signed(regulator.clone()).into(),
+		T::MinRegulatorBond::get(),
+	)?;
+	Ok(())
+}
+
+/// Register a provider on `eth.mainnet` owned by the returned account.
+fn register_provider<T: Config>(provider_id: &T::MassbitId) -> Result<T::AccountId, &'static str> {
+	let regulator: T::AccountId = account("regulator", 10000, SEED);
+	bond_regulator::<T>(&regulator)?;
+	Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
+	let owner: T::AccountId = account("owner", 20000, SEED);
+	Pallet::<T>::register_provider(
+		RawOrigin::Signed(regulator).into(),
+		provider_id.clone(),
+		ProviderType::Gateway,
+		owner.clone(),
+		"eth.mainnet".into(),
+	)?;
+	Ok(owner)
+}
+
+/// Register a project on `eth.mainnet` and return its Id.
+fn create_project<T: Config>() -> Result<T::MassbitId, &'static str> {
+	let consumer: T::AccountId = account("consumer", 10000, SEED);
+	let _ = T::Currency::make_free_balance_be(&consumer, BalanceOf::<T>::max_value());
+	let project_id = T::MassbitId::default();
+	let amount = BalanceOf::<T>::max_value() / 3u32.into();
+	Pallet::<T>::register_project(
+		RawOrigin::Signed(consumer).into(),
+		project_id.clone(),
+		vec!["eth.mainnet".into()],
+		amount,
+		false,
+	)?;
+	Ok(project_id)
 }
 
 /// Assert that the last event equals the provided one.
@@ -33,11 +114,12 @@ benchmarks! {
 		let project_id = T::MassbitId::default();
 		let amount = BalanceOf::<T>::max_value() / 2u32.into();
 		let quota = Pallet::<T>::calculate_quota(amount);
-		let chain_id: Vec<u8> = "eth.mainnet".into();
+		let chain_ids: Vec<Vec<u8>> = vec!["eth.mainnet".into()];
+		MonthlyResetInterval::<T>::put(T::BlockNumber::from(100u32));
 
-	}: _(RawOrigin::Signed(consumer.clone()), project_id.clone(), chain_id.clone(), amount.clone())
+	}: _(RawOrigin::Signed(consumer.clone()), project_id.clone(), chain_ids.clone(), amount.clone(), true)
 	verify {
-		assert_last_event::<T>(Event::<T>::ProjectRegistered{project_id, consumer, chain_id, quota}.into());
+		assert_last_event::<T>(Event::<T>::ProjectRegistered{project_id, consumer, chain_ids, quota, status: ProjectStatus::Active}.into());
 	}
 
 	deposit_project {
@@ -47,14 +129,108 @@ benchmarks! {
 		let _ = T::Currency::make_free_balance_be(&consumer, BalanceOf::<T>::max_value());
 		let project_id = T::MassbitId::default();
 		let amount = BalanceOf::<T>::max_value() / 3u32.into();
-		let chain_id = "eth.mainnet".into();
-		Pallet::<T>::register_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), chain_id, amount.clone())?;
+		let chain_ids = vec!["eth.mainnet".into()];
+		Pallet::<T>::register_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), chain_ids, amount.clone(), false)?;
 
 	}: _(RawOrigin::Signed(consumer.clone()), project_id.clone(), amount.clone())
 
+	refund_project {
+		initialize::<T>();
+
+		let consumer: T::AccountId = account("consumer", 10000, SEED);
+		let _ = T::Currency::make_free_balance_be(&consumer, BalanceOf::<T>::max_value());
+		let project_id = T::MassbitId::default();
+		let amount = BalanceOf::<T>::max_value() / 3u32.into();
+		Pallet::<T>::register_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), vec!["eth.mainnet".into()], amount, false)?;
+		let refund = Pallet::<T>::calculate_deposit(Pallet::<T>::calculate_quota(amount) / 2);
+		let remaining_quota = Pallet::<T>::calculate_quota(amount) - Pallet::<T>::calculate_quota(refund);
+
+	}: _(RawOrigin::Signed(consumer.clone()), project_id.clone(), refund)
+	verify {
+		assert_last_event::<T>(Event::<T>::ProjectRefunded{project_id, amount: refund, remaining_quota}.into());
+	}
+
+	transfer_project {
+		initialize::<T>();
+
+		let consumer: T::AccountId = account("consumer", 10000, SEED);
+		let _ = T::Currency::make_free_balance_be(&consumer, BalanceOf::<T>::max_value());
+		let project_id = T::MassbitId::default();
+		let amount = BalanceOf::<T>::max_value() / 3u32.into();
+		Pallet::<T>::register_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), vec!["eth.mainnet".into()], amount, false)?;
+		let new_owner: T::AccountId = account("new_owner", 10001, SEED);
+
+	}: _(RawOrigin::Signed(consumer.clone()), project_id.clone(), new_owner.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProjectTransferInitiated{project_id, old_owner: consumer, new_owner}.into());
+	}
+
+	accept_project_transfer {
+		initialize::<T>();
+
+		let consumer: T::AccountId = account("consumer", 10000, SEED);
+		let _ = T::Currency::make_free_balance_be(&consumer, BalanceOf::<T>::max_value());
+		let project_id = T::MassbitId::default();
+		let amount = BalanceOf::<T>::max_value() / 3u32.into();
+		Pallet::<T>::register_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), vec!["eth.mainnet".into()], amount, false)?;
+		let new_owner: T::AccountId = account("new_owner", 10001, SEED);
+		Pallet::<T>::transfer_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), new_owner.clone())?;
+
+	}: _(RawOrigin::Signed(new_owner.clone()), project_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProjectTransferred{project_id, old_owner: consumer, new_owner}.into());
+	}
+
+	register_providers_batch {
+		let n in 1 .. T::MaxBatchProviders::get();
+		initialize::<T>();
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
+		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
+
+		let mut registrations = Vec::new();
+		for i in 0..n {
+			registrations.push(ProviderRegistration {
+				provider_id: massbit_id::<T>(i)?,
+				provider_type: ProviderType::Gateway,
+				owner: account("owner", i, SEED),
+				chain_id: "eth.mainnet".into(),
+			});
+		}
+		let registrations: BoundedVec<_, T::MaxBatchProviders> =
+			registrations.try_into().map_err(|_| "too many registrations")?;
+	}: _(RawOrigin::Signed(regulator), registrations)
+	verify {
+		assert_eq!(Providers::<T>::iter().count() as u32, n);
+	}
+
+	batch_register_providers {
+		let n in 1 .. T::MaxBatchProviders::get();
+		initialize::<T>();
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
+		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
+
+		let mut registrations = Vec::new();
+		for i in 0..n {
+			registrations.push(ProviderRegistration {
+				provider_id: massbit_id::<T>(i)?,
+				provider_type: ProviderType::Gateway,
+				owner: account("owner", i, SEED),
+				chain_id: "eth.mainnet".into(),
+			});
+		}
+		let registrations: BoundedVec<_, T::MaxBatchProviders> =
+			registrations.try_into().map_err(|_| "too many registrations")?;
+	}: _(RawOrigin::Signed(regulator), registrations)
+	verify {
+		assert_eq!(Providers::<T>::iter().count() as u32, n);
+	}
+
 	deposit_provider {
 		initialize::<T>();
 		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
 		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
 
 		let owner: T::AccountId = account("owner", 20000, SEED);
@@ -71,9 +247,11 @@ benchmarks! {
 			chain_id.clone(),
 		)?;
 
-		let deposit_amount = BalanceOf::<T>::max_value() / 2u32.into();
+		let deposit_amount = T::GatewayMinStake::get();
+		Pallet::<T>::set_whitelist_mode(RawOrigin::Root.into(), true)?;
+		Pallet::<T>::whitelist_provider(RawOrigin::Root.into(), provider_id.clone())?;
 
-	}: _(RawOrigin::Signed(owner.clone()), provider_id.clone(), deposit_amount)
+	}: _(RawOrigin::Signed(owner.clone()), provider_id.clone(), deposit_amount, Perbill::one())
 	verify {
 		assert_last_event::<T>(Event::<T>::ProviderActivated{provider_id, provider_type}.into());
 	}
@@ -81,6 +259,7 @@ benchmarks! {
 	unregister_provider {
 		initialize::<T>();
 		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
 		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
 
 		let owner: T::AccountId = account("owner", 20000, SEED);
@@ -97,33 +276,341 @@ benchmarks! {
 			chain_id.clone(),
 		)?;
 
-		let deposit_amount = BalanceOf::<T>::max_value() / 2u32.into();
-		Pallet::<T>::deposit_provider(RawOrigin::Signed(owner.clone()).into(), provider_id.clone(), deposit_amount)?;
+		let deposit_amount = T::GatewayMinStake::get();
+		Pallet::<T>::deposit_provider(RawOrigin::Signed(owner.clone()).into(), provider_id.clone(), deposit_amount, Perbill::one())?;
 
 	}: _(RawOrigin::Signed(owner.clone()), provider_id.clone())
 	verify {
 		assert_last_event::<T>(Event::<T>::ProviderDeactivated{provider_id, provider_type, reason: ProviderDeactivateReason::UnRegistered}.into());
 	}
 
+	add_provider_chain {
+		initialize::<T>();
+		let provider_id = T::MassbitId::default();
+		let owner = register_provider::<T>(&provider_id)?;
+		let chain_id: Vec<u8> = "dot.mainnet".into();
+		Pallet::<T>::add_chain_id(RawOrigin::Root.into(), chain_id.clone(), Default::default())?;
+
+	}: _(RawOrigin::Signed(owner), provider_id.clone(), chain_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderChainAdded{provider_id, chain_id}.into());
+	}
+
+	remove_provider_chain {
+		initialize::<T>();
+		let provider_id = T::MassbitId::default();
+		let owner = register_provider::<T>(&provider_id)?;
+		let chain_id: Vec<u8> = "dot.mainnet".into();
+		Pallet::<T>::add_chain_id(RawOrigin::Root.into(), chain_id.clone(), Default::default())?;
+		Pallet::<T>::add_provider_chain(RawOrigin::Signed(owner.clone()).into(), provider_id.clone(), chain_id.clone())?;
+
+	}: _(RawOrigin::Signed(owner), provider_id.clone(), chain_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderChainRemoved{provider_id, chain_id}.into());
+	}
+
+	update_provider_metadata {
+		initialize::<T>();
+		let provider_id = T::MassbitId::default();
+		let owner = register_provider::<T>(&provider_id)?;
+		let metadata = ProviderMetadataExtra {
+			name: vec![b'n'; T::MaxNameLength::get() as usize],
+			endpoint: vec![b'e'; T::MaxEndpointLength::get() as usize],
+			description: vec![b'd'; T::MaxDescriptionLength::get() as usize],
+		};
+
+	}: _(RawOrigin::Signed(owner), provider_id.clone(), metadata)
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderMetadataUpdated{provider_id}.into());
+	}
+
+	submit_provider_rating {
+		initialize::<T>();
+		let provider_id = T::MassbitId::default();
+		register_provider::<T>(&provider_id)?;
+		let consumer: T::AccountId = account("consumer", 10000, SEED);
+		let _ = T::Currency::make_free_balance_be(&consumer, BalanceOf::<T>::max_value());
+		let amount = BalanceOf::<T>::max_value() / 3u32.into();
+		let project_id = T::MassbitId::default();
+		Pallet::<T>::register_project(RawOrigin::Signed(consumer.clone()).into(), project_id.clone(), vec!["eth.mainnet".into()], amount, false)?;
+		// Replacing a previous rating is the heaviest path.
+		ProviderRatings::<T>::insert(&provider_id, &consumer, 5);
+		ProviderRatingAggregate::<T>::insert(&provider_id, (5, 1));
+		let rating = MAX_PROVIDER_RATING;
+
+	}: _(RawOrigin::Signed(consumer.clone()), provider_id.clone(), project_id, rating)
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderRated{provider_id, by: consumer, rating}.into());
+	}
+
+	update_provider_score {
+		initialize::<T>();
+		let provider_id = T::MassbitId::default();
+		register_provider::<T>(&provider_id)?;
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		// Replacing a previous score is the heaviest path.
+		ProviderScore::<T>::insert(&provider_id, 0);
+
+	}: _(RawOrigin::Signed(regulator), provider_id.clone(), MAX_PROVIDER_SCORE)
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderScoreUpdated{provider_id, old_score: Some(0), new_score: MAX_PROVIDER_SCORE}.into());
+	}
+
+	record_provider_uptime {
+		initialize::<T>();
+		let provider_id = T::MassbitId::default();
+		let owner = register_provider::<T>(&provider_id)?;
+		let _ = T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value());
+		Pallet::<T>::deposit_provider(RawOrigin::Signed(owner).into(), provider_id.clone(), T::GatewayMinStake::get(), Perbill::one())?;
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		// A failed check ending the evaluation period deactivates the provider, the heaviest path.
+		let total_checks = T::SlaEvaluationPeriod::get().max(1);
+		ProviderUptimeHistory::<T>::insert(&provider_id, UptimeRecord { total_checks: total_checks - 1, successful_checks: 0 });
+
+	}: _(RawOrigin::Signed(regulator), provider_id.clone(), false)
+	verify {
+		let reason = ProviderDeactivateReason::SlaViolation { total_checks, successful_checks: 0 };
+		assert_last_event::<T>(Event::<T>::ProviderDeactivated{provider_id, provider_type: ProviderType::Gateway, reason}.into());
+	}
+
+	suspend_project {
+		initialize::<T>();
+		let project_id = create_project::<T>()?;
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
+		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
+		let reason = vec![b'r'; T::MaxReasonLength::get() as usize];
+		let bounded_reason: BoundedVec<u8, T::MaxReasonLength> = reason.clone().try_into().unwrap();
+
+	}: _(RawOrigin::Signed(regulator), project_id.clone(), bounded_reason)
+	verify {
+		assert_last_event::<T>(Event::<T>::ProjectSuspended{project_id, reason}.into());
+	}
+
+	unsuspend_project {
+		initialize::<T>();
+		let project_id = create_project::<T>()?;
+		Projects::<T>::mutate(&project_id, |project| {
+			if let Some(project) = project {
+				project.status = ProjectStatus::Suspended;
+			}
+		});
+
+	}: _(RawOrigin::Root, project_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProjectUnsuspended{project_id}.into());
+	}
+
+	set_monthly_reset_interval {
+		let interval = T::BlockNumber::from(100u32);
+	}: _(RawOrigin::Root, interval)
+	verify {
+		assert_eq!(Pallet::<T>::monthly_reset_interval(), Some(interval));
+	}
+
+	set_quota_price {
+		let new_price = T::MaxQuotaPrice::get();
+	}: _(RawOrigin::Root, new_price)
+	verify {
+		assert_eq!(Pallet::<T>::quota_price(), new_price);
+	}
+
+	set_whitelist_mode {
+	}: _(RawOrigin::Root, true)
+	verify {
+		assert!(Pallet::<T>::whitelist_mode());
+	}
+
+	pause_pallet {
+	}: _(RawOrigin::Root)
+	verify {
+		assert!(Pallet::<T>::pallet_paused());
+	}
+
+	unpause_pallet {
+		PalletPaused::<T>::put(true);
+	}: _(RawOrigin::Root)
+	verify {
+		assert!(!Pallet::<T>::pallet_paused());
+	}
+
+	propose_provider_registration {
+		initialize::<T>();
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
+		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
+
+		let owner: T::AccountId = account("owner", 20000, SEED);
+		let provider_id = T::MassbitId::default();
+	}: _(RawOrigin::Signed(regulator.clone()), provider_id.clone(), ProviderType::Gateway, owner.clone(), "eth.mainnet".into())
+	verify {
+		let expiry = frame_system::Pallet::<T>::block_number() + T::RegistrationTimeout::get();
+		assert_last_event::<T>(Event::<T>::ProviderRegistrationProposed{provider_id, owner, expiry}.into());
+	}
+
+	accept_provider_registration {
+		initialize::<T>();
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
+		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
+
+		let owner: T::AccountId = account("owner", 20000, SEED);
+		let provider_id = T::MassbitId::default();
+		let chain_id: Vec<u8> = "eth.mainnet".into();
+		Pallet::<T>::propose_provider_registration(
+			RawOrigin::Signed(regulator.clone()).into(),
+			provider_id.clone(),
+			ProviderType::Gateway,
+			owner.clone(),
+			chain_id.clone(),
+		)?;
+	}: _(RawOrigin::Signed(owner.clone()), provider_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderRegistered{provider_id, provider_type: ProviderType::Gateway, owner, chain_id}.into());
+	}
+
+	whitelist_provider {
+		let provider_id = T::MassbitId::default();
+	}: _(RawOrigin::Root, provider_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderWhitelisted{provider_id}.into());
+	}
+
+	remove_from_whitelist {
+		let provider_id = T::MassbitId::default();
+		Pallet::<T>::whitelist_provider(RawOrigin::Root.into(), provider_id.clone())?;
+	}: _(RawOrigin::Root, provider_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ProviderRemovedFromWhitelist{provider_id}.into());
+	}
+
 	add_chain_id {
-		ChainIds::<T>::kill();
-	}: _(RawOrigin::Root, "eth.mainnet".into())
+		ChainIds::<T>::remove_all(None);
+	}: _(RawOrigin::Root, "eth.mainnet".into(), chain_metadata::<T>())
 
 	remove_chain_id {
-		ChainIds::<T>::kill();
-		Pallet::<T>::add_chain_id(RawOrigin::Root.into(), "eth.mainnet".into())?;
+		ChainIds::<T>::remove_all(None);
+		Pallet::<T>::add_chain_id(RawOrigin::Root.into(), "eth.mainnet".into(), Default::default())?;
 	}: _(RawOrigin::Root, "eth.mainnet".into())
 
+	update_chain_metadata {
+		ChainIds::<T>::remove_all(None);
+		Pallet::<T>::add_chain_id(RawOrigin::Root.into(), "eth.mainnet".into(), Default::default())?;
+	}: _(RawOrigin::Root, "eth.mainnet".into(), chain_metadata::<T>())
+	verify {
+		assert_last_event::<T>(Event::<T>::ChainMetadataUpdated{chain_id: "eth.mainnet".into()}.into());
+	}
+
+	add_chain_ids_batch {
+		let n in 1 .. T::MaxBatchChainIds::get();
+		ChainIds::<T>::remove_all(None);
+		let chain_ids: BoundedVec<_, T::MaxBatchChainIds> =
+			(0..n).map(chain_id).collect::<Vec<_>>().try_into().unwrap();
+	}: _(RawOrigin::Root, chain_ids)
+	verify {
+		assert_eq!(ChainIds::<T>::iter().count() as u32, n);
+	}
+
+	remove_chain_ids_batch {
+		let n in 1 .. T::MaxBatchChainIds::get();
+		ChainIds::<T>::remove_all(None);
+		let chain_ids: BoundedVec<_, T::MaxBatchChainIds> =
+			(0..n).map(chain_id).collect::<Vec<_>>().try_into().unwrap();
+		Pallet::<T>::add_chain_ids_batch(RawOrigin::Root.into(), chain_ids.clone())?;
+	}: _(RawOrigin::Root, chain_ids)
+	verify {
+		assert_eq!(ChainIds::<T>::iter().count(), 0);
+	}
+
 	add_regulator {
 		initialize::<T>();
 		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
 	}: _(RawOrigin::Root, regulator)
 
 	remove_regulator {
 		initialize::<T>();
 		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
 		Pallet::<T>::add_regulator(RawOrigin::Root.into(), regulator.clone())?;
-	}: _(RawOrigin::Root, regulator)
+		let log = vec![
+			(T::MassbitId::default(), T::BlockNumber::default());
+			T::MaxLogEntries::get() as usize
+		];
+		RegulatorActionLog::<T>::insert(&regulator, BoundedVec::try_from(log).unwrap());
+	}: _(RawOrigin::Root, regulator.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::RegulatorActionsRetracted {
+			who: regulator,
+			retracted_count: T::MaxLogEntries::get(),
+		}.into());
+	}
+
+	batch_add_regulators {
+		let n in 1 .. T::MaxRegulatorsPerBatch::get();
+		initialize::<T>();
+		let accounts: Vec<T::AccountId> = (0..n).map(|i| account("regulator", i, SEED)).collect();
+		for regulator in accounts.iter() {
+			bond_regulator::<T>(regulator)?;
+		}
+		let bounded_accounts: BoundedVec<_, T::MaxRegulatorsPerBatch> =
+			accounts.clone().try_into().unwrap();
+	}: _(RawOrigin::Root, bounded_accounts)
+	verify {
+		assert_last_event::<T>(Event::<T>::RegulatorsUpdated {
+			added: accounts,
+			removed: Vec::new(),
+		}.into());
+	}
+
+	batch_remove_regulators {
+		let n in 1 .. T::MaxRegulatorsPerBatch::get();
+		initialize::<T>();
+		let accounts: Vec<T::AccountId> = (0..n).map(|i| account("regulator", i, SEED)).collect();
+		for regulator in accounts.iter() {
+			bond_regulator::<T>(regulator)?;
+		}
+		let bounded_accounts: BoundedVec<_, T::MaxRegulatorsPerBatch> =
+			accounts.clone().try_into().unwrap();
+		Pallet::<T>::batch_add_regulators(RawOrigin::Root.into(), bounded_accounts.clone())?;
+	}: _(RawOrigin::Root, bounded_accounts)
+	verify {
+		assert_last_event::<T>(Event::<T>::RegulatorsUpdated {
+			added: Vec::new(),
+			removed: accounts,
+		}.into());
+	}
+
+	bond_as_regulator {
+		initialize::<T>();
+		let who: T::AccountId = account("regulator", 10000, SEED);
+		let _ = T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value());
+		let amount = T::MinRegulatorBond::get();
+	}: _(RawOrigin::Signed(who.clone()), amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::RegulatorBonded { who, amount }.into());
+	}
+
+	unbond_regulator {
+		initialize::<T>();
+		let who: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&who)?;
+	}: _(RawOrigin::Signed(who.clone()))
+	verify {
+		assert_last_event::<T>(Event::<T>::RegulatorUnbonded {
+			who,
+			amount: T::MinRegulatorBond::get(),
+		}.into());
+	}
+
+	slash_regulator {
+		initialize::<T>();
+		let regulator: T::AccountId = account("regulator", 10000, SEED);
+		bond_regulator::<T>(&regulator)?;
+	}: _(RawOrigin::Root, regulator.clone(), Perbill::one())
+	verify {
+		assert!(RegulatorBond::<T>::get(&regulator).is_zero());
+	}
 }
 
 #[cfg(test)]
@@ -14,13 +14,27 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use common::ValidateId;
 use frame_support::{
 	pallet_prelude::{DispatchResultWithPostInfo, *},
-	traits::{Currency, ExistenceRequirement, IsSubType, OnUnbalanced, WithdrawReasons},
+	traits::{
+		Currency, ExistenceRequirement, Imbalance, IsSubType, OnUnbalanced, ReservableCurrency,
+		WithdrawReasons,
+	},
+	PalletId,
+};
+use sp_runtime::{
+	traits::{
+		AccountIdConversion, DispatchInfoOf, Hash, MaybeSerializeDeserialize, SaturatedConversion,
+		Scale, SignedExtension, Zero,
+	},
+	Perbill,
 };
-use sp_runtime::traits::{DispatchInfoOf, Scale, SignedExtension};
 use sp_std::{collections::btree_set::BTreeSet, fmt::Debug, prelude::*};
 
+pub mod migrations;
+pub mod runtime_api;
+pub mod traits;
 pub mod types;
 pub mod weights;
 
@@ -41,15 +55,49 @@ type BalanceOf<T> =
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use crate::traits::QuotaDeduction;
 	use frame_system::pallet_prelude::*;
-	use pallet_dapi_staking::traits::DapiStakingRegistration;
+	use pallet_dapi_staking::traits::{DapiStakingRegistration, MAX_PROVIDER_SCORE};
 
 	type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 
 	/// Blockchain identifier, e.g `eth.mainnet`
 	type ChainId<T> = BoundedVec<u8, <T as Config>::MaxChainIdLength>;
 
+	/// Chains covered by a single project.
+	type ProjectChainIds<T> = BoundedVec<ChainId<T>, <T as Config>::MaxChainsPerProject>;
+
+	/// Provider metadata as stored on-chain.
+	pub type ProviderMetadataOf<T> = ProviderMetadataExtra<
+		BoundedVec<u8, <T as Config>::MaxNameLength>,
+		BoundedVec<u8, <T as Config>::MaxEndpointLength>,
+		BoundedVec<u8, <T as Config>::MaxDescriptionLength>,
+	>;
+
+	/// Chain metadata as stored on-chain.
+	pub type ChainMetadataOf<T> = ChainMetadata<
+		BoundedVec<u8, <T as Config>::MaxChainMetadataLength>,
+		BoundedVec<u8, <T as Config>::MaxUrlLength>,
+	>;
+
+	/// Pending regulator proposal as stored on-chain.
+	pub type RegulatorProposalOf<T> = ProposalInfo<
+		BoundedVec<AccountIdOf<T>, <T as Config>::MaxRegulatorVoters>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Price of one request of quota until root sets it.
+	pub const QUOTA_PRICE: u128 = 1_000_000_000_000_000;
+
+	/// Highest rating a consumer can give to a provider.
+	pub const MAX_PROVIDER_RATING: u8 = 10;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -59,7 +107,7 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The currency mechanism.
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// dAPI staking helper.
 		type DapiStaking: DapiStakingRegistration<Self::AccountId, Self::MassbitId, BalanceOf<Self>>;
@@ -70,8 +118,129 @@ pub mod pallet {
 		/// For constraining the maximum length of a Chain Id.
 		type MaxChainIdLength: Get<u32>;
 
+		/// Max number of recent actions kept in the log of each regulator.
+		#[pallet::constant]
+		type MaxLogEntries: Get<u32>;
+
+		/// Max number of additional chains a provider can serve.
+		#[pallet::constant]
+		type MaxSupportedChains: Get<u32>;
+
+		/// Max number of chains a project can cover.
+		#[pallet::constant]
+		type MaxChainsPerProject: Get<u32>;
+
+		/// Max number of chain Ids which can be added or removed in a single batch.
+		#[pallet::constant]
+		type MaxBatchChainIds: Get<u32>;
+
+		/// Max number of providers which can be registered in a single batch.
+		#[pallet::constant]
+		type MaxBatchProviders: Get<u32>;
+
+		/// Number of eras a consumer must wait before rating the same provider again.
+		#[pallet::constant]
+		type RatingCooldownEras: Get<u32>;
+
+		/// Max length of a provider name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// Max length of a provider endpoint.
+		#[pallet::constant]
+		type MaxEndpointLength: Get<u32>;
+
+		/// Max length of a provider description.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+
+		/// Max length of the display name of a chain.
+		#[pallet::constant]
+		type MaxChainMetadataLength: Get<u32>;
+
+		/// Max length of the explorer URL and RPC endpoint of a chain.
+		#[pallet::constant]
+		type MaxUrlLength: Get<u32>;
+
+		/// Max length of the reason a project is suspended for.
+		#[pallet::constant]
+		type MaxReasonLength: Get<u32>;
+
+		/// Number of blocks the new owner of a project has to accept its transfer.
+		#[pallet::constant]
+		type TransferWindowBlocks: Get<Self::BlockNumber>;
+
+		/// Number of blocks the owner of a proposed provider has to accept its registration.
+		#[pallet::constant]
+		type RegistrationTimeout: Get<Self::BlockNumber>;
+
+		/// Current price of one request of quota.
+		type QuotaOracle: Get<BalanceOf<Self>>;
+
+		/// Lowest price of a request root can set.
+		#[pallet::constant]
+		type MinQuotaPrice: Get<BalanceOf<Self>>;
+
+		/// Highest price of a request root can set.
+		#[pallet::constant]
+		type MaxQuotaPrice: Get<BalanceOf<Self>>;
+
+		/// Number of blocks without a deposit after which a used up project is removed. Projects
+		/// with quota left are removed only if they weren't used over the period either.
+		#[pallet::constant]
+		type ProjectDepositPeriod: Get<Self::BlockNumber>;
+
+		/// Share of the total stake which has to approve a change of the regulator set.
+		#[pallet::constant]
+		type RegulatorApprovalThreshold: Get<Perbill>;
+
+		/// Share of the quota a project has to use up before `ProjectQuotaLow` is emitted.
+		#[pallet::constant]
+		type QuotaLowThreshold: Get<Perbill>;
+
+		/// Number of blocks a regulator proposal can be voted on.
+		#[pallet::constant]
+		type RegulatorProposalDuration: Get<Self::BlockNumber>;
+
+		/// Max number of stakers voting on a single regulator proposal.
+		#[pallet::constant]
+		type MaxRegulatorVoters: Get<u32>;
+
+		/// Max number of regulators which can be added or removed in a single batch.
+		#[pallet::constant]
+		type MaxRegulatorsPerBatch: Get<u32>;
+
+		/// Share of the bond slashed from a provider deactivated for bad performance.
+		#[pallet::constant]
+		type OffenceSlashRatio: Get<Perbill>;
+
+		/// Minimum deposit required to activate a gateway.
+		#[pallet::constant]
+		type GatewayMinStake: Get<BalanceOf<Self>>;
+
+		/// Minimum deposit required to activate a node.
+		#[pallet::constant]
+		type NodeMinStake: Get<BalanceOf<Self>>;
+
+		/// Number of uptime checks after which a provider is held to its SLA commitment.
+		#[pallet::constant]
+		type SlaEvaluationPeriod: Get<u32>;
+
+		/// Minimum bond an account must reserve before it can be made a regulator.
+		#[pallet::constant]
+		type MinRegulatorBond: Get<BalanceOf<Self>>;
+
+		/// Handler for the bond slashed from regulators.
+		type RegulatorSlashDestination: OnUnbalanced<
+			<Self::Currency as Currency<Self::AccountId>>::NegativeImbalance,
+		>;
+
 		/// The id type of Massbit provider or project.
-		type MassbitId: Parameter + Member + Default;
+		type MassbitId: Parameter + Member + Default + MaybeSerializeDeserialize + ValidateId;
+
+		/// Id from which the escrow accounts of projects are derived.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
 
 		/// Handle project payment as imbalance.
 		type OnProjectPayment: OnUnbalanced<
@@ -93,6 +262,34 @@ pub mod pallet {
 		PermissionDenied,
 		InvalidProviderStatus,
 		InvalidChainId,
+		InsufficientQuota,
+		TooManyProviderChains,
+		TooManyProjectChains,
+		SubscriptionUnavailable,
+		InvalidResetInterval,
+		InvalidRating,
+		NotEligibleRater,
+		RatingCooldown,
+		MetadataTooLong,
+		NoPendingTransfer,
+		TransferExpired,
+		InvalidScore,
+		NoStake,
+		AlreadyVoted,
+		TooManyVoters,
+		ProposalMismatch,
+		ProjectSuspended,
+		ProjectNotSuspended,
+		InactiveProject,
+		InsufficientRegulatorBond,
+		RegulatorNotBonded,
+		StillRegulator,
+		InvalidQuotaPrice,
+		ProviderNotWhitelisted,
+		PalletPaused,
+		NoPendingRegistration,
+		RegistrationExpired,
+		InvalidMassbitId,
 	}
 
 	#[pallet::event]
@@ -101,8 +298,9 @@ pub mod pallet {
 		ProjectRegistered {
 			project_id: T::MassbitId,
 			consumer: T::AccountId,
-			chain_id: Vec<u8>,
+			chain_ids: Vec<Vec<u8>>,
 			quota: u128,
+			status: ProjectStatus,
 		},
 		ProjectDeposited {
 			project_id: T::MassbitId,
@@ -110,14 +308,30 @@ pub mod pallet {
 		},
 		ProjectUsageUpdated {
 			project_id: T::MassbitId,
+			chain_id: Vec<u8>,
 			usage: u128,
 		},
+		ProjectQuotaLow {
+			project_id: T::MassbitId,
+			remaining_quota: u128,
+		},
+		ProjectQuotaExhausted {
+			project_id: T::MassbitId,
+		},
+		ProjectQuotaDeducted {
+			project_id: T::MassbitId,
+			quota: u128,
+		},
 		ProviderRegistered {
 			provider_id: T::MassbitId,
 			provider_type: ProviderType,
 			owner: T::AccountId,
 			chain_id: Vec<u8>,
 		},
+		ProviderRegistrationSkipped {
+			provider_id: T::MassbitId,
+			reason: DispatchError,
+		},
 		ProviderActivated {
 			provider_id: T::MassbitId,
 			provider_type: ProviderType,
@@ -133,42 +347,303 @@ pub mod pallet {
 		ChainIdRemoved {
 			chain_id: Vec<u8>,
 		},
+		ChainMetadataUpdated {
+			chain_id: Vec<u8>,
+		},
+		ChainIdsBatchAdded {
+			added: Vec<Vec<u8>>,
+			skipped: Vec<Vec<u8>>,
+		},
+		ChainIdsBatchRemoved {
+			removed: Vec<Vec<u8>>,
+			skipped: Vec<Vec<u8>>,
+		},
 		RegulatorAdded {
 			account_id: T::AccountId,
 		},
 		RegulatorRemoved {
 			account_id: T::AccountId,
 		},
+		/// A batch of regulators has been added or removed.
+		RegulatorsUpdated {
+			added: Vec<T::AccountId>,
+			removed: Vec<T::AccountId>,
+		},
+		/// Recorded actions of a removed regulator have been cleared.
+		RegulatorActionsRetracted {
+			who: T::AccountId,
+			retracted_count: u32,
+		},
+		RegulatorBonded {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		RegulatorUnbonded {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		RegulatorSlashed {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		ProviderChainAdded {
+			provider_id: T::MassbitId,
+			chain_id: Vec<u8>,
+		},
+		ProviderChainRemoved {
+			provider_id: T::MassbitId,
+			chain_id: Vec<u8>,
+		},
+		MonthlyResetIntervalSet {
+			interval: T::BlockNumber,
+		},
+		QuotaPriceUpdated {
+			old_price: BalanceOf<T>,
+			new_price: BalanceOf<T>,
+		},
+		WhitelistModeChanged {
+			enabled: bool,
+		},
+		ProviderWhitelisted {
+			provider_id: T::MassbitId,
+		},
+		ProviderRemovedFromWhitelist {
+			provider_id: T::MassbitId,
+		},
+		PalletPauseChanged {
+			paused: bool,
+		},
+		ProviderRegistrationProposed {
+			provider_id: T::MassbitId,
+			owner: T::AccountId,
+			expiry: T::BlockNumber,
+		},
+		ProjectQuotaRenewed {
+			project_id: T::MassbitId,
+			new_renewal_block: T::BlockNumber,
+		},
+		ProjectRenewalFailed {
+			project_id: T::MassbitId,
+		},
+		/// An inactive project has been removed and its escrow refunded to the consumer.
+		ProjectExpired {
+			project_id: T::MassbitId,
+		},
+		ProviderRated {
+			provider_id: T::MassbitId,
+			by: T::AccountId,
+			rating: u8,
+		},
+		ProviderMetadataUpdated {
+			provider_id: T::MassbitId,
+		},
+		ProjectTransferInitiated {
+			project_id: T::MassbitId,
+			old_owner: T::AccountId,
+			new_owner: T::AccountId,
+		},
+		ProjectTransferred {
+			project_id: T::MassbitId,
+			old_owner: T::AccountId,
+			new_owner: T::AccountId,
+		},
+		ProjectSuspended {
+			project_id: T::MassbitId,
+			reason: Vec<u8>,
+		},
+		ProjectUnsuspended {
+			project_id: T::MassbitId,
+		},
+		ProviderScoreUpdated {
+			provider_id: T::MassbitId,
+			old_score: Option<u32>,
+			new_score: u32,
+		},
+		/// A provider passed less uptime checks than it committed to.
+		SlaViolation {
+			provider_id: T::MassbitId,
+		},
+		ProjectRefunded {
+			project_id: T::MassbitId,
+			amount: BalanceOf<T>,
+			remaining_quota: u128,
+		},
+		RegulatorProposalVoted {
+			candidate: T::AccountId,
+			voter: T::AccountId,
+			change: RegulatorChange,
+			approval_stake: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn projects)]
-	pub(super) type Projects<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::MassbitId, Project<AccountIdOf<T>, ChainId<T>>>;
+	pub(super) type Projects<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::MassbitId,
+		Project<AccountIdOf<T>, ProjectChainIds<T>, T::BlockNumber>,
+	>;
+
+	/// Pending ownership transfers of projects, as the new owner and the last block in which it
+	/// can accept the transfer.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_project_transfers)]
+	pub type PendingProjectTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MassbitId, (T::AccountId, T::BlockNumber)>;
+
+	/// Provider registrations proposed by regulators, waiting for the consent of their owners.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_provider_registrations)]
+	pub type PendingProviderRegistrations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::MassbitId,
+		ProviderRegProposal<T::AccountId, ChainId<T>, T::BlockNumber>,
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn providers)]
 	pub(super) type Providers<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::MassbitId, Provider<AccountIdOf<T>, ChainId<T>>>;
 
+	/// Additional chains served by a provider, besides its primary chain.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_chains)]
+	pub(super) type ProviderChains<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::MassbitId,
+		BoundedVec<ChainId<T>, T::MaxSupportedChains>,
+		ValueQuery,
+	>;
+
+	/// Human-readable information of a provider, set by its owner.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_metadata)]
+	pub type ProviderMetadata<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MassbitId, ProviderMetadataOf<T>>;
+
+	/// Performance score of a provider, from 0 to `MAX_PROVIDER_SCORE`, set by regulators.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_score)]
+	pub type ProviderScore<T: Config> = StorageMap<_, Blake2_128Concat, T::MassbitId, u32>;
+
+	/// Uptime checks of a provider, tracked against its SLA commitment.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_uptime_history)]
+	pub type ProviderUptimeHistory<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MassbitId, UptimeRecord, ValueQuery>;
+
+	/// Latest rating given by each consumer to a provider.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_ratings)]
+	pub type ProviderRatings<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::MassbitId, Blake2_128Concat, T::AccountId, u8>;
+
+	/// Sum and count of the ratings given to a provider.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_rating_aggregate)]
+	pub type ProviderRatingAggregate<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MassbitId, (u64, u32), ValueQuery>;
+
+	/// Era in which a consumer last rated a provider.
+	#[pallet::storage]
+	#[pallet::getter(fn rating_last_era)]
+	pub type RatingLastEra<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::MassbitId, Blake2_128Concat, T::AccountId, u32>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn regulators)]
 	pub type Regulators<T: Config> = StorageValue<_, BTreeSet<T::AccountId>, ValueQuery>;
 
+	/// Bond reserved by an account to be eligible as a regulator.
+	#[pallet::storage]
+	#[pallet::getter(fn regulator_bond)]
+	pub type RegulatorBond<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Pending stake-weighted proposals to add or remove a regulator, by candidate.
+	#[pallet::storage]
+	#[pallet::getter(fn regulator_proposals)]
+	pub type RegulatorProposals<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RegulatorProposalOf<T>>;
+
+	/// Recent actions of each regulator, as the targeted Massbit Id and the block of the action.
+	#[pallet::storage]
+	#[pallet::getter(fn regulator_action_log)]
+	pub type RegulatorActionLog<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(T::MassbitId, T::BlockNumber), T::MaxLogEntries>,
+		ValueQuery,
+	>;
+
+	/// Number of blocks between two quota renewals of subscription projects. Subscriptions are
+	/// unavailable until set.
+	#[pallet::storage]
+	#[pallet::getter(fn monthly_reset_interval)]
+	pub type MonthlyResetInterval<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	#[pallet::type_value]
+	pub fn DefaultQuotaPrice<T: Config>() -> BalanceOf<T> {
+		QUOTA_PRICE.saturated_into()
+	}
+
+	/// Price of one request of quota set by root, read through `StorageValueOracle`.
+	#[pallet::storage]
+	#[pallet::getter(fn quota_price)]
+	pub type QuotaPrice<T: Config> =
+		StorageValue<_, BalanceOf<T>, ValueQuery, DefaultQuotaPrice<T>>;
+
+	/// Whether only providers in `ProviderWhitelist` can be activated.
+	#[pallet::storage]
+	#[pallet::getter(fn whitelist_mode)]
+	pub type WhitelistMode<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Providers allowed to be activated while `WhitelistMode` is enabled.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_whitelist)]
+	pub type ProviderWhitelist<T: Config> = StorageValue<_, BTreeSet<T::MassbitId>, ValueQuery>;
+
+	/// Whether user facing calls are disabled by root, e.g. while a critical bug is fixed.
+	#[pallet::storage]
+	#[pallet::getter(fn pallet_paused)]
+	pub type PalletPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Registered chain Ids with their metadata
+	#[pallet::storage]
+	#[pallet::getter(fn chain_metadata)]
+	pub type ChainIds<T: Config> = StorageMap<_, Blake2_128Concat, ChainId<T>, ChainMetadataOf<T>>;
+
+	/// Stake of an active provider counted in `ChainStake`, i.e. its total stake when it was
+	/// activated or when its chains last changed.
+	#[pallet::storage]
+	pub(super) type IndexedProviderStake<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MassbitId, BalanceOf<T>>;
+
+	/// Total stake of the active providers serving a chain, either as primary or additional chain.
+	#[pallet::storage]
+	#[pallet::getter(fn chain_stake)]
+	pub type ChainStake<T: Config> = StorageMap<_, Blake2_128Concat, ChainId<T>, BalanceOf<T>>;
+
+	/// Chain with the most stake in `ChainStake`, updated along with it.
 	#[pallet::storage]
-	#[pallet::getter(fn chain_ids)]
-	pub type ChainIds<T: Config> = StorageValue<_, BTreeSet<ChainId<T>>, ValueQuery>;
+	pub(super) type MostStakedChain<T: Config> = StorageValue<_, ChainId<T>>;
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub regulators: Vec<T::AccountId>,
 		pub chain_ids: Vec<Vec<u8>>,
+		/// Providers registered at genesis, on chains of `chain_ids`.
+		pub providers: Vec<ProviderRegistration<T::AccountId, T::MassbitId>>,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { regulators: Vec::new(), chain_ids: Vec::new() }
+			Self { regulators: Vec::new(), chain_ids: Vec::new(), providers: Vec::new() }
 		}
 	}
 
@@ -178,45 +653,105 @@ pub mod pallet {
 			let regulators =
 				&self.regulators.iter().map(|r| r.clone()).collect::<BTreeSet<T::AccountId>>();
 			Regulators::<T>::put(&regulators);
-			let chain_ids = &self
-				.chain_ids
-				.iter()
-				.map(|c| c.clone().try_into().map_err(|_| Error::<T>::InvalidChainId).unwrap())
-				.collect::<BTreeSet<BoundedVec<u8, T::MaxChainIdLength>>>();
-			ChainIds::<T>::put(&chain_ids);
+			for chain_id in self.chain_ids.iter() {
+				let chain_id: ChainId<T> =
+					chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId).unwrap();
+				ChainIds::<T>::insert(chain_id, ChainMetadataOf::<T>::default());
+			}
+			for registration in self.providers.iter().cloned() {
+				let ProviderRegistration { provider_id, provider_type, owner, chain_id } =
+					registration;
+				Pallet::<T>::do_register_provider(provider_id, provider_type, owner, chain_id)
+					.expect("genesis provider should be valid");
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let mut weight = match MonthlyResetInterval::<T>::get() {
+				Some(interval) if (now % interval).is_zero() =>
+					Self::renew_subscriptions(now, interval),
+				_ => T::DbWeight::get().reads(1),
+			};
+			let period = T::ProjectDepositPeriod::get();
+			if !period.is_zero() && (now % period).is_zero() {
+				weight = weight.saturating_add(Self::expire_inactive_projects(now, period));
+			}
+			weight
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Consumer register new dAPI project and be charged some tokens in exchange for requests
-		/// quota
+		/// quota. The quota is shared by all `chain_ids` of the project. The quota of a
+		/// subscription project is renewed every `MonthlyResetInterval` blocks, charging the
+		/// consumer again for it.
 		#[pallet::weight(T::WeightInfo::register_project())]
 		pub fn register_project(
 			origin: OriginFor<T>,
 			project_id: T::MassbitId,
-			chain_id: Vec<u8>,
+			chain_ids: Vec<Vec<u8>>,
 			#[pallet::compact] deposit: BalanceOf<T>,
+			subscription: bool,
 		) -> DispatchResultWithPostInfo {
 			let consumer = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			ensure!(project_id.is_valid_id(), Error::<T>::InvalidMassbitId);
 			ensure!(!<Projects<T>>::contains_key(&project_id), Error::<T>::ProjectExists);
-			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
-				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
-			ensure!(Self::chain_ids().contains(&bounded_chain_id), Error::<T>::InvalidChainId);
+			ensure!(!chain_ids.is_empty(), Error::<T>::InvalidChainId);
+			let mut project_chain_ids = ProjectChainIds::<T>::default();
+			for chain_id in chain_ids.iter() {
+				let bounded_chain_id: ChainId<T> =
+					chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+				ensure!(
+					ChainIds::<T>::contains_key(&bounded_chain_id) &&
+						!project_chain_ids.contains(&bounded_chain_id),
+					Error::<T>::InvalidChainId
+				);
+				project_chain_ids
+					.try_push(bounded_chain_id)
+					.map_err(|_| Error::<T>::TooManyProjectChains)?;
+			}
+			let renewal_block = if subscription {
+				let interval =
+					Self::monthly_reset_interval().ok_or(Error::<T>::SubscriptionUnavailable)?;
+				Some(<frame_system::Pallet<T>>::block_number().saturating_add(interval))
+			} else {
+				None
+			};
 
-			let imbalance = T::Currency::withdraw(
+			T::Currency::transfer(
 				&consumer,
+				&Self::project_account(&project_id),
 				deposit,
-				WithdrawReasons::TRANSFER,
 				ExistenceRequirement::KeepAlive,
 			)?;
-			T::OnProjectPayment::on_unbalanced(imbalance);
 			let quota = Self::calculate_quota(deposit);
+			let status = ProjectStatus::Active;
+			let now = <frame_system::Pallet<T>>::block_number();
 			<Projects<T>>::insert(
 				&project_id,
-				Project { consumer: consumer.clone(), chain_id: bounded_chain_id, quota, usage: 0 },
+				Project {
+					consumer: consumer.clone(),
+					chain_ids: project_chain_ids,
+					quota,
+					usage: 0,
+					renewal_block,
+					status,
+					last_deposit_block: now,
+					last_usage_block: now,
+				},
 			);
-			Self::deposit_event(Event::ProjectRegistered { project_id, consumer, chain_id, quota });
+			Self::deposit_event(Event::ProjectRegistered {
+				project_id,
+				consumer,
+				chain_ids,
+				quota,
+				status,
+			});
 			Ok(().into())
 		}
 
@@ -228,35 +763,187 @@ pub mod pallet {
 			#[pallet::compact] deposit: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let consumer = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
 			let mut project = Projects::<T>::get(&project_id).ok_or(Error::<T>::ProjectDNE)?;
 			let quota = project.quota.saturating_add(Self::calculate_quota(deposit));
 			project.quota = quota;
-			let imbalance = T::Currency::withdraw(
+			project.last_deposit_block = <frame_system::Pallet<T>>::block_number();
+			T::Currency::transfer(
 				&consumer,
+				&Self::project_account(&project_id),
 				deposit,
-				WithdrawReasons::TRANSFER,
 				ExistenceRequirement::KeepAlive,
 			)?;
-			T::OnProjectPayment::on_unbalanced(imbalance);
 			<Projects<T>>::insert(&project_id, project);
 			Self::deposit_event(Event::ProjectDeposited { project_id, new_quota: quota });
 			Ok(().into())
 		}
 
-		/// Regulator submit project's usage. This will disable project if the quota is excess.
+		/// Consumer takes back `amount` worth of the unused quota of his project from its escrow
+		/// account. Quota which is already used can't be refunded.
+		#[pallet::weight(T::WeightInfo::refund_project())]
+		pub fn refund_project(
+			origin: OriginFor<T>,
+			project_id: T::MassbitId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let consumer = ensure_signed(origin)?;
+			let mut project = Projects::<T>::get(&project_id).ok_or(Error::<T>::ProjectDNE)?;
+			ensure!(project.consumer == consumer, Error::<T>::NotOwner);
+			let refund_quota = Self::calculate_quota(amount);
+			ensure!(
+				project.quota.saturating_sub(project.usage) >= refund_quota,
+				Error::<T>::InsufficientQuota
+			);
+
+			let amount = Self::calculate_deposit(refund_quota);
+			T::Currency::transfer(
+				&Self::project_account(&project_id),
+				&consumer,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			project.quota = project.quota.saturating_sub(refund_quota);
+			let remaining_quota = project.quota;
+			<Projects<T>>::insert(&project_id, project);
+			Self::deposit_event(Event::ProjectRefunded { project_id, amount, remaining_quota });
+			Ok(().into())
+		}
+
+		/// Consumer offers the ownership of his project to `new_owner`, who has to accept it
+		/// within `TransferWindowBlocks`. Replaces any pending transfer of the project.
+		#[pallet::weight(T::WeightInfo::transfer_project())]
+		pub fn transfer_project(
+			origin: OriginFor<T>,
+			project_id: T::MassbitId,
+			new_owner: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let consumer = ensure_signed(origin)?;
+			let project = Projects::<T>::get(&project_id).ok_or(Error::<T>::ProjectDNE)?;
+			ensure!(project.consumer == consumer, Error::<T>::NotOwner);
+
+			let expiry = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::TransferWindowBlocks::get());
+			PendingProjectTransfers::<T>::insert(&project_id, (new_owner.clone(), expiry));
+			Self::deposit_event(Event::ProjectTransferInitiated {
+				project_id,
+				old_owner: consumer,
+				new_owner,
+			});
+			Ok(().into())
+		}
+
+		/// New owner accepts a pending transfer of a project, keeping its quota and usage.
+		#[pallet::weight(T::WeightInfo::accept_project_transfer())]
+		pub fn accept_project_transfer(
+			origin: OriginFor<T>,
+			project_id: T::MassbitId,
+		) -> DispatchResultWithPostInfo {
+			let new_owner = ensure_signed(origin)?;
+			let (recipient, expiry) = PendingProjectTransfers::<T>::get(&project_id)
+				.ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(recipient == new_owner, Error::<T>::PermissionDenied);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= expiry,
+				Error::<T>::TransferExpired
+			);
+			let mut project = Projects::<T>::get(&project_id).ok_or(Error::<T>::ProjectDNE)?;
+
+			let old_owner = sp_std::mem::replace(&mut project.consumer, new_owner.clone());
+			<Projects<T>>::insert(&project_id, project);
+			PendingProjectTransfers::<T>::remove(&project_id);
+			Self::deposit_event(Event::ProjectTransferred { project_id, old_owner, new_owner });
+			Ok(().into())
+		}
+
+		/// Regulator submit project's usage on one of its chains. This will disable project if
+		/// the quota is excess.
 		#[pallet::weight((0, DispatchClass::Normal, Pays::No))]
 		pub fn submit_project_usage(
 			origin: OriginFor<T>,
 			project_id: T::MassbitId,
+			chain_id: Vec<u8>,
 			usage: u128,
 		) -> DispatchResultWithPostInfo {
 			let regulator = ensure_signed(origin)?;
 			ensure!(Self::regulators().contains(&regulator), Error::<T>::PermissionDenied);
+			Self::ensure_not_paused()?;
 			let mut project = Projects::<T>::get(&project_id).ok_or(Error::<T>::ProjectDNE)?;
+			ensure!(project.status != ProjectStatus::Suspended, Error::<T>::ProjectSuspended);
+			ensure!(
+				project.chain_ids.iter().any(|project_chain| project_chain[..] == chain_id[..]),
+				Error::<T>::InvalidChainId
+			);
+			let previous_usage = project.usage;
 			project.usage = project.usage.saturating_add(usage).min(project.quota);
-			let usage = project.usage;
+			project.last_usage_block = <frame_system::Pallet<T>>::block_number();
+			Self::settle_project_payment(&project_id, project.usage.saturating_sub(previous_usage));
+			let (usage, quota) = (project.usage, project.quota);
 			Projects::<T>::insert(&project_id, project);
-			Self::deposit_event(Event::ProjectUsageUpdated { project_id, usage });
+			Self::log_regulator_action(&regulator, &project_id);
+			Self::deposit_event(Event::ProjectUsageUpdated {
+				project_id: project_id.clone(),
+				chain_id,
+				usage,
+			});
+
+			// Alert only when a threshold is crossed, not on every submission past it.
+			let low_threshold = T::QuotaLowThreshold::get() * quota;
+			if previous_usage < quota && usage >= quota {
+				Self::deposit_event(Event::ProjectQuotaExhausted { project_id });
+			} else if previous_usage <= low_threshold && usage > low_threshold {
+				Self::deposit_event(Event::ProjectQuotaLow {
+					project_id,
+					remaining_quota: quota.saturating_sub(usage),
+				});
+			}
+			Ok(().into())
+		}
+
+		/// Regulator suspends an active project flagged for abuse. The project keeps its quota
+		/// but its usage can't be submitted until root unsuspends it.
+		#[pallet::weight((T::WeightInfo::suspend_project(), DispatchClass::Normal, Pays::No))]
+		pub fn suspend_project(
+			origin: OriginFor<T>,
+			project_id: T::MassbitId,
+			reason: BoundedVec<u8, T::MaxReasonLength>,
+		) -> DispatchResultWithPostInfo {
+			let regulator = ensure_signed(origin)?;
+			ensure!(Self::regulators().contains(&regulator), Error::<T>::PermissionDenied);
+			Projects::<T>::try_mutate(&project_id, |maybe_project| -> DispatchResult {
+				let project = maybe_project.as_mut().ok_or(Error::<T>::ProjectDNE)?;
+				match project.status {
+					ProjectStatus::Active => project.status = ProjectStatus::Suspended,
+					ProjectStatus::Suspended => return Err(Error::<T>::ProjectSuspended.into()),
+					ProjectStatus::Expired => return Err(Error::<T>::InactiveProject.into()),
+				}
+				Ok(())
+			})?;
+			Self::log_regulator_action(&regulator, &project_id);
+			Self::deposit_event(Event::ProjectSuspended {
+				project_id,
+				reason: reason.into_inner(),
+			});
+			Ok(().into())
+		}
+
+		/// Lift the suspension of a project.
+		#[pallet::weight(T::WeightInfo::unsuspend_project())]
+		pub fn unsuspend_project(
+			origin: OriginFor<T>,
+			project_id: T::MassbitId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Projects::<T>::try_mutate(&project_id, |maybe_project| -> DispatchResult {
+				let project = maybe_project.as_mut().ok_or(Error::<T>::ProjectDNE)?;
+				ensure!(
+					project.status == ProjectStatus::Suspended,
+					Error::<T>::ProjectNotSuspended
+				);
+				project.status = ProjectStatus::Active;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ProjectUnsuspended { project_id });
 			Ok(().into())
 		}
 
@@ -271,49 +958,170 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let regulator = ensure_signed(origin)?;
 			ensure!(<Regulators<T>>::get().contains(&regulator), Error::<T>::PermissionDenied);
+			Self::ensure_not_paused()?;
+			Self::do_register_provider(provider_id.clone(), provider_type, owner, chain_id)?;
+			Self::log_regulator_action(&regulator, &provider_id);
+			Ok(().into())
+		}
+
+		/// Regulator proposes the registration of a provider, which only takes effect once
+		/// `owner` accepts it within `RegistrationTimeout`. Replaces any pending proposal of the
+		/// provider.
+		#[pallet::weight(T::WeightInfo::propose_provider_registration())]
+		pub fn propose_provider_registration(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			provider_type: ProviderType,
+			owner: T::AccountId,
+			chain_id: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let regulator = ensure_signed(origin)?;
+			ensure!(<Regulators<T>>::get().contains(&regulator), Error::<T>::PermissionDenied);
+			Self::ensure_not_paused()?;
+			ensure!(provider_id.is_valid_id(), Error::<T>::InvalidMassbitId);
 			ensure!(!<Providers<T>>::contains_key(&provider_id), Error::<T>::AlreadyExist);
-			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
-				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
-			ensure!(<ChainIds<T>>::get().contains(&bounded_chain_id), Error::<T>::InvalidChainId);
-			<Providers<T>>::insert(
+			let chain_id: ChainId<T> =
+				chain_id.try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+			ensure!(ChainIds::<T>::contains_key(&chain_id), Error::<T>::InvalidChainId);
+
+			let expiry = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::RegistrationTimeout::get());
+			PendingProviderRegistrations::<T>::insert(
 				&provider_id,
-				Provider {
-					provider_type,
-					owner: owner.clone(),
-					chain_id: bounded_chain_id,
-					status: ProviderStatus::Registered,
-				},
+				ProviderRegProposal { provider_type, owner: owner.clone(), chain_id, expiry },
 			);
-			Self::deposit_event(Event::ProviderRegistered {
-				provider_id,
-				provider_type,
-				owner,
-				chain_id,
-			});
+			Self::log_regulator_action(&regulator, &provider_id);
+			Self::deposit_event(Event::ProviderRegistrationProposed { provider_id, owner, expiry });
 			Ok(().into())
 		}
 
-		/// Operator stake some funds to his own provider
-		#[pallet::weight(T::WeightInfo::deposit_provider())]
-		pub fn deposit_provider(
+		/// Owner accepts a pending registration of its provider, which can then be deposited.
+		#[pallet::weight(T::WeightInfo::accept_provider_registration())]
+		pub fn accept_provider_registration(
 			origin: OriginFor<T>,
 			provider_id: T::MassbitId,
-			#[pallet::compact] deposit: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let owner = ensure_signed(origin)?;
-			let mut provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
-			ensure!(provider.owner == owner, Error::<T>::NotOwner);
+			Self::ensure_not_paused()?;
+			let proposal = PendingProviderRegistrations::<T>::get(&provider_id)
+				.ok_or(Error::<T>::NoPendingRegistration)?;
+			ensure!(proposal.owner == owner, Error::<T>::PermissionDenied);
 			ensure!(
-				provider.status == ProviderStatus::Registered,
-				Error::<T>::InvalidProviderStatus
+				<frame_system::Pallet<T>>::block_number() <= proposal.expiry,
+				Error::<T>::RegistrationExpired
 			);
-			T::DapiStaking::register_provider(owner.clone(), provider_id.clone(), deposit)?;
-			provider.status = ProviderStatus::Active;
-			Providers::<T>::insert(&provider_id, provider.clone());
-			Self::deposit_event(Event::ProviderActivated {
+
+			PendingProviderRegistrations::<T>::remove(&provider_id);
+			Self::do_register_provider(
 				provider_id,
-				provider_type: provider.provider_type,
-			});
+				proposal.provider_type,
+				owner,
+				proposal.chain_id.into_inner(),
+			)?;
+			Ok(().into())
+		}
+
+		/// Register a batch of providers. Invalid entries are skipped.
+		#[pallet::weight((
+			T::WeightInfo::register_providers_batch(registrations.len() as u32),
+			DispatchClass::Normal,
+			Pays::No
+		))]
+		pub fn register_providers_batch(
+			origin: OriginFor<T>,
+			registrations: BoundedVec<
+				ProviderRegistration<T::AccountId, T::MassbitId>,
+				T::MaxBatchProviders,
+			>,
+		) -> DispatchResultWithPostInfo {
+			let regulator = ensure_signed(origin)?;
+			ensure!(<Regulators<T>>::get().contains(&regulator), Error::<T>::PermissionDenied);
+			Self::ensure_not_paused()?;
+			for ProviderRegistration { provider_id, provider_type, owner, chain_id } in
+				registrations
+			{
+				match Self::do_register_provider(
+					provider_id.clone(),
+					provider_type,
+					owner,
+					chain_id,
+				) {
+					Ok(_) => Self::log_regulator_action(&regulator, &provider_id),
+					Err(reason) => Self::deposit_event(Event::ProviderRegistrationSkipped {
+						provider_id,
+						reason,
+					}),
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Register a batch of providers. Fails without registering any if one of them is
+		/// invalid.
+		#[pallet::weight((
+			T::WeightInfo::batch_register_providers(registrations.len() as u32),
+			DispatchClass::Normal,
+			Pays::No
+		))]
+		pub fn batch_register_providers(
+			origin: OriginFor<T>,
+			registrations: BoundedVec<
+				ProviderRegistration<T::AccountId, T::MassbitId>,
+				T::MaxBatchProviders,
+			>,
+		) -> DispatchResultWithPostInfo {
+			let regulator = ensure_signed(origin)?;
+			ensure!(<Regulators<T>>::get().contains(&regulator), Error::<T>::PermissionDenied);
+			Self::ensure_not_paused()?;
+			for ProviderRegistration { provider_id, provider_type, owner, chain_id } in
+				registrations
+			{
+				Self::do_register_provider(provider_id.clone(), provider_type, owner, chain_id)?;
+				Self::log_regulator_action(&regulator, &provider_id);
+			}
+			Ok(().into())
+		}
+
+		/// Operator stake some funds to his own provider and commits to pass `sla_commitment` of
+		/// its uptime checks.
+		#[pallet::weight(T::WeightInfo::deposit_provider())]
+		pub fn deposit_provider(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			#[pallet::compact] deposit: BalanceOf<T>,
+			sla_commitment: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			let mut provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider.owner == owner, Error::<T>::NotOwner);
+			ensure!(
+				provider.status == ProviderStatus::Registered,
+				Error::<T>::InvalidProviderStatus
+			);
+			ensure!(
+				!Self::whitelist_mode() || Self::provider_whitelist().contains(&provider_id),
+				Error::<T>::ProviderNotWhitelisted
+			);
+			let min_stake = match provider.provider_type {
+				ProviderType::Gateway => T::GatewayMinStake::get(),
+				ProviderType::Node => T::NodeMinStake::get(),
+			};
+			T::DapiStaking::register_provider(
+				owner.clone(),
+				provider_id.clone(),
+				deposit,
+				min_stake,
+			)?;
+			provider.status = ProviderStatus::Active;
+			provider.sla_commitment = sla_commitment;
+			Providers::<T>::insert(&provider_id, provider.clone());
+			let chains = Self::chains_of(&provider_id, &provider);
+			Self::index_provider_stake(&provider_id, &[], &chains);
+			Self::deposit_event(Event::ProviderActivated {
+				provider_id,
+				provider_type: provider.provider_type,
+			});
 			Ok(().into())
 		}
 
@@ -324,6 +1132,7 @@ pub mod pallet {
 			provider_id: T::MassbitId,
 		) -> DispatchResultWithPostInfo {
 			let account = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
 			let mut provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
 			ensure!(provider.owner == account, Error::<T>::NotOwner);
 			ensure!(provider.status == ProviderStatus::Active, Error::<T>::InvalidProviderStatus);
@@ -332,6 +1141,8 @@ pub mod pallet {
 			provider.status =
 				ProviderStatus::InActive { reason: ProviderDeactivateReason::UnRegistered };
 			Providers::<T>::insert(&provider_id, provider.clone());
+			let chains = Self::chains_of(&provider_id, &provider);
+			Self::index_provider_stake(&provider_id, &chains, &[]);
 
 			Self::deposit_event(Event::<T>::ProviderDeactivated {
 				provider_id,
@@ -341,7 +1152,139 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// Fisherman report malfunction provider and unregister it from staking pool.
+		/// Operator adds a chain served by his own provider, besides its primary chain.
+		#[pallet::weight(T::WeightInfo::add_provider_chain())]
+		pub fn add_provider_chain(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			chain_id: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider.owner == owner, Error::<T>::NotOwner);
+			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
+				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+			ensure!(ChainIds::<T>::contains_key(&bounded_chain_id), Error::<T>::InvalidChainId);
+			ensure!(provider.chain_id != bounded_chain_id, Error::<T>::AlreadyExist);
+			let mut chains = ProviderChains::<T>::get(&provider_id);
+			ensure!(!chains.contains(&bounded_chain_id), Error::<T>::AlreadyExist);
+			let old_chains = Self::chains_of(&provider_id, &provider);
+			chains
+				.try_push(bounded_chain_id)
+				.map_err(|_| Error::<T>::TooManyProviderChains)?;
+			ProviderChains::<T>::insert(&provider_id, chains);
+			if provider.status == ProviderStatus::Active {
+				let new_chains = Self::chains_of(&provider_id, &provider);
+				Self::index_provider_stake(&provider_id, &old_chains, &new_chains);
+			}
+			Self::deposit_event(Event::ProviderChainAdded { provider_id, chain_id });
+			Ok(().into())
+		}
+
+		/// Operator removes an additional chain served by his own provider.
+		#[pallet::weight(T::WeightInfo::remove_provider_chain())]
+		pub fn remove_provider_chain(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			chain_id: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider.owner == owner, Error::<T>::NotOwner);
+			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
+				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+			let mut chains = ProviderChains::<T>::get(&provider_id);
+			let index = chains
+				.iter()
+				.position(|c| c == &bounded_chain_id)
+				.ok_or(Error::<T>::InvalidChainId)?;
+			let old_chains = Self::chains_of(&provider_id, &provider);
+			chains.remove(index);
+			if chains.is_empty() {
+				ProviderChains::<T>::remove(&provider_id);
+			} else {
+				ProviderChains::<T>::insert(&provider_id, chains);
+			}
+			if provider.status == ProviderStatus::Active {
+				let new_chains = Self::chains_of(&provider_id, &provider);
+				Self::index_provider_stake(&provider_id, &old_chains, &new_chains);
+			}
+			Self::deposit_event(Event::ProviderChainRemoved { provider_id, chain_id });
+			Ok(().into())
+		}
+
+		/// Operator sets the name, endpoint and description of his own provider.
+		#[pallet::weight(T::WeightInfo::update_provider_metadata())]
+		pub fn update_provider_metadata(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			metadata: ProviderMetadataExtra<Vec<u8>, Vec<u8>, Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider.owner == owner, Error::<T>::NotOwner);
+			let metadata: ProviderMetadataOf<T> = ProviderMetadataExtra {
+				name: metadata.name.try_into().map_err(|_| Error::<T>::MetadataTooLong)?,
+				endpoint: metadata.endpoint.try_into().map_err(|_| Error::<T>::MetadataTooLong)?,
+				description: metadata
+					.description
+					.try_into()
+					.map_err(|_| Error::<T>::MetadataTooLong)?,
+			};
+			ProviderMetadata::<T>::insert(&provider_id, metadata);
+			Self::deposit_event(Event::ProviderMetadataUpdated { provider_id });
+			Ok(().into())
+		}
+
+		/// Consumer rates a provider from 0 to 10. Only consumers with an active project on a
+		/// chain served by the provider can rate it, at most once every `RatingCooldownEras`.
+		/// `project_id` is the consumer's project proving the eligibility.
+		#[pallet::weight(T::WeightInfo::submit_provider_rating())]
+		pub fn submit_provider_rating(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			project_id: T::MassbitId,
+			rating: u8,
+		) -> DispatchResultWithPostInfo {
+			let consumer = ensure_signed(origin)?;
+			ensure!(rating <= MAX_PROVIDER_RATING, Error::<T>::InvalidRating);
+			let provider = Providers::<T>::get(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			let project = Projects::<T>::get(&project_id).ok_or(Error::<T>::ProjectDNE)?;
+			let chains = ProviderChains::<T>::get(&provider_id);
+			ensure!(
+				project.consumer == consumer &&
+					project.status == ProjectStatus::Active &&
+					project.chain_ids.iter().any(|chain_id| {
+						*chain_id == provider.chain_id || chains.contains(chain_id)
+					}),
+				Error::<T>::NotEligibleRater
+			);
+
+			let current_era = T::DapiStaking::current_era();
+			if let Some(last_era) = RatingLastEra::<T>::get(&provider_id, &consumer) {
+				ensure!(
+					current_era >= last_era.saturating_add(T::RatingCooldownEras::get()),
+					Error::<T>::RatingCooldown
+				);
+			}
+
+			let previous = ProviderRatings::<T>::get(&provider_id, &consumer);
+			ProviderRatingAggregate::<T>::mutate(&provider_id, |(sum, count)| {
+				match previous {
+					Some(previous) => *sum = sum.saturating_sub(previous as u64),
+					None => *count = count.saturating_add(1),
+				}
+				*sum = sum.saturating_add(rating as u64);
+			});
+			ProviderRatings::<T>::insert(&provider_id, &consumer, rating);
+			RatingLastEra::<T>::insert(&provider_id, &consumer, current_era);
+
+			Self::deposit_event(Event::ProviderRated { provider_id, by: consumer, rating });
+			Ok(().into())
+		}
+
+		/// Fisherman report malfunction provider and unregister it from staking pool. A provider
+		/// with bad performance also gets `OffenceSlashRatio` of its bond slashed.
 		#[pallet::weight((0, DispatchClass::Normal, Pays::No))]
 		pub fn report_provider_offence(
 			origin: OriginFor<T>,
@@ -350,32 +1293,85 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let regulator = ensure_signed(origin)?;
 			ensure!(Self::regulators().contains(&regulator), Error::<T>::PermissionDenied);
-			let mut provider = Self::providers(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			Self::ensure_not_paused()?;
+			let provider = Self::providers(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
 			ensure!(provider.status == ProviderStatus::Active, Error::<T>::InvalidProviderStatus);
 
-			T::DapiStaking::unregister_provider(provider_id.clone())?;
-			provider.status = ProviderStatus::InActive { reason };
-			Providers::<T>::insert(&provider_id, provider.clone());
-			Self::deposit_event(Event::<T>::ProviderDeactivated {
+			Self::do_report_provider_offence(&regulator, provider_id, provider, reason)?;
+			Ok(().into())
+		}
+
+		/// Regulator sets the performance score of a provider, from 0 to `MAX_PROVIDER_SCORE`.
+		/// The score scales part of the provider's delegators' rewards.
+		#[pallet::weight((T::WeightInfo::update_provider_score(), DispatchClass::Normal, Pays::No))]
+		pub fn update_provider_score(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			score: u32,
+		) -> DispatchResultWithPostInfo {
+			let regulator = ensure_signed(origin)?;
+			ensure!(Self::regulators().contains(&regulator), Error::<T>::PermissionDenied);
+			ensure!(Providers::<T>::contains_key(&provider_id), Error::<T>::ProviderDNE);
+			ensure!(score <= MAX_PROVIDER_SCORE, Error::<T>::InvalidScore);
+
+			let old_score = ProviderScore::<T>::mutate(&provider_id, |s| s.replace(score));
+			Self::log_regulator_action(&regulator, &provider_id);
+			Self::deposit_event(Event::<T>::ProviderScoreUpdated {
 				provider_id,
-				provider_type: provider.provider_type,
-				reason,
+				old_score,
+				new_score: score,
 			});
 			Ok(().into())
 		}
 
-		/// Add new regulator.
+		/// Regulator records the result of an uptime check of an active provider. Once
+		/// `SlaEvaluationPeriod` checks are recorded, a provider passing less than its SLA
+		/// commitment is reported for the violation.
+		#[pallet::weight((T::WeightInfo::record_provider_uptime(), DispatchClass::Normal, Pays::No))]
+		pub fn record_provider_uptime(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+			success: bool,
+		) -> DispatchResultWithPostInfo {
+			let regulator = ensure_signed(origin)?;
+			ensure!(Self::regulators().contains(&regulator), Error::<T>::PermissionDenied);
+			let provider = Self::providers(&provider_id).ok_or(Error::<T>::ProviderDNE)?;
+			ensure!(provider.status == ProviderStatus::Active, Error::<T>::InvalidProviderStatus);
+
+			let record = ProviderUptimeHistory::<T>::mutate(&provider_id, |record| {
+				record.total_checks = record.total_checks.saturating_add(1);
+				if success {
+					record.successful_checks = record.successful_checks.saturating_add(1);
+				}
+				*record
+			});
+
+			if record.total_checks >= T::SlaEvaluationPeriod::get() &&
+				Perbill::from_rational(record.successful_checks, record.total_checks) <
+					provider.sla_commitment
+			{
+				Self::deposit_event(Event::<T>::SlaViolation { provider_id: provider_id.clone() });
+				let reason = ProviderDeactivateReason::SlaViolation {
+					total_checks: record.total_checks,
+					successful_checks: record.successful_checks,
+				};
+				Self::do_report_provider_offence(&regulator, provider_id, provider, reason)?;
+			} else {
+				Self::log_regulator_action(&regulator, &provider_id);
+			}
+			Ok(().into())
+		}
+
+		/// Add new regulator. The account must have bonded at least `MinRegulatorBond`.
 		#[pallet::weight(T::WeightInfo::add_regulator())]
 		pub fn add_regulator(
 			origin: OriginFor<T>,
 			account_id: T::AccountId,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
-			let mut regulators = Regulators::<T>::get();
-			ensure!(!regulators.contains(&account_id), Error::<T>::AlreadyExist);
-			regulators.insert(account_id.clone());
-			Regulators::<T>::put(&regulators);
-			Self::deposit_event(Event::RegulatorAdded { account_id });
+			ensure!(!Regulators::<T>::get().contains(&account_id), Error::<T>::AlreadyExist);
+			Self::ensure_regulator_bond(&account_id)?;
+			Self::do_add_regulator(account_id);
 			Ok(().into())
 		}
 
@@ -386,29 +1382,262 @@ pub mod pallet {
 			account_id: T::AccountId,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
-			let mut regulators = Regulators::<T>::get();
-			ensure!(regulators.contains(&account_id), Error::<T>::PermissionDenied);
-			regulators.remove(&account_id);
-			Regulators::<T>::put(&regulators);
-			Self::deposit_event(Event::RegulatorRemoved { account_id });
+			ensure!(Regulators::<T>::get().contains(&account_id), Error::<T>::PermissionDenied);
+			Self::do_remove_regulator(account_id);
 			Ok(().into())
 		}
 
-		/// Register new chain Id. The format should be `chainName.networkName` (e.g.
-		/// "dot.mainnet", "eth.mainnet")
+		/// Add a batch of regulators. Fails without adding any if one of them is already a
+		/// regulator or hasn't bonded enough.
+		#[pallet::weight(T::WeightInfo::batch_add_regulators(accounts.len() as u32))]
+		pub fn batch_add_regulators(
+			origin: OriginFor<T>,
+			accounts: BoundedVec<T::AccountId, T::MaxRegulatorsPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Regulators::<T>::try_mutate(|regulators| -> DispatchResult {
+				for account_id in accounts.iter() {
+					ensure!(regulators.insert(account_id.clone()), Error::<T>::AlreadyExist);
+					Self::ensure_regulator_bond(account_id)?;
+				}
+				Ok(())
+			})?;
+			Self::deposit_event(Event::RegulatorsUpdated {
+				added: accounts.into_inner(),
+				removed: Vec::new(),
+			});
+			Ok(().into())
+		}
+
+		/// Remove a batch of regulators and clear their recorded actions. Fails without removing
+		/// any if one of them isn't a regulator.
+		#[pallet::weight(T::WeightInfo::batch_remove_regulators(accounts.len() as u32))]
+		pub fn batch_remove_regulators(
+			origin: OriginFor<T>,
+			accounts: BoundedVec<T::AccountId, T::MaxRegulatorsPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Regulators::<T>::try_mutate(|regulators| -> DispatchResult {
+				for account_id in accounts.iter() {
+					ensure!(regulators.remove(account_id), Error::<T>::PermissionDenied);
+				}
+				Ok(())
+			})?;
+			for account_id in accounts.iter() {
+				RegulatorActionLog::<T>::remove(account_id);
+			}
+			Self::deposit_event(Event::RegulatorsUpdated {
+				added: Vec::new(),
+				removed: accounts.into_inner(),
+			});
+			Ok(().into())
+		}
+
+		/// Reserve `amount` towards the bond required to become a regulator. The total bond must
+		/// reach `MinRegulatorBond`.
+		#[pallet::weight(T::WeightInfo::bond_as_regulator())]
+		pub fn bond_as_regulator(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let bond = RegulatorBond::<T>::get(&who).saturating_add(amount);
+			ensure!(bond >= T::MinRegulatorBond::get(), Error::<T>::InsufficientRegulatorBond);
+			T::Currency::reserve(&who, amount)?;
+			RegulatorBond::<T>::insert(&who, bond);
+			Self::deposit_event(Event::RegulatorBonded { who, amount });
+			Ok(().into())
+		}
+
+		/// Release the whole regulator bond of the caller, who must not be a regulator anymore.
+		#[pallet::weight(T::WeightInfo::unbond_regulator())]
+		pub fn unbond_regulator(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Regulators::<T>::get().contains(&who), Error::<T>::StillRegulator);
+			let amount = RegulatorBond::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::RegulatorNotBonded);
+			T::Currency::unreserve(&who, amount);
+			Self::deposit_event(Event::RegulatorUnbonded { who, amount });
+			Ok(().into())
+		}
+
+		/// Slash `ratio` of the bond of `regulator`, e.g. once an appeal found one of its offence
+		/// reports to be false.
+		#[pallet::weight(T::WeightInfo::slash_regulator())]
+		pub fn slash_regulator(
+			origin: OriginFor<T>,
+			regulator: T::AccountId,
+			ratio: Perbill,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let bond = RegulatorBond::<T>::get(&regulator);
+			ensure!(!bond.is_zero(), Error::<T>::RegulatorNotBonded);
+			let (imbalance, _) = T::Currency::slash_reserved(&regulator, ratio * bond);
+			let amount = imbalance.peek();
+			let remaining = bond.saturating_sub(amount);
+			if remaining.is_zero() {
+				RegulatorBond::<T>::remove(&regulator);
+			} else {
+				RegulatorBond::<T>::insert(&regulator, remaining);
+			}
+			T::RegulatorSlashDestination::on_unbalanced(imbalance);
+			Self::deposit_event(Event::RegulatorSlashed { who: regulator, amount });
+			Ok(().into())
+		}
+
+		/// Staker votes for `candidate` to become a regulator, weighted by his delegated stake.
+		/// The candidate is added once the current stake of all voters exceeds
+		/// `RegulatorApprovalThreshold` of the total stake. The candidate must have bonded at
+		/// least `MinRegulatorBond`.
+		#[pallet::weight(T::WeightInfo::propose_regulator(T::MaxRegulatorVoters::get()))]
+		pub fn propose_regulator(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+			ensure!(!Regulators::<T>::get().contains(&candidate), Error::<T>::AlreadyExist);
+			Self::ensure_regulator_bond(&candidate)?;
+			Self::vote_regulator_change(voter, candidate, RegulatorChange::Add)?;
+			Ok(().into())
+		}
+
+		/// Staker votes for `regulator` to be removed, the same way as `propose_regulator`.
+		#[pallet::weight(T::WeightInfo::propose_regulator_removal(T::MaxRegulatorVoters::get()))]
+		pub fn propose_regulator_removal(
+			origin: OriginFor<T>,
+			regulator: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+			ensure!(Regulators::<T>::get().contains(&regulator), Error::<T>::PermissionDenied);
+			Self::vote_regulator_change(voter, regulator, RegulatorChange::Remove)?;
+			Ok(().into())
+		}
+
+		/// Set the number of blocks between two quota renewals of subscription projects.
+		#[pallet::weight(T::WeightInfo::set_monthly_reset_interval())]
+		pub fn set_monthly_reset_interval(
+			origin: OriginFor<T>,
+			interval: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(!interval.is_zero(), Error::<T>::InvalidResetInterval);
+			MonthlyResetInterval::<T>::put(interval);
+			Self::deposit_event(Event::MonthlyResetIntervalSet { interval });
+			Ok(().into())
+		}
+
+		/// Set the price of one request of quota, within `MinQuotaPrice` and `MaxQuotaPrice`.
+		/// Only used by runtimes reading the price through `StorageValueOracle`.
+		#[pallet::weight(T::WeightInfo::set_quota_price())]
+		pub fn set_quota_price(
+			origin: OriginFor<T>,
+			#[pallet::compact] price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				price >= T::MinQuotaPrice::get() && price <= T::MaxQuotaPrice::get(),
+				Error::<T>::InvalidQuotaPrice
+			);
+			let old_price = QuotaPrice::<T>::mutate(|current| sp_std::mem::replace(current, price));
+			Self::deposit_event(Event::QuotaPriceUpdated { old_price, new_price: price });
+			Ok(().into())
+		}
+
+		/// Enable or disable the whitelist mode, in which only providers in `ProviderWhitelist`
+		/// can be activated. Providers already active are not affected.
+		#[pallet::weight(T::WeightInfo::set_whitelist_mode())]
+		pub fn set_whitelist_mode(
+			origin: OriginFor<T>,
+			enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			WhitelistMode::<T>::put(enabled);
+			Self::deposit_event(Event::WhitelistModeChanged { enabled });
+			Ok(().into())
+		}
+
+		/// Allow a provider to be activated while the whitelist mode is enabled.
+		#[pallet::weight(T::WeightInfo::whitelist_provider())]
+		pub fn whitelist_provider(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				ProviderWhitelist::<T>::mutate(|whitelist| whitelist.insert(provider_id.clone())),
+				Error::<T>::AlreadyExist
+			);
+			Self::deposit_event(Event::ProviderWhitelisted { provider_id });
+			Ok(().into())
+		}
+
+		/// Remove a provider from the whitelist. Its activation, if any, is kept.
+		#[pallet::weight(T::WeightInfo::remove_from_whitelist())]
+		pub fn remove_from_whitelist(
+			origin: OriginFor<T>,
+			provider_id: T::MassbitId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				ProviderWhitelist::<T>::mutate(|whitelist| whitelist.remove(&provider_id)),
+				Error::<T>::ProviderNotWhitelisted
+			);
+			Self::deposit_event(Event::ProviderRemovedFromWhitelist { provider_id });
+			Ok(().into())
+		}
+
+		/// Disable the user facing calls of the pallet until `unpause_pallet` is called. Root
+		/// calls keep working.
+		#[pallet::weight(T::WeightInfo::pause_pallet())]
+		pub fn pause_pallet(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			PalletPaused::<T>::put(true);
+			Self::deposit_event(Event::PalletPauseChanged { paused: true });
+			Ok(().into())
+		}
+
+		/// Enable the user facing calls of the pallet again.
+		#[pallet::weight(T::WeightInfo::unpause_pallet())]
+		pub fn unpause_pallet(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			PalletPaused::<T>::put(false);
+			Self::deposit_event(Event::PalletPauseChanged { paused: false });
+			Ok(().into())
+		}
+
+		/// Register new chain Id with its metadata. The format should be
+		/// `chainName.networkName` (e.g. "dot.mainnet", "eth.mainnet")
 		#[pallet::weight(T::WeightInfo::add_chain_id())]
-		pub fn add_chain_id(origin: OriginFor<T>, chain_id: Vec<u8>) -> DispatchResultWithPostInfo {
+		pub fn add_chain_id(
+			origin: OriginFor<T>,
+			chain_id: Vec<u8>,
+			metadata: ChainMetadata<Vec<u8>, Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
 				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
-			let mut chain_ids = ChainIds::<T>::get();
-			ensure!(!chain_ids.contains(&bounded_chain_id), Error::<T>::AlreadyExist);
-			chain_ids.insert(bounded_chain_id);
-			ChainIds::<T>::put(&chain_ids);
+			ensure!(!ChainIds::<T>::contains_key(&bounded_chain_id), Error::<T>::AlreadyExist);
+			ChainIds::<T>::insert(&bounded_chain_id, Self::bound_chain_metadata(metadata)?);
 			Self::deposit_event(Event::ChainIdAdded { chain_id });
 			Ok(().into())
 		}
 
+		/// Update the metadata of a registered chain Id.
+		#[pallet::weight(T::WeightInfo::update_chain_metadata())]
+		pub fn update_chain_metadata(
+			origin: OriginFor<T>,
+			chain_id: Vec<u8>,
+			metadata: ChainMetadata<Vec<u8>, Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let bounded_chain_id: ChainId<T> =
+				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+			ensure!(ChainIds::<T>::contains_key(&bounded_chain_id), Error::<T>::InvalidChainId);
+			ChainIds::<T>::insert(&bounded_chain_id, Self::bound_chain_metadata(metadata)?);
+			Self::deposit_event(Event::ChainMetadataUpdated { chain_id });
+			Ok(().into())
+		}
+
 		/// Remove chain Id.
 		#[pallet::weight(T::WeightInfo::remove_chain_id())]
 		pub fn remove_chain_id(
@@ -418,22 +1647,486 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
 				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
-			let mut chain_ids = ChainIds::<T>::get();
-			ensure!(chain_ids.contains(&bounded_chain_id), Error::<T>::InvalidChainId);
-			chain_ids.remove(&bounded_chain_id);
-			ChainIds::<T>::put(&chain_ids);
+			ensure!(ChainIds::<T>::contains_key(&bounded_chain_id), Error::<T>::InvalidChainId);
+			ChainIds::<T>::remove(&bounded_chain_id);
 			Self::deposit_event(Event::ChainIdRemoved { chain_id });
 			Ok(().into())
 		}
+
+		/// Register a batch of chain Ids without metadata. Chain Ids which are already
+		/// registered are skipped.
+		#[pallet::weight(T::WeightInfo::add_chain_ids_batch(chain_ids.len() as u32))]
+		pub fn add_chain_ids_batch(
+			origin: OriginFor<T>,
+			chain_ids: BoundedVec<Vec<u8>, T::MaxBatchChainIds>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let (mut added, mut skipped) = (Vec::new(), Vec::new());
+			for chain_id in chain_ids {
+				let bounded_chain_id: ChainId<T> =
+					chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+				if ChainIds::<T>::contains_key(&bounded_chain_id) {
+					skipped.push(chain_id);
+				} else {
+					ChainIds::<T>::insert(bounded_chain_id, ChainMetadataOf::<T>::default());
+					added.push(chain_id);
+				}
+			}
+			Self::deposit_event(Event::ChainIdsBatchAdded { added, skipped });
+			Ok(().into())
+		}
+
+		/// Remove a batch of chain Ids. Chain Ids which aren't registered or are still used by
+		/// a provider or an active project are skipped.
+		#[pallet::weight(T::WeightInfo::remove_chain_ids_batch(chain_ids.len() as u32))]
+		pub fn remove_chain_ids_batch(
+			origin: OriginFor<T>,
+			chain_ids: BoundedVec<Vec<u8>, T::MaxBatchChainIds>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let in_use = Self::chain_ids_in_use();
+			let (mut removed, mut skipped) = (Vec::new(), Vec::new());
+			for chain_id in chain_ids {
+				let bounded_chain_id: ChainId<T> =
+					chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+				if !in_use.contains(&bounded_chain_id) &&
+					ChainIds::<T>::contains_key(&bounded_chain_id)
+				{
+					ChainIds::<T>::remove(&bounded_chain_id);
+					removed.push(chain_id);
+				} else {
+					skipped.push(chain_id);
+				}
+			}
+			Self::deposit_event(Event::ChainIdsBatchRemoved { removed, skipped });
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> QuotaDeduction<T::MassbitId, BalanceOf<T>> for Pallet<T> {
+		fn deduct(project_id: T::MassbitId, amount: BalanceOf<T>) -> DispatchResult {
+			let quota = Self::calculate_quota(amount);
+			Projects::<T>::try_mutate(&project_id, |maybe_project| -> DispatchResult {
+				let project = maybe_project.as_mut().ok_or(Error::<T>::ProjectDNE)?;
+				ensure!(
+					project.quota.saturating_sub(project.usage) >= quota,
+					Error::<T>::InsufficientQuota
+				);
+				project.quota = project.quota.saturating_sub(quota);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ProjectQuotaDeducted { project_id, quota });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		fn ensure_regulator_bond(account_id: &T::AccountId) -> DispatchResult {
+			ensure!(
+				RegulatorBond::<T>::get(account_id) >= T::MinRegulatorBond::get(),
+				Error::<T>::RegulatorNotBonded
+			);
+			Ok(())
+		}
+
+		fn do_add_regulator(account_id: T::AccountId) {
+			Regulators::<T>::mutate(|regulators| regulators.insert(account_id.clone()));
+			Self::deposit_event(Event::RegulatorAdded { account_id });
+		}
+
+		fn do_remove_regulator(account_id: T::AccountId) {
+			Regulators::<T>::mutate(|regulators| regulators.remove(&account_id));
+			let retracted_count = RegulatorActionLog::<T>::take(&account_id).len() as u32;
+			Self::deposit_event(Event::RegulatorRemoved { account_id: account_id.clone() });
+			Self::deposit_event(Event::RegulatorActionsRetracted {
+				who: account_id,
+				retracted_count,
+			});
+		}
+
+		/// Count the vote of `voter` for `change` of `candidate`, applying the change once it's
+		/// approved. The approval is recounted from the current stake of every voter, so stake
+		/// moved to another account after voting can't be counted twice. An expired proposal is
+		/// replaced by a new one.
+		fn vote_regulator_change(
+			voter: T::AccountId,
+			candidate: T::AccountId,
+			change: RegulatorChange,
+		) -> DispatchResult {
+			let stake = T::DapiStaking::delegated_stake(&voter);
+			ensure!(!stake.is_zero(), Error::<T>::NoStake);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut proposal = match RegulatorProposals::<T>::get(&candidate) {
+				Some(proposal) if proposal.expiry >= now => {
+					ensure!(proposal.change == change, Error::<T>::ProposalMismatch);
+					proposal
+				},
+				_ => ProposalInfo {
+					change,
+					voters: Default::default(),
+					approval_stake: Zero::zero(),
+					expiry: now.saturating_add(T::RegulatorProposalDuration::get()),
+				},
+			};
+			ensure!(!proposal.voters.contains(&voter), Error::<T>::AlreadyVoted);
+			proposal.voters.try_push(voter.clone()).map_err(|_| Error::<T>::TooManyVoters)?;
+			proposal.approval_stake = proposal.voters.iter().fold(Zero::zero(), |total, voter| {
+				total.saturating_add(T::DapiStaking::delegated_stake(voter))
+			});
+			let approval_stake = proposal.approval_stake;
+			Self::deposit_event(Event::RegulatorProposalVoted {
+				candidate: candidate.clone(),
+				voter,
+				change,
+				approval_stake,
+			});
+
+			let threshold = T::RegulatorApprovalThreshold::get() * T::DapiStaking::total_staked();
+			if approval_stake > threshold {
+				RegulatorProposals::<T>::remove(&candidate);
+				match change {
+					RegulatorChange::Add => Self::do_add_regulator(candidate),
+					RegulatorChange::Remove => Self::do_remove_regulator(candidate),
+				}
+			} else {
+				RegulatorProposals::<T>::insert(&candidate, proposal);
+			}
+			Ok(())
+		}
+
+		/// Fail with `PalletPaused` while user facing calls are disabled.
+		fn ensure_not_paused() -> DispatchResult {
+			ensure!(!Self::pallet_paused(), Error::<T>::PalletPaused);
+			Ok(())
+		}
+
+		/// Record an action of `regulator` on `id`. The oldest entry is dropped if the log is
+		/// full.
+		fn log_regulator_action(regulator: &T::AccountId, id: &T::MassbitId) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			RegulatorActionLog::<T>::mutate(regulator, |log| {
+				if log.len() as u32 >= T::MaxLogEntries::get() {
+					log.remove(0);
+				}
+				let _ = log.try_push((id.clone(), now));
+			});
+		}
+
+		/// Get all chains served by a provider, its primary chain first.
+		pub fn all_provider_chains(provider_id: T::MassbitId) -> Vec<Vec<u8>> {
+			let primary = match Providers::<T>::get(&provider_id) {
+				Some(provider) => provider.chain_id,
+				None => return Vec::new(),
+			};
+			sp_std::iter::once(primary)
+				.chain(ProviderChains::<T>::get(&provider_id))
+				.map(|chain_id| chain_id.into_inner())
+				.collect()
+		}
+
+		/// Get providers serving `chain_id`, either as primary or additional chain.
+		pub fn providers_by_chain(chain_id: Vec<u8>) -> Vec<T::MassbitId> {
+			let bounded_chain_id: ChainId<T> = match chain_id.try_into() {
+				Ok(chain_id) => chain_id,
+				Err(_) => return Vec::new(),
+			};
+			Providers::<T>::iter()
+				.filter(|(provider_id, provider)| {
+					provider.chain_id == bounded_chain_id ||
+						ProviderChains::<T>::get(provider_id).contains(&bounded_chain_id)
+				})
+				.map(|(provider_id, _)| provider_id)
+				.collect()
+		}
+
+		/// Get the chain with the most stake of active providers, if any.
+		pub fn most_served_chain() -> Option<Vec<u8>> {
+			MostStakedChain::<T>::get().map(|chain_id| chain_id.into_inner())
+		}
+
+		/// Get the average rating of a provider in tenths, e.g `75` for `7.5`, if it was rated.
+		pub fn provider_average_rating(provider_id: T::MassbitId) -> Option<u32> {
+			let (sum, count) = ProviderRatingAggregate::<T>::get(&provider_id);
+			if count.is_zero() {
+				return None
+			}
+			Some((sum.saturating_mul(10) / count as u64) as u32)
+		}
+
+		/// Get the quota bought by `amount` at the current price.
 		pub fn calculate_quota(amount: BalanceOf<T>) -> u128 {
-			TryInto::<u128>::try_into(amount)
-				.ok()
-				.unwrap_or_default()
-				.div(1_000_000_000_000_000u128)
+			let amount: u128 = amount.saturated_into();
+			amount.checked_div(T::QuotaOracle::get().saturated_into()).unwrap_or_default()
+		}
+
+		/// Get the escrow account holding the payment for the unused quota of a project.
+		pub fn project_account(project_id: &T::MassbitId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(T::Hashing::hash_of(project_id))
+		}
+
+		/// Pay for `quota` used by a project out of its escrow account.
+		fn settle_project_payment(project_id: &T::MassbitId, quota: u128) {
+			let escrow = Self::project_account(project_id);
+			let amount = Self::calculate_deposit(quota).min(T::Currency::free_balance(&escrow));
+			if let Ok(imbalance) = T::Currency::withdraw(
+				&escrow,
+				amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			) {
+				T::OnProjectPayment::on_unbalanced(imbalance);
+			}
+		}
+
+		/// Chains served by `provider`, its primary chain first.
+		pub(crate) fn chains_of(
+			provider_id: &T::MassbitId,
+			provider: &Provider<AccountIdOf<T>, ChainId<T>>,
+		) -> Vec<ChainId<T>> {
+			sp_std::iter::once(provider.chain_id.clone())
+				.chain(ProviderChains::<T>::get(provider_id))
+				.collect()
+		}
+
+		/// Move the stake of `provider_id` counted in `ChainStake` from `old_chains` to
+		/// `new_chains`, counting its current stake. `MostStakedChain` is updated by scanning the
+		/// staked chains, whose number is bounded by the chain Ids added by root.
+		pub(crate) fn index_provider_stake(
+			provider_id: &T::MassbitId,
+			old_chains: &[ChainId<T>],
+			new_chains: &[ChainId<T>],
+		) {
+			if let Some(stake) = IndexedProviderStake::<T>::take(provider_id) {
+				for chain_id in old_chains {
+					ChainStake::<T>::mutate_exists(chain_id, |total| {
+						*total = total
+							.map(|total| total.saturating_sub(stake))
+							.filter(|total| !total.is_zero());
+					});
+				}
+			}
+			if !new_chains.is_empty() {
+				let stake = T::DapiStaking::provider_stake(provider_id);
+				for chain_id in new_chains {
+					ChainStake::<T>::mutate(chain_id, |total| {
+						*total = Some(total.unwrap_or_default().saturating_add(stake));
+					});
+				}
+				IndexedProviderStake::<T>::insert(provider_id, stake);
+			}
+			MostStakedChain::<T>::set(
+				ChainStake::<T>::iter()
+					.max_by_key(|(_, stake)| *stake)
+					.map(|(chain_id, _)| chain_id),
+			);
+		}
+
+		/// Deactivate `provider` for `reason`, slashing its bond for bad performance or an SLA
+		/// violation.
+		fn do_report_provider_offence(
+			regulator: &T::AccountId,
+			provider_id: T::MassbitId,
+			mut provider: Provider<AccountIdOf<T>, ChainId<T>>,
+			reason: ProviderDeactivateReason,
+		) -> DispatchResult {
+			if matches!(
+				reason,
+				ProviderDeactivateReason::BadPerformance { .. } |
+					ProviderDeactivateReason::SlaViolation { .. }
+			) {
+				T::DapiStaking::slash_provider(provider_id.clone(), T::OffenceSlashRatio::get())?;
+			}
+			T::DapiStaking::unregister_provider(provider_id.clone())?;
+			provider.status = ProviderStatus::InActive { reason };
+			Providers::<T>::insert(&provider_id, provider.clone());
+			let chains = Self::chains_of(&provider_id, &provider);
+			Self::index_provider_stake(&provider_id, &chains, &[]);
+			Self::log_regulator_action(regulator, &provider_id);
+			Self::deposit_event(Event::<T>::ProviderDeactivated {
+				provider_id,
+				provider_type: provider.provider_type,
+				reason,
+			});
+			Ok(())
+		}
+
+		fn do_register_provider(
+			provider_id: T::MassbitId,
+			provider_type: ProviderType,
+			owner: T::AccountId,
+			chain_id: Vec<u8>,
+		) -> DispatchResult {
+			ensure!(provider_id.is_valid_id(), Error::<T>::InvalidMassbitId);
+			ensure!(!<Providers<T>>::contains_key(&provider_id), Error::<T>::AlreadyExist);
+			let bounded_chain_id: BoundedVec<u8, T::MaxChainIdLength> =
+				chain_id.clone().try_into().map_err(|_| Error::<T>::InvalidChainId)?;
+			ensure!(ChainIds::<T>::contains_key(&bounded_chain_id), Error::<T>::InvalidChainId);
+			<Providers<T>>::insert(
+				&provider_id,
+				Provider {
+					provider_type,
+					owner: owner.clone(),
+					chain_id: bounded_chain_id,
+					status: ProviderStatus::Registered,
+					sla_commitment: Perbill::zero(),
+				},
+			);
+			Self::deposit_event(Event::ProviderRegistered {
+				provider_id,
+				provider_type,
+				owner,
+				chain_id,
+			});
+			Ok(())
+		}
+
+		/// Check whether `chain_id` is registered.
+		pub fn chain_id_exists(chain_id: Vec<u8>) -> bool {
+			match ChainId::<T>::try_from(chain_id) {
+				Ok(chain_id) => ChainIds::<T>::contains_key(&chain_id),
+				Err(_) => false,
+			}
 		}
+
+		/// Get the metadata of `chain_id`, if it is registered.
+		pub fn chain_metadata_of(chain_id: Vec<u8>) -> Option<ChainMetadata<Vec<u8>, Vec<u8>>> {
+			let chain_id = ChainId::<T>::try_from(chain_id).ok()?;
+			ChainIds::<T>::get(&chain_id).map(|metadata| ChainMetadata {
+				display_name: metadata.display_name.into_inner(),
+				explorer_url: metadata.explorer_url.into_inner(),
+				rpc_endpoint: metadata.rpc_endpoint.into_inner(),
+			})
+		}
+
+		/// Bound the fields of chain `metadata` to their max lengths.
+		fn bound_chain_metadata(
+			metadata: ChainMetadata<Vec<u8>, Vec<u8>>,
+		) -> Result<ChainMetadataOf<T>, Error<T>> {
+			Ok(ChainMetadata {
+				display_name: metadata
+					.display_name
+					.try_into()
+					.map_err(|_| Error::<T>::MetadataTooLong)?,
+				explorer_url: metadata
+					.explorer_url
+					.try_into()
+					.map_err(|_| Error::<T>::MetadataTooLong)?,
+				rpc_endpoint: metadata
+					.rpc_endpoint
+					.try_into()
+					.map_err(|_| Error::<T>::MetadataTooLong)?,
+			})
+		}
+
+		/// Get the chain Ids served by providers which aren't deactivated or used by projects
+		/// which haven't expired.
+		fn chain_ids_in_use() -> BTreeSet<ChainId<T>> {
+			let mut in_use = BTreeSet::new();
+			for (provider_id, provider) in Providers::<T>::iter() {
+				if !matches!(provider.status, ProviderStatus::InActive { .. }) {
+					in_use.insert(provider.chain_id);
+					in_use.extend(ProviderChains::<T>::get(&provider_id));
+				}
+			}
+			in_use.extend(
+				Projects::<T>::iter_values()
+					.filter(|project| project.status != ProjectStatus::Expired)
+					.flat_map(|project| project.chain_ids),
+			);
+			in_use
+		}
+
+		/// Get the amount to pay for `quota`.
+		pub fn calculate_deposit(quota: u128) -> BalanceOf<T> {
+			quota.saturating_mul(T::QuotaOracle::get().saturated_into()).saturated_into()
+		}
+
+		/// Renew the quota of subscription projects which are due at `now`. Consumers who can't
+		/// pay for the renewal have their project expired.
+		fn renew_subscriptions(now: T::BlockNumber, interval: T::BlockNumber) -> Weight {
+			let mut reads: Weight = 1;
+			let mut writes: Weight = 0;
+			for (project_id, mut project) in Projects::<T>::iter() {
+				reads += 1;
+				let renewal_block = match project.renewal_block {
+					Some(block) if block <= now && project.status == ProjectStatus::Active => block,
+					_ => continue,
+				};
+				reads += 2;
+				writes += 3;
+				// Quota left unused in the ending period is forfeited.
+				Self::settle_project_payment(
+					&project_id,
+					project.quota.saturating_sub(project.usage),
+				);
+				match T::Currency::transfer(
+					&project.consumer,
+					&Self::project_account(&project_id),
+					Self::calculate_deposit(project.quota),
+					ExistenceRequirement::KeepAlive,
+				) {
+					Ok(()) => {
+						let new_renewal_block = renewal_block.saturating_add(interval);
+						project.usage = 0;
+						project.renewal_block = Some(new_renewal_block);
+						project.last_deposit_block = now;
+						Self::deposit_event(Event::ProjectQuotaRenewed {
+							project_id: project_id.clone(),
+							new_renewal_block,
+						});
+					},
+					Err(_) => {
+						project.status = ProjectStatus::Expired;
+						Self::deposit_event(Event::ProjectRenewalFailed {
+							project_id: project_id.clone(),
+						});
+					},
+				}
+				Projects::<T>::insert(&project_id, project);
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		/// Remove projects without a deposit for `period` blocks which are used up or weren't
+		/// used over the period either. Suspended projects are kept for regulators to review.
+		fn expire_inactive_projects(now: T::BlockNumber, period: T::BlockNumber) -> Weight {
+			let is_stale = |block: T::BlockNumber| block.saturating_add(period) < now;
+			let mut reads: Weight = 0;
+			let expired: Vec<_> = Projects::<T>::iter()
+				.filter(|(_, project)| {
+					reads += 1;
+					project.status != ProjectStatus::Suspended &&
+						is_stale(project.last_deposit_block) &&
+						(project.quota <= project.usage || is_stale(project.last_usage_block))
+				})
+				.map(|(project_id, project)| (project_id, project.consumer))
+				.collect();
+
+			let removed = expired.len() as Weight;
+			for (project_id, consumer) in expired {
+				let escrow = Self::project_account(&project_id);
+				let _ = T::Currency::transfer(
+					&escrow,
+					&consumer,
+					T::Currency::free_balance(&escrow),
+					ExistenceRequirement::AllowDeath,
+				);
+				Self::deposit_event(Event::ProjectExpired { project_id: project_id.clone() });
+				Projects::<T>::remove(&project_id);
+				PendingProjectTransfers::<T>::remove(&project_id);
+			}
+			T::DbWeight::get().reads_writes(reads + 2 * removed, 4 * removed)
+		}
+	}
+}
+
+/// Quota price oracle reading the price set by root with `set_quota_price`.
+pub struct StorageValueOracle<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> Get<BalanceOf<T>> for StorageValueOracle<T> {
+	fn get() -> BalanceOf<T> {
+		QuotaPrice::<T>::get()
 	}
 }
 
@@ -504,8 +2197,13 @@ where
 		if let Some(local_call) = call.is_sub_type() {
 			match local_call {
 				Call::submit_project_usage { .. } |
+				Call::suspend_project { .. } |
 				Call::register_provider { .. } |
-				Call::report_provider_offence { .. } => {
+				Call::register_providers_batch { .. } |
+				Call::batch_register_providers { .. } |
+				Call::report_provider_offence { .. } |
+				Call::update_provider_score { .. } |
+				Call::record_provider_uptime { .. } => {
 					ensure!(<Regulators<T>>::get().contains(who), InvalidTransaction::BadSigner);
 				},
 				_ => {},
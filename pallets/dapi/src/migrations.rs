@@ -0,0 +1,264 @@
+//! Storage migrations for dAPI pallet.
+
+use crate::{
+	ChainIds, ChainMetadataOf, Config, Pallet, Project, ProjectStatus, Projects, Provider,
+	ProviderStatus, ProviderType, Providers,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	log,
+	storage::{unhashed, StoragePrefixedMap},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::Perbill;
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, vec};
+
+type ChainIdOf<T> = BoundedVec<u8, <T as Config>::MaxChainIdLength>;
+
+/// Project layout before subscriptions were introduced.
+#[derive(Encode, Decode)]
+struct OldProject<AccountId, ChainId> {
+	consumer: AccountId,
+	chain_id: ChainId,
+	quota: u128,
+	usage: u128,
+}
+
+type OldProjectOf<T> = OldProject<<T as frame_system::Config>::AccountId, ChainIdOf<T>>;
+
+/// Project layout before projects could cover multiple chains.
+#[derive(Encode, Decode)]
+struct ProjectV1<AccountId, ChainId, BlockNumber> {
+	consumer: AccountId,
+	chain_id: ChainId,
+	quota: u128,
+	usage: u128,
+	renewal_block: Option<BlockNumber>,
+	status: ProjectStatus,
+}
+
+type ProjectV1Of<T> = ProjectV1<
+	<T as frame_system::Config>::AccountId,
+	ChainIdOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// Project layout before inactive projects expired.
+#[derive(Encode, Decode)]
+struct ProjectV2<AccountId, ChainIds, BlockNumber> {
+	consumer: AccountId,
+	chain_ids: ChainIds,
+	quota: u128,
+	usage: u128,
+	renewal_block: Option<BlockNumber>,
+	status: ProjectStatus,
+}
+
+type ProjectV2Of<T> = ProjectV2<
+	<T as frame_system::Config>::AccountId,
+	BoundedVec<ChainIdOf<T>, <T as Config>::MaxChainsPerProject>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// Provider layout before SLA commitments were introduced.
+#[derive(Encode, Decode)]
+struct OldProvider<AccountId, ChainId> {
+	provider_type: ProviderType,
+	owner: AccountId,
+	chain_id: ChainId,
+	status: ProviderStatus,
+}
+
+type OldProviderOf<T> = OldProvider<<T as frame_system::Config>::AccountId, ChainIdOf<T>>;
+
+/// Wrap the single chain of a project into its list of chains.
+fn single_chain<T: Config>(
+	chain_id: ChainIdOf<T>,
+) -> BoundedVec<ChainIdOf<T>, T::MaxChainsPerProject> {
+	BoundedVec::try_from(vec![chain_id]).unwrap_or_default()
+}
+
+/// Migrates existing projects to the layout with subscription renewal. Existing projects are
+/// not subscriptions.
+///
+/// Projects are written straight in the latest layout, so this also stands for
+/// [`MigrateProjectsToV2`].
+pub struct MigrateProjectsToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProjectsToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			log::info!(target: "dapi", "Projects already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut translated: Weight = 0;
+		Projects::<T>::translate::<OldProjectOf<T>, _>(|_, old| {
+			translated += 1;
+			Some(Project {
+				consumer: old.consumer,
+				chain_ids: single_chain::<T>(old.chain_id),
+				quota: old.quota,
+				usage: old.usage,
+				renewal_block: None,
+				status: ProjectStatus::Active,
+				last_deposit_block: now,
+				last_usage_block: now,
+			})
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		log::info!(target: "dapi", "Migrated {} projects", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+/// Migrates existing projects to the layout with a list of chains, made of their single chain.
+pub struct MigrateProjectsToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProjectsToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			log::info!(target: "dapi", "Project chains already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut translated: Weight = 0;
+		Projects::<T>::translate::<ProjectV1Of<T>, _>(|_, old| {
+			translated += 1;
+			Some(Project {
+				consumer: old.consumer,
+				chain_ids: single_chain::<T>(old.chain_id),
+				quota: old.quota,
+				usage: old.usage,
+				renewal_block: old.renewal_block,
+				status: old.status,
+				last_deposit_block: now,
+				last_usage_block: now,
+			})
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		log::info!(target: "dapi", "Migrated chains of {} projects", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+/// Migrates the set of registered chain Ids to a map of chain Ids to their metadata. Existing
+/// chains get empty metadata.
+pub struct MigrateChainIdsToV3<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateChainIdsToV3<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			log::info!(target: "dapi", "Chain Ids already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		// The set was stored as a single value under the prefix of the map
+		let chain_ids: BTreeSet<ChainIdOf<T>> =
+			unhashed::take(&ChainIds::<T>::final_prefix()).unwrap_or_default();
+		let migrated = chain_ids.len() as Weight;
+		for chain_id in chain_ids {
+			ChainIds::<T>::insert(chain_id, ChainMetadataOf::<T>::default());
+		}
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		log::info!(target: "dapi", "Migrated {} chain Ids", migrated);
+		T::DbWeight::get().reads_writes(2, migrated + 2)
+	}
+}
+
+/// Migrates existing providers to the layout with an SLA commitment. Existing providers commit to
+/// nothing until they deposit again.
+pub struct MigrateProvidersToV4<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProvidersToV4<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 4 {
+			log::info!(target: "dapi", "Providers already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated: Weight = 0;
+		Providers::<T>::translate::<OldProviderOf<T>, _>(|_, old| {
+			translated += 1;
+			Some(Provider {
+				provider_type: old.provider_type,
+				owner: old.owner,
+				chain_id: old.chain_id,
+				status: old.status,
+				sla_commitment: Perbill::zero(),
+			})
+		});
+		StorageVersion::new(4).put::<Pallet<T>>();
+
+		log::info!(target: "dapi", "Migrated {} providers", translated);
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+}
+
+/// Migrates existing projects to the layout tracking their last deposit and usage. Existing
+/// projects count as active at the upgrade, so none expires before a full deposit period.
+pub struct MigrateProjectsToV5<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateProjectsToV5<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 5 {
+			log::info!(target: "dapi", "Project activity already migrated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut translated: Weight = 0;
+		Projects::<T>::translate::<ProjectV2Of<T>, _>(|_, old| {
+			translated += 1;
+			Some(Project {
+				consumer: old.consumer,
+				chain_ids: old.chain_ids,
+				quota: old.quota,
+				usage: old.usage,
+				renewal_block: old.renewal_block,
+				status: old.status,
+				last_deposit_block: now,
+				last_usage_block: now,
+			})
+		});
+		StorageVersion::new(5).put::<Pallet<T>>();
+
+		log::info!(target: "dapi", "Migrated activity of {} projects", translated);
+		T::DbWeight::get().reads_writes(translated + 2, translated + 1)
+	}
+}
+
+/// Populates the stake of the chains served by active providers, which ranks chains for block
+/// rewards.
+pub struct PopulateChainStakeToV6<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for PopulateChainStakeToV6<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 6 {
+			log::info!(target: "dapi", "Chain stake already populated, skipping");
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut providers: Weight = 0;
+		let mut indexed: Weight = 0;
+		for (provider_id, provider) in Providers::<T>::iter() {
+			providers += 1;
+			if provider.status == ProviderStatus::Active {
+				let chains = Pallet::<T>::chains_of(&provider_id, &provider);
+				Pallet::<T>::index_provider_stake(&provider_id, &[], &chains);
+				indexed += 1;
+			}
+		}
+		StorageVersion::new(6).put::<Pallet<T>>();
+
+		log::info!(target: "dapi", "Indexed the chain stake of {} active providers", indexed);
+		T::DbWeight::get().reads_writes(providers + 5 * indexed + 1, 4 * indexed + 1)
+	}
+}
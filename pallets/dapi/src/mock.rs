@@ -24,13 +24,26 @@ type Block = frame_system::mocking::MockBlock<TestRuntime>;
 
 pub(crate) const EXISTENTIAL_DEPOSIT: Balance = 2;
 pub(crate) const MIN_PROVIDER_STAKE: Balance = 10;
+pub(crate) const MAX_PROVIDER_BOND: Balance = 1000;
+pub(crate) const GATEWAY_MIN_STAKE: Balance = 10;
+pub(crate) const NODE_MIN_STAKE: Balance = 200;
+pub(crate) const MIN_REGULATOR_BOND: Balance = 5;
 pub(crate) const PROVIDER_REWARD_PERCENTAGE: u32 = 80;
 pub(crate) const MAX_NUMBER_OF_DELEGATORS: u32 = 5;
 pub(crate) const MIN_DELEGATOR_STAKE: Balance = 10;
+pub(crate) const MAX_DELEGATIONS_PER_DELEGATOR: u32 = 3;
 pub(crate) const MAX_UNLOCKING_CHUNKS: u32 = 4;
+pub(crate) const MAX_BATCH_REGISTRATIONS: u32 = 4;
+pub(crate) const MAX_PROVIDERS_PER_ERA_CLEAN: u32 = 2;
+pub(crate) const MAX_CLAIMABLE_ERA_AGE: u32 = 10;
+pub(crate) const PROVIDER_TRANSFER_PERIOD: BlockNumber = 10;
+pub(crate) const COMMISSION_CHANGE_DELAY: u32 = 2;
+pub(crate) const MAX_INACTIVE_ERAS: u32 = 4;
 pub(crate) const UNBONDING_PERIOD: EraIndex = 3;
 pub(crate) const MAX_ERA_STAKE_VALUES: u32 = 8;
 pub(crate) const BLOCKS_PER_ERA: u32 = 3;
+/// Amount of tokens needed to buy a single request.
+pub(crate) const QUOTA_PRICE: Balance = 1_000_000_000_000_000;
 
 construct_runtime!(
 	pub enum TestRuntime where
@@ -109,13 +122,24 @@ impl pallet_timestamp::Config for TestRuntime {
 
 parameter_types! {
 	pub const MinProviderStake: Balance = MIN_PROVIDER_STAKE;
+	pub const MaxProviderBond: Balance = MAX_PROVIDER_BOND;
 	pub const MaxDelegatorsPerProvider: u32 = MAX_NUMBER_OF_DELEGATORS;
 	pub const MinDelegatorStake: Balance = MIN_DELEGATOR_STAKE;
+	pub const MaxDelegationsPerDelegator: u32 = MAX_DELEGATIONS_PER_DELEGATOR;
 	pub const ProviderRewardsPercentage: Perbill = Perbill::from_percent(PROVIDER_REWARD_PERCENTAGE);
 	pub const DapiStakingPalletId: PalletId = PalletId(*b"mokdpstk");
 	pub const MaxUnlockingChunks: u32 = MAX_UNLOCKING_CHUNKS;
+	pub const MaxBatchRegistrations: u32 = MAX_BATCH_REGISTRATIONS;
+	pub const MaxProvidersPerEraClean: u32 = MAX_PROVIDERS_PER_ERA_CLEAN;
+	pub const MaxInactiveEras: u32 = MAX_INACTIVE_ERAS;
+	pub const MaxClaimableEraAge: u32 = MAX_CLAIMABLE_ERA_AGE;
+	pub const ProviderTransferPeriod: BlockNumber = PROVIDER_TRANSFER_PERIOD;
+	pub const CommissionChangeDelay: u32 = COMMISSION_CHANGE_DELAY;
+	pub const VestingPeriod: BlockNumber = 0;
+	pub const ScoreRewardMultiplier: Perbill = Perbill::zero();
+	pub const StakeEffectDelay: EraIndex = 0;
 	pub const UnbondingPeriod: EraIndex = UNBONDING_PERIOD;
-	pub const MaxEraStakeValues: u32 = MAX_ERA_STAKE_VALUES;
+	pub const DefaultMaxEraStakeValues: u32 = MAX_ERA_STAKE_VALUES;
 	pub const DefaultBlocksPerEra: u32 = BLOCKS_PER_ERA;
 }
 
@@ -126,17 +150,47 @@ impl pallet_dapi_staking::Config for TestRuntime {
 	type ProviderId = MassbitId;
 	type ProviderRewardsPercentage = ProviderRewardsPercentage;
 	type MinProviderStake = MinProviderStake;
+	type MaxProviderBond = MaxProviderBond;
 	type MaxDelegatorsPerProvider = MaxDelegatorsPerProvider;
 	type MinDelegatorStake = MinDelegatorStake;
-	type MaxEraStakeValues = MaxEraStakeValues;
+	type MaxDelegationsPerDelegator = MaxDelegationsPerDelegator;
+	type DefaultMaxEraStakeValues = DefaultMaxEraStakeValues;
+	type StakeEffectDelay = StakeEffectDelay;
 	type UnbondingPeriod = UnbondingPeriod;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxBatchRegistrations = MaxBatchRegistrations;
+	type MaxProvidersPerEraClean = MaxProvidersPerEraClean;
+	type MaxInactiveEras = MaxInactiveEras;
+	type MaxClaimableEraAge = MaxClaimableEraAge;
+	type MaxClaimHistory = ConstU32<20>;
+	type ProviderTransferPeriod = ProviderTransferPeriod;
+	type CommissionChangeDelay = CommissionChangeDelay;
+	type VestingPeriod = VestingPeriod;
+	type MaxVestingSchedules = ConstU32<8>;
+	type ProviderScore = ();
+	type ScoreRewardMultiplier = ScoreRewardMultiplier;
 	type PalletId = DapiStakingPalletId;
+	type ForceEraOrigin = EnsureRoot<AccountId>;
+	type SlashDestination = ();
+	type ExpiredRewardDestination = ();
+	type MaxProvidersExpiredPerBlock = ConstU32<8>;
 	type WeightInfo = pallet_dapi_staking::weights::SubstrateWeight<TestRuntime>;
 }
 
 parameter_types! {
-	pub const ProjectDepositPeriod: BlockNumber = 10;
+	pub const ProjectDepositPeriod: BlockNumber = 50;
+	pub const TransferWindowBlocks: BlockNumber = 10;
+	pub const RegistrationTimeout: BlockNumber = 10;
+	pub const RegulatorApprovalThreshold: Perbill = Perbill::from_percent(50);
+	pub const QuotaLowThreshold: Perbill = Perbill::from_percent(80);
+	pub const RegulatorProposalDuration: BlockNumber = 10;
+	pub const OffenceSlashRatio: Perbill = Perbill::from_percent(10);
+	pub const GatewayMinStake: Balance = GATEWAY_MIN_STAKE;
+	pub const NodeMinStake: Balance = NODE_MIN_STAKE;
+	pub const MinRegulatorBond: Balance = MIN_REGULATOR_BOND;
+	pub const MinQuotaPrice: Balance = QUOTA_PRICE / 10;
+	pub const MaxQuotaPrice: Balance = QUOTA_PRICE * 10;
+	pub const DapiPalletId: PalletId = PalletId(*b"prjescrw");
 }
 
 impl pallet_dapi::Config for TestRuntime {
@@ -145,7 +199,37 @@ impl pallet_dapi::Config for TestRuntime {
 	type DapiStaking = DapiStaking;
 	type UpdateOrigin = EnsureRoot<AccountId>;
 	type MaxChainIdLength = ConstU32<64>;
+	type MaxLogEntries = ConstU32<3>;
+	type MaxSupportedChains = ConstU32<2>;
+	type MaxChainsPerProject = ConstU32<2>;
+	type MaxBatchChainIds = ConstU32<4>;
+	type MaxBatchProviders = ConstU32<4>;
+	type RatingCooldownEras = ConstU32<2>;
+	type MaxNameLength = ConstU32<16>;
+	type MaxEndpointLength = ConstU32<32>;
+	type MaxDescriptionLength = ConstU32<64>;
+	type MaxChainMetadataLength = ConstU32<16>;
+	type MaxUrlLength = ConstU32<32>;
+	type MaxReasonLength = ConstU32<32>;
+	type TransferWindowBlocks = TransferWindowBlocks;
+	type RegistrationTimeout = RegistrationTimeout;
+	type ProjectDepositPeriod = ProjectDepositPeriod;
+	type QuotaOracle = pallet_dapi::StorageValueOracle<TestRuntime>;
+	type MinQuotaPrice = MinQuotaPrice;
+	type MaxQuotaPrice = MaxQuotaPrice;
+	type QuotaLowThreshold = QuotaLowThreshold;
+	type RegulatorApprovalThreshold = RegulatorApprovalThreshold;
+	type RegulatorProposalDuration = RegulatorProposalDuration;
+	type MaxRegulatorVoters = ConstU32<4>;
+	type MaxRegulatorsPerBatch = ConstU32<3>;
+	type OffenceSlashRatio = OffenceSlashRatio;
+	type GatewayMinStake = GatewayMinStake;
+	type NodeMinStake = NodeMinStake;
+	type SlaEvaluationPeriod = ConstU32<4>;
+	type MinRegulatorBond = MinRegulatorBond;
+	type RegulatorSlashDestination = ();
 	type MassbitId = MassbitId;
+	type PalletId = DapiPalletId;
 	type OnProjectPayment = ();
 	type WeightInfo = weights::SubstrateWeight<TestRuntime>;
 }
@@ -173,6 +257,7 @@ impl ExternalityBuilder {
 				(20, 10),
 				(540, EXISTENTIAL_DEPOSIT),
 				(1337, 1_000_000_000_000),
+				(1338, 1_000_000 * QUOTA_PRICE),
 			],
 		}
 		.assimilate_storage(&mut storage)
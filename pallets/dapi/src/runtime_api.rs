@@ -0,0 +1,33 @@
+//! Runtime API definition for dAPI pallet.
+
+use crate::types::ChainMetadata;
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DapiApi<MassbitId, Balance> where
+		MassbitId: Codec,
+		Balance: Codec,
+	{
+		/// Returns all chains served by a provider, its primary chain first.
+		fn provider_chains(provider_id: MassbitId) -> Vec<Vec<u8>>;
+
+		/// Returns the providers serving `chain_id`, either as primary or additional chain.
+		fn providers_by_chain(chain_id: Vec<u8>) -> Vec<MassbitId>;
+
+		/// Returns whether `chain_id` is registered.
+		fn chain_id_exists(chain_id: Vec<u8>) -> bool;
+
+		/// Returns the metadata of `chain_id`, if it is registered.
+		fn chain_metadata(chain_id: Vec<u8>) -> Option<ChainMetadata<Vec<u8>, Vec<u8>>>;
+
+		/// Returns the average consumer rating of a provider in tenths, if it was rated.
+		fn provider_average_rating(provider_id: MassbitId) -> Option<u32>;
+
+		/// Returns the deposit buying `target_quota` requests at the current quota price.
+		fn estimate_deposit_for_quota(target_quota: u128) -> Balance;
+
+		/// Returns the quota bought by `deposit` at the current quota price.
+		fn calculate_quota_for_deposit(deposit: Balance) -> u128;
+	}
+}
@@ -1 +1,1937 @@
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ConstU32, Currency, OnInitialize},
+	BoundedVec,
+};
+use sp_runtime::{traits::BadOrigin, Perbill};
 
+use crate::{
+	mock::{self, *},
+	traits::QuotaDeduction,
+	ChainIds, ChainMetadata, Error, Event, ProjectStatus, ProviderDeactivateReason,
+	ProviderMetadataExtra, ProviderRegistration, ProviderStatus, ProviderType, RegulatorChange,
+	UptimeRecord,
+};
+use common::MassbitId;
+use pallet_dapi_staking::traits::{DapiStakingRegistration, MAX_PROVIDER_SCORE};
+
+const CONSUMER: AccountId = 1338;
+const REGULATOR: AccountId = 1;
+
+fn chain_id() -> Vec<u8> {
+	"eth.mainnet".into()
+}
+
+/// Build a Massbit Id in the UUID format from the given seed.
+fn massbit_id(seed: u8) -> MassbitId {
+	MassbitId::from_uuid_str(&format!("{:08x}-0000-0000-0000-000000000000", seed)).unwrap()
+}
+
+fn initialize() {
+	assert_ok!(Dapi::add_chain_id(Origin::root(), chain_id(), Default::default()));
+	bond_regulator(REGULATOR);
+	assert_ok!(Dapi::add_regulator(Origin::root(), REGULATOR));
+}
+
+fn bond_regulator(who: AccountId) {
+	assert_ok!(Dapi::bond_as_regulator(Origin::signed(who), MIN_REGULATOR_BOND));
+}
+
+/// Stake 600 in total, of which accounts 3 and 9 delegate 300 and 200.
+fn setup_stakers() {
+	let provider_id = massbit_id(9);
+	assert_ok!(<DapiStaking as DapiStakingRegistration<_, _, _>>::register_provider(
+		10,
+		provider_id,
+		100,
+		MIN_PROVIDER_STAKE
+	));
+	assert_ok!(DapiStaking::delegate(Origin::signed(3), provider_id, 300));
+	assert_ok!(DapiStaking::delegate(Origin::signed(9), provider_id, 200));
+}
+
+fn register_project(project_id: MassbitId, deposit: Balance) {
+	assert_ok!(Dapi::register_project(
+		Origin::signed(CONSUMER),
+		project_id,
+		vec![chain_id()],
+		deposit,
+		false
+	));
+}
+
+#[test]
+fn quota_deduction_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		register_project(project_id, 100 * QUOTA_PRICE);
+
+		assert_ok!(Dapi::deduct(project_id, 30 * QUOTA_PRICE));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectQuotaDeducted {
+			project_id,
+			quota: 30,
+		}));
+		assert_eq!(Dapi::projects(project_id).unwrap().quota, 70);
+	})
+}
+
+#[test]
+fn suspended_project_usage_is_rejected() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		register_project(project_id, 100 * QUOTA_PRICE);
+		let reason: BoundedVec<u8, ConstU32<32>> = b"abuse".to_vec().try_into().unwrap();
+
+		assert_noop!(
+			Dapi::suspend_project(Origin::signed(CONSUMER), project_id, reason.clone()),
+			Error::<TestRuntime>::PermissionDenied
+		);
+		assert_ok!(Dapi::suspend_project(Origin::signed(REGULATOR), project_id, reason.clone()));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectSuspended {
+			project_id,
+			reason: reason.to_vec(),
+		}));
+		assert_eq!(Dapi::projects(project_id).unwrap().status, ProjectStatus::Suspended);
+		assert_noop!(
+			Dapi::suspend_project(Origin::signed(REGULATOR), project_id, reason),
+			Error::<TestRuntime>::ProjectSuspended
+		);
+		assert_noop!(
+			Dapi::submit_project_usage(Origin::signed(REGULATOR), project_id, chain_id(), 10),
+			Error::<TestRuntime>::ProjectSuspended
+		);
+
+		assert_noop!(Dapi::unsuspend_project(Origin::signed(REGULATOR), project_id), BadOrigin);
+		assert_ok!(Dapi::unsuspend_project(Origin::root(), project_id));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectUnsuspended { project_id }));
+		assert_noop!(
+			Dapi::unsuspend_project(Origin::root(), project_id),
+			Error::<TestRuntime>::ProjectNotSuspended
+		);
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			10
+		));
+		assert_eq!(Dapi::projects(project_id).unwrap().quota, 100);
+	})
+}
+
+#[test]
+fn quota_deduction_with_insufficient_quota_fails() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		register_project(project_id, 100 * QUOTA_PRICE);
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			80
+		));
+
+		assert_noop!(
+			Dapi::deduct(project_id, 21 * QUOTA_PRICE),
+			Error::<TestRuntime>::InsufficientQuota
+		);
+		assert_noop!(Dapi::deduct(massbit_id(2), QUOTA_PRICE), Error::<TestRuntime>::ProjectDNE);
+
+		assert_ok!(Dapi::deduct(project_id, 20 * QUOTA_PRICE));
+		assert_eq!(Dapi::projects(project_id).unwrap().quota, 80);
+	})
+}
+
+#[test]
+fn refund_project_only_returns_unused_quota() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		let escrow = Dapi::project_account(&project_id);
+		register_project(project_id, 100 * QUOTA_PRICE);
+		assert_eq!(Balances::free_balance(&escrow), 100 * QUOTA_PRICE);
+
+		// Used quota is paid out of the escrow account
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			40
+		));
+		assert_eq!(Balances::free_balance(&escrow), 60 * QUOTA_PRICE);
+
+		assert_noop!(
+			Dapi::refund_project(Origin::signed(REGULATOR), project_id, 10 * QUOTA_PRICE),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			Dapi::refund_project(Origin::signed(CONSUMER), project_id, 61 * QUOTA_PRICE),
+			Error::<TestRuntime>::InsufficientQuota
+		);
+
+		let consumer_balance = Balances::free_balance(&CONSUMER);
+		assert_ok!(Dapi::refund_project(Origin::signed(CONSUMER), project_id, 50 * QUOTA_PRICE));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectRefunded {
+			project_id,
+			amount: 50 * QUOTA_PRICE,
+			remaining_quota: 50,
+		}));
+		let project = Dapi::projects(project_id).unwrap();
+		assert_eq!((project.quota, project.usage), (50, 40));
+		assert_eq!(Balances::free_balance(&CONSUMER), consumer_balance + 50 * QUOTA_PRICE);
+		assert_eq!(Balances::free_balance(&escrow), 10 * QUOTA_PRICE);
+	})
+}
+
+#[test]
+fn multi_chain_project_shares_quota() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let polygon: Vec<u8> = "polygon.mainnet".into();
+		let bsc: Vec<u8> = "bsc.mainnet".into();
+		for chain in [&polygon, &bsc] {
+			assert_ok!(Dapi::add_chain_id(Origin::root(), chain.clone(), Default::default()));
+		}
+		let project_id = massbit_id(1);
+
+		assert_noop!(
+			Dapi::register_project(
+				Origin::signed(CONSUMER),
+				project_id,
+				vec![chain_id(), chain_id()],
+				100 * QUOTA_PRICE,
+				false
+			),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		assert_noop!(
+			Dapi::register_project(
+				Origin::signed(CONSUMER),
+				project_id,
+				vec![chain_id(), polygon.clone(), bsc.clone()],
+				100 * QUOTA_PRICE,
+				false
+			),
+			Error::<TestRuntime>::TooManyProjectChains
+		);
+		assert_ok!(Dapi::register_project(
+			Origin::signed(CONSUMER),
+			project_id,
+			vec![chain_id(), polygon.clone()],
+			100 * QUOTA_PRICE,
+			false
+		));
+
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			30
+		));
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			polygon.clone(),
+			20
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectUsageUpdated {
+			project_id,
+			chain_id: polygon,
+			usage: 50,
+		}));
+		assert_noop!(
+			Dapi::submit_project_usage(Origin::signed(REGULATOR), project_id, bsc, 20),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		assert_eq!(Dapi::projects(project_id).unwrap().usage, 50);
+	})
+}
+
+#[test]
+fn project_quota_alerts_are_emitted_on_threshold_crossing() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		register_project(project_id, 100 * QUOTA_PRICE);
+
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			80
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectUsageUpdated {
+			project_id,
+			chain_id: chain_id(),
+			usage: 80,
+		}));
+
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			5
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectQuotaLow {
+			project_id,
+			remaining_quota: 15,
+		}));
+
+		// No repeated alert while staying above the threshold
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			5
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectUsageUpdated {
+			project_id,
+			chain_id: chain_id(),
+			usage: 90,
+		}));
+
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			20
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectQuotaExhausted { project_id }));
+		assert_eq!(Dapi::projects(project_id).unwrap().usage, 100);
+	})
+}
+
+#[test]
+fn project_transfer_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		let new_owner = 2;
+		register_project(project_id, 100 * QUOTA_PRICE);
+
+		assert_noop!(
+			Dapi::transfer_project(Origin::signed(new_owner), project_id, new_owner),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			Dapi::accept_project_transfer(Origin::signed(new_owner), project_id),
+			Error::<TestRuntime>::NoPendingTransfer
+		);
+
+		assert_ok!(Dapi::transfer_project(Origin::signed(CONSUMER), project_id, new_owner));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectTransferInitiated {
+			project_id,
+			old_owner: CONSUMER,
+			new_owner,
+		}));
+		assert_noop!(
+			Dapi::accept_project_transfer(Origin::signed(3), project_id),
+			Error::<TestRuntime>::PermissionDenied
+		);
+
+		assert_ok!(Dapi::accept_project_transfer(Origin::signed(new_owner), project_id));
+		System::assert_last_event(mock::Event::Dapi(Event::ProjectTransferred {
+			project_id,
+			old_owner: CONSUMER,
+			new_owner,
+		}));
+		let project = Dapi::projects(project_id).unwrap();
+		assert_eq!(project.consumer, new_owner);
+		assert_eq!(project.quota, 100);
+		assert!(Dapi::pending_project_transfers(project_id).is_none());
+	})
+}
+
+#[test]
+fn project_transfer_expires() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		let new_owner = 2;
+		register_project(project_id, 100 * QUOTA_PRICE);
+
+		assert_ok!(Dapi::transfer_project(Origin::signed(CONSUMER), project_id, new_owner));
+		System::set_block_number(System::block_number() + TransferWindowBlocks::get() + 1);
+		assert_noop!(
+			Dapi::accept_project_transfer(Origin::signed(new_owner), project_id),
+			Error::<TestRuntime>::TransferExpired
+		);
+		assert_eq!(Dapi::projects(project_id).unwrap().consumer, CONSUMER);
+	})
+}
+
+#[test]
+fn provider_registration_requires_owner_consent() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let provider_id = massbit_id(1);
+
+		assert_noop!(
+			Dapi::propose_provider_registration(
+				Origin::signed(owner),
+				provider_id,
+				ProviderType::Gateway,
+				owner,
+				chain_id()
+			),
+			Error::<TestRuntime>::PermissionDenied
+		);
+		assert_noop!(
+			Dapi::propose_provider_registration(
+				Origin::signed(REGULATOR),
+				provider_id,
+				ProviderType::Gateway,
+				owner,
+				"unknown.chain".into()
+			),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		assert_noop!(
+			Dapi::accept_provider_registration(Origin::signed(owner), provider_id),
+			Error::<TestRuntime>::NoPendingRegistration
+		);
+
+		assert_ok!(Dapi::propose_provider_registration(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			owner,
+			chain_id()
+		));
+		let expiry = System::block_number() + RegistrationTimeout::get();
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderRegistrationProposed {
+			provider_id,
+			owner,
+			expiry,
+		}));
+		assert_noop!(
+			Dapi::deposit_provider(
+				Origin::signed(owner),
+				provider_id,
+				GATEWAY_MIN_STAKE,
+				Perbill::zero()
+			),
+			Error::<TestRuntime>::ProviderDNE
+		);
+		assert_noop!(
+			Dapi::accept_provider_registration(Origin::signed(3), provider_id),
+			Error::<TestRuntime>::PermissionDenied
+		);
+
+		assert_ok!(Dapi::accept_provider_registration(Origin::signed(owner), provider_id));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderRegistered {
+			provider_id,
+			provider_type: ProviderType::Gateway,
+			owner,
+			chain_id: chain_id(),
+		}));
+		assert!(Dapi::pending_provider_registrations(provider_id).is_none());
+		assert_eq!(Dapi::providers(provider_id).unwrap().status, ProviderStatus::Registered);
+		assert_ok!(Dapi::deposit_provider(
+			Origin::signed(owner),
+			provider_id,
+			GATEWAY_MIN_STAKE,
+			Perbill::zero()
+		));
+
+		assert_noop!(
+			Dapi::propose_provider_registration(
+				Origin::signed(REGULATOR),
+				provider_id,
+				ProviderType::Gateway,
+				owner,
+				chain_id()
+			),
+			Error::<TestRuntime>::AlreadyExist
+		);
+	})
+}
+
+#[test]
+fn provider_registration_proposal_expires() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let provider_id = massbit_id(1);
+
+		assert_ok!(Dapi::propose_provider_registration(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Node,
+			owner,
+			chain_id()
+		));
+		System::set_block_number(System::block_number() + RegistrationTimeout::get() + 1);
+		assert_noop!(
+			Dapi::accept_provider_registration(Origin::signed(owner), provider_id),
+			Error::<TestRuntime>::RegistrationExpired
+		);
+		assert!(Dapi::providers(provider_id).is_none());
+	})
+}
+
+#[test]
+fn regulator_actions_are_logged() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		register_project(project_id, 100 * QUOTA_PRICE);
+
+		for block in 1..=4 {
+			System::set_block_number(block);
+			assert_ok!(Dapi::submit_project_usage(
+				Origin::signed(REGULATOR),
+				project_id,
+				chain_id(),
+				1
+			));
+		}
+
+		// Only the most recent entries are kept
+		assert_eq!(
+			Dapi::regulator_action_log(REGULATOR).into_inner(),
+			vec![(project_id, 2), (project_id, 3), (project_id, 4)]
+		);
+	})
+}
+
+#[test]
+fn remove_regulator_clears_action_log() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		register_project(project_id, 100 * QUOTA_PRICE);
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			1
+		));
+		assert_ok!(Dapi::submit_project_usage(
+			Origin::signed(REGULATOR),
+			project_id,
+			chain_id(),
+			1
+		));
+
+		assert_ok!(Dapi::remove_regulator(Origin::root(), REGULATOR));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorActionsRetracted {
+			who: REGULATOR,
+			retracted_count: 2,
+		}));
+		assert!(Dapi::regulator_action_log(REGULATOR).is_empty());
+		assert_noop!(
+			Dapi::submit_project_usage(Origin::signed(REGULATOR), project_id, chain_id(), 1),
+			Error::<TestRuntime>::PermissionDenied
+		);
+	})
+}
+
+#[test]
+fn batch_regulator_updates_are_atomic() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let (first, second) = (7, 8);
+		bond_regulator(first);
+
+		assert_noop!(
+			Dapi::batch_add_regulators(Origin::root(), vec![first, REGULATOR].try_into().unwrap()),
+			Error::<TestRuntime>::AlreadyExist
+		);
+		assert_noop!(
+			Dapi::batch_add_regulators(Origin::root(), vec![first, second].try_into().unwrap()),
+			Error::<TestRuntime>::RegulatorNotBonded
+		);
+		assert!(!Dapi::regulators().contains(&first));
+
+		bond_regulator(second);
+
+		assert_ok!(Dapi::batch_add_regulators(
+			Origin::root(),
+			vec![first, second].try_into().unwrap()
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorsUpdated {
+			added: vec![first, second],
+			removed: vec![],
+		}));
+
+		assert_noop!(
+			Dapi::batch_remove_regulators(Origin::root(), vec![first, 23].try_into().unwrap()),
+			Error::<TestRuntime>::PermissionDenied
+		);
+		assert_ok!(Dapi::batch_remove_regulators(
+			Origin::root(),
+			vec![first, second].try_into().unwrap()
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorsUpdated {
+			added: vec![],
+			removed: vec![first, second],
+		}));
+		assert!(Dapi::regulators().contains(&REGULATOR));
+		assert!(!Dapi::regulators().contains(&first));
+	})
+}
+
+#[test]
+fn malformed_massbit_ids_are_rejected() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let malformed = MassbitId::decode(&mut &[1; 36][..]).unwrap();
+
+		assert_noop!(
+			Dapi::register_project(
+				Origin::signed(CONSUMER),
+				malformed,
+				vec![chain_id()],
+				100 * QUOTA_PRICE,
+				false
+			),
+			Error::<TestRuntime>::InvalidMassbitId
+		);
+		assert_noop!(
+			Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				malformed,
+				ProviderType::Gateway,
+				2,
+				chain_id()
+			),
+			Error::<TestRuntime>::InvalidMassbitId
+		);
+		assert_noop!(
+			Dapi::propose_provider_registration(
+				Origin::signed(REGULATOR),
+				malformed,
+				ProviderType::Gateway,
+				2,
+				chain_id()
+			),
+			Error::<TestRuntime>::InvalidMassbitId
+		);
+	})
+}
+
+#[test]
+fn provider_chains_are_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 2;
+		let provider_id = massbit_id(1);
+		let chains: Vec<Vec<u8>> = vec!["dot.mainnet".into(), "ksm.mainnet".into()];
+		for chain in chains.iter() {
+			assert_ok!(Dapi::add_chain_id(Origin::root(), chain.clone(), Default::default()));
+		}
+		assert_ok!(Dapi::add_chain_id(Origin::root(), "bsc.mainnet".into(), Default::default()));
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			owner,
+			chain_id()
+		));
+
+		assert_noop!(
+			Dapi::add_provider_chain(Origin::signed(3), provider_id, chains[0].clone()),
+			Error::<TestRuntime>::NotOwner
+		);
+		assert_noop!(
+			Dapi::add_provider_chain(Origin::signed(owner), provider_id, "sol.mainnet".into()),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		assert_noop!(
+			Dapi::add_provider_chain(Origin::signed(owner), provider_id, chain_id()),
+			Error::<TestRuntime>::AlreadyExist
+		);
+
+		for chain in chains.iter() {
+			assert_ok!(Dapi::add_provider_chain(Origin::signed(owner), provider_id, chain.clone()));
+			System::assert_last_event(mock::Event::Dapi(Event::ProviderChainAdded {
+				provider_id,
+				chain_id: chain.clone(),
+			}));
+		}
+		assert_noop!(
+			Dapi::add_provider_chain(Origin::signed(owner), provider_id, chains[0].clone()),
+			Error::<TestRuntime>::AlreadyExist
+		);
+		assert_noop!(
+			Dapi::add_provider_chain(Origin::signed(owner), provider_id, "bsc.mainnet".into()),
+			Error::<TestRuntime>::TooManyProviderChains
+		);
+		assert_eq!(
+			Dapi::all_provider_chains(provider_id),
+			vec![chain_id(), chains[0].clone(), chains[1].clone()]
+		);
+
+		assert_ok!(Dapi::remove_provider_chain(
+			Origin::signed(owner),
+			provider_id,
+			chains[0].clone()
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderChainRemoved {
+			provider_id,
+			chain_id: chains[0].clone(),
+		}));
+		assert_noop!(
+			Dapi::remove_provider_chain(Origin::signed(owner), provider_id, chains[0].clone()),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		assert_eq!(Dapi::all_provider_chains(provider_id), vec![chain_id(), chains[1].clone()]);
+	})
+}
+
+#[test]
+fn providers_by_chain_includes_additional_chains() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let dot: Vec<u8> = "dot.mainnet".into();
+		assert_ok!(Dapi::add_chain_id(Origin::root(), dot.clone(), Default::default()));
+		for (seed, chain) in [(1, chain_id()), (2, dot.clone()), (3, chain_id())] {
+			assert_ok!(Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				massbit_id(seed),
+				ProviderType::Node,
+				2,
+				chain
+			));
+		}
+		assert_ok!(Dapi::add_provider_chain(Origin::signed(2), massbit_id(1), dot.clone()));
+
+		let mut providers = Dapi::providers_by_chain(dot);
+		providers.sort_by_key(|id| id.encode());
+		assert_eq!(providers, vec![massbit_id(1), massbit_id(2)]);
+		assert_eq!(Dapi::providers_by_chain(chain_id()).len(), 2);
+		assert!(Dapi::providers_by_chain("sol.mainnet".into()).is_empty());
+	})
+}
+
+#[test]
+fn update_provider_metadata_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 2;
+		let provider_id = massbit_id(1);
+		let metadata = ProviderMetadataExtra {
+			name: b"massbit".to_vec(),
+			endpoint: b"https://eth.massbit.io".to_vec(),
+			description: b"Ethereum gateway".to_vec(),
+		};
+		assert_noop!(
+			Dapi::update_provider_metadata(Origin::signed(owner), provider_id, metadata.clone()),
+			Error::<TestRuntime>::ProviderDNE
+		);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			owner,
+			chain_id()
+		));
+
+		assert_noop!(
+			Dapi::update_provider_metadata(Origin::signed(3), provider_id, metadata.clone()),
+			Error::<TestRuntime>::NotOwner
+		);
+		let too_long = ProviderMetadataExtra { name: vec![b'n'; 17], ..metadata.clone() };
+		assert_noop!(
+			Dapi::update_provider_metadata(Origin::signed(owner), provider_id, too_long),
+			Error::<TestRuntime>::MetadataTooLong
+		);
+
+		assert_ok!(Dapi::update_provider_metadata(
+			Origin::signed(owner),
+			provider_id,
+			metadata.clone()
+		));
+		let stored = Dapi::provider_metadata(provider_id).unwrap();
+		assert_eq!(stored.name.into_inner(), metadata.name);
+		assert_eq!(stored.endpoint.into_inner(), metadata.endpoint);
+		assert_eq!(stored.description.into_inner(), metadata.description);
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderMetadataUpdated {
+			provider_id,
+		}));
+	})
+}
+
+#[test]
+fn update_provider_score_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let provider_id = massbit_id(1);
+		assert_noop!(
+			Dapi::update_provider_score(Origin::signed(REGULATOR), provider_id, 5000),
+			Error::<TestRuntime>::ProviderDNE
+		);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Node,
+			2,
+			chain_id()
+		));
+
+		assert_noop!(
+			Dapi::update_provider_score(Origin::signed(2), provider_id, 5000),
+			Error::<TestRuntime>::PermissionDenied
+		);
+		assert_noop!(
+			Dapi::update_provider_score(
+				Origin::signed(REGULATOR),
+				provider_id,
+				MAX_PROVIDER_SCORE + 1
+			),
+			Error::<TestRuntime>::InvalidScore
+		);
+
+		assert_ok!(Dapi::update_provider_score(Origin::signed(REGULATOR), provider_id, 5000));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderScoreUpdated {
+			provider_id,
+			old_score: None,
+			new_score: 5000,
+		}));
+		assert_ok!(Dapi::update_provider_score(Origin::signed(REGULATOR), provider_id, 7000));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderScoreUpdated {
+			provider_id,
+			old_score: Some(5000),
+			new_score: 7000,
+		}));
+		assert_eq!(Dapi::provider_score(provider_id), Some(7000));
+	})
+}
+
+/// Run blocks up to `n`, triggering dAPI's `on_initialize`.
+fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Dapi::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn set_monthly_reset_interval_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_noop!(Dapi::set_monthly_reset_interval(Origin::signed(REGULATOR), 10), BadOrigin);
+		assert_noop!(
+			Dapi::set_monthly_reset_interval(Origin::root(), 0),
+			Error::<TestRuntime>::InvalidResetInterval
+		);
+		assert_ok!(Dapi::set_monthly_reset_interval(Origin::root(), 10));
+		System::assert_last_event(mock::Event::Dapi(Event::MonthlyResetIntervalSet {
+			interval: 10,
+		}));
+		assert_eq!(Dapi::monthly_reset_interval(), Some(10));
+	})
+}
+
+#[test]
+fn quota_price_is_set_by_root() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let project_id = massbit_id(1);
+		let new_price = 2 * QUOTA_PRICE;
+
+		assert_noop!(Dapi::set_quota_price(Origin::signed(REGULATOR), new_price), BadOrigin);
+		for price in [QUOTA_PRICE / 10 - 1, QUOTA_PRICE * 10 + 1] {
+			assert_noop!(
+				Dapi::set_quota_price(Origin::root(), price),
+				Error::<TestRuntime>::InvalidQuotaPrice
+			);
+		}
+		assert_ok!(Dapi::set_quota_price(Origin::root(), new_price));
+		System::assert_last_event(mock::Event::Dapi(Event::QuotaPriceUpdated {
+			old_price: QUOTA_PRICE,
+			new_price,
+		}));
+
+		register_project(project_id, 100 * QUOTA_PRICE);
+		assert_eq!(Dapi::projects(project_id).unwrap().quota, 50);
+	})
+}
+
+#[test]
+fn deposit_estimate_buys_target_quota() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(Dapi::calculate_deposit(100), 100 * QUOTA_PRICE);
+		assert_eq!(Dapi::calculate_quota(Dapi::calculate_deposit(100)), 100);
+		assert_eq!(Dapi::calculate_quota(Dapi::calculate_deposit(100) - 1), 99);
+
+		assert_ok!(Dapi::set_quota_price(Origin::root(), 2 * QUOTA_PRICE));
+		assert_eq!(Dapi::calculate_deposit(100), 200 * QUOTA_PRICE);
+		assert_eq!(Dapi::calculate_quota(Dapi::calculate_deposit(100)), 100);
+	})
+}
+
+#[test]
+fn subscription_quota_renewal_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let poor_consumer = 9;
+		let (project_1, project_2, project_3) = (massbit_id(1), massbit_id(2), massbit_id(3));
+		let deposit = 100 * QUOTA_PRICE;
+		Balances::make_free_balance_be(&poor_consumer, 150 * QUOTA_PRICE);
+
+		assert_noop!(
+			Dapi::register_project(
+				Origin::signed(CONSUMER),
+				project_1,
+				vec![chain_id()],
+				deposit,
+				true
+			),
+			Error::<TestRuntime>::SubscriptionUnavailable
+		);
+		assert_ok!(Dapi::set_monthly_reset_interval(Origin::root(), 10));
+		assert_ok!(Dapi::register_project(
+			Origin::signed(CONSUMER),
+			project_1,
+			vec![chain_id()],
+			deposit,
+			true
+		));
+		assert_ok!(Dapi::register_project(
+			Origin::signed(poor_consumer),
+			project_2,
+			vec![chain_id()],
+			deposit,
+			true
+		));
+		register_project(project_3, deposit);
+		assert_eq!(Dapi::projects(project_1).unwrap().renewal_block, Some(11));
+		assert_eq!(Dapi::projects(project_3).unwrap().renewal_block, None);
+		for project_id in [project_1, project_3] {
+			assert_ok!(Dapi::submit_project_usage(
+				Origin::signed(REGULATOR),
+				project_id,
+				chain_id(),
+				40
+			));
+		}
+
+		// Renewal is only checked at interval boundaries
+		let consumer_balance = Balances::free_balance(&CONSUMER);
+		run_to_block(19);
+		assert_eq!(Dapi::projects(project_1).unwrap().usage, 40);
+
+		run_to_block(20);
+		System::assert_has_event(mock::Event::Dapi(Event::ProjectQuotaRenewed {
+			project_id: project_1,
+			new_renewal_block: 21,
+		}));
+		System::assert_has_event(mock::Event::Dapi(Event::ProjectRenewalFailed {
+			project_id: project_2,
+		}));
+		let project = Dapi::projects(project_1).unwrap();
+		assert_eq!((project.usage, project.quota, project.renewal_block), (0, 100, Some(21)));
+		assert_eq!(Balances::free_balance(&CONSUMER), consumer_balance - deposit);
+		assert_eq!(Dapi::projects(project_2).unwrap().status, ProjectStatus::Expired);
+		assert_eq!(Dapi::projects(project_3).unwrap().usage, 40);
+
+		run_to_block(30);
+		assert_eq!(Dapi::projects(project_1).unwrap().renewal_block, Some(31));
+		assert_eq!(Balances::free_balance(&CONSUMER), consumer_balance - 2 * deposit);
+		assert_eq!(Dapi::projects(project_2).unwrap().renewal_block, Some(11));
+	})
+}
+
+#[test]
+fn inactive_projects_expire() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let (used_up, in_use, abandoned) = (massbit_id(1), massbit_id(2), massbit_id(3));
+		let deposit = 100 * QUOTA_PRICE;
+		for project_id in [used_up, in_use, abandoned] {
+			register_project(project_id, deposit);
+		}
+		assert_ok!(Dapi::submit_project_usage(Origin::signed(REGULATOR), used_up, chain_id(), 100));
+
+		run_to_block(60);
+		assert_ok!(Dapi::submit_project_usage(Origin::signed(REGULATOR), in_use, chain_id(), 10));
+		assert!(Dapi::projects(used_up).is_some());
+
+		// Expiry is only checked at deposit period boundaries
+		let consumer_balance = Balances::free_balance(&CONSUMER);
+		run_to_block(100);
+		for project_id in [used_up, abandoned] {
+			System::assert_has_event(mock::Event::Dapi(Event::ProjectExpired { project_id }));
+			assert_eq!(Dapi::projects(project_id), None);
+		}
+		assert!(Dapi::projects(in_use).is_some());
+		// The unused quota of the abandoned project is refunded
+		assert_eq!(Balances::free_balance(&CONSUMER), consumer_balance + deposit);
+		assert_eq!(Balances::free_balance(&Dapi::project_account(&abandoned)), 0);
+	})
+}
+
+#[test]
+fn add_chain_ids_batch_skips_duplicates() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let chain_ids: Vec<Vec<u8>> =
+			vec![chain_id(), "dot.mainnet".into(), "ksm.mainnet".into(), "dot.mainnet".into()];
+
+		assert_noop!(
+			Dapi::add_chain_ids_batch(
+				Origin::signed(REGULATOR),
+				chain_ids.clone().try_into().unwrap()
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Dapi::add_chain_ids_batch(Origin::root(), vec![vec![0u8; 65]].try_into().unwrap()),
+			Error::<TestRuntime>::InvalidChainId
+		);
+
+		assert_ok!(Dapi::add_chain_ids_batch(Origin::root(), chain_ids.try_into().unwrap()));
+		System::assert_last_event(mock::Event::Dapi(Event::ChainIdsBatchAdded {
+			added: vec!["dot.mainnet".into(), "ksm.mainnet".into()],
+			skipped: vec![chain_id(), "dot.mainnet".into()],
+		}));
+		assert_eq!(ChainIds::<TestRuntime>::iter().count(), 3);
+		assert!(Dapi::chain_id_exists("ksm.mainnet".into()));
+		assert!(!Dapi::chain_id_exists("sol.mainnet".into()));
+	})
+}
+
+#[test]
+fn remove_chain_ids_batch_skips_chains_in_use() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let (dot, ksm, bsc): (Vec<u8>, Vec<u8>, Vec<u8>) =
+			("dot.mainnet".into(), "ksm.mainnet".into(), "bsc.mainnet".into());
+		assert_ok!(Dapi::add_chain_ids_batch(
+			Origin::root(),
+			vec![dot.clone(), ksm.clone(), bsc.clone()].try_into().unwrap()
+		));
+		// `eth.mainnet` is used by a project and `dot.mainnet` by a provider
+		register_project(massbit_id(1), 100 * QUOTA_PRICE);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			massbit_id(2),
+			ProviderType::Node,
+			2,
+			dot.clone()
+		));
+
+		assert_ok!(Dapi::remove_chain_ids_batch(
+			Origin::root(),
+			vec![chain_id(), dot.clone(), ksm.clone(), "sol.mainnet".into()]
+				.try_into()
+				.unwrap()
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ChainIdsBatchRemoved {
+			removed: vec![ksm.clone()],
+			skipped: vec![chain_id(), dot.clone(), "sol.mainnet".into()],
+		}));
+		assert!(Dapi::chain_id_exists(chain_id()));
+		assert!(Dapi::chain_id_exists(dot));
+		assert!(!Dapi::chain_id_exists(ksm));
+		assert!(Dapi::chain_id_exists(bsc));
+	})
+}
+
+#[test]
+fn register_providers_batch_skips_invalid_entries() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 2;
+		let registration = |seed: u8, chain_id: Vec<u8>| ProviderRegistration {
+			provider_id: massbit_id(seed),
+			provider_type: ProviderType::Gateway,
+			owner,
+			chain_id,
+		};
+		let registrations: BoundedVec<_, _> = vec![
+			registration(1, chain_id()),
+			registration(1, chain_id()),
+			registration(2, "sol.mainnet".into()),
+			registration(3, chain_id()),
+		]
+		.try_into()
+		.unwrap();
+
+		assert_noop!(
+			Dapi::register_providers_batch(Origin::signed(owner), registrations.clone()),
+			Error::<TestRuntime>::PermissionDenied
+		);
+		assert_ok!(Dapi::register_providers_batch(Origin::signed(REGULATOR), registrations));
+
+		System::assert_has_event(mock::Event::Dapi(Event::ProviderRegistrationSkipped {
+			provider_id: massbit_id(1),
+			reason: Error::<TestRuntime>::AlreadyExist.into(),
+		}));
+		System::assert_has_event(mock::Event::Dapi(Event::ProviderRegistrationSkipped {
+			provider_id: massbit_id(2),
+			reason: Error::<TestRuntime>::InvalidChainId.into(),
+		}));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderRegistered {
+			provider_id: massbit_id(3),
+			provider_type: ProviderType::Gateway,
+			owner,
+			chain_id: chain_id(),
+		}));
+		assert!(Dapi::providers(massbit_id(1)).is_some());
+		assert!(Dapi::providers(massbit_id(2)).is_none());
+		assert_eq!(Dapi::providers(massbit_id(3)).unwrap().status, ProviderStatus::Registered);
+	})
+}
+
+#[test]
+fn batch_register_providers_is_atomic() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 2;
+		let registration = |seed: u8, chain_id: Vec<u8>| ProviderRegistration {
+			provider_id: massbit_id(seed),
+			provider_type: ProviderType::Gateway,
+			owner,
+			chain_id,
+		};
+
+		let invalid: BoundedVec<_, _> =
+			vec![registration(1, chain_id()), registration(2, "sol.mainnet".into())]
+				.try_into()
+				.unwrap();
+		assert_noop!(
+			Dapi::batch_register_providers(Origin::signed(REGULATOR), invalid),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		let duplicated: BoundedVec<_, _> =
+			vec![registration(1, chain_id()), registration(1, chain_id())]
+				.try_into()
+				.unwrap();
+		assert_noop!(
+			Dapi::batch_register_providers(Origin::signed(REGULATOR), duplicated),
+			Error::<TestRuntime>::AlreadyExist
+		);
+
+		let registrations: BoundedVec<_, _> =
+			vec![registration(1, chain_id()), registration(2, chain_id())]
+				.try_into()
+				.unwrap();
+		assert_ok!(Dapi::batch_register_providers(Origin::signed(REGULATOR), registrations));
+		for seed in [1, 2] {
+			System::assert_has_event(mock::Event::Dapi(Event::ProviderRegistered {
+				provider_id: massbit_id(seed),
+				provider_type: ProviderType::Gateway,
+				owner,
+				chain_id: chain_id(),
+			}));
+			assert_eq!(
+				Dapi::providers(massbit_id(seed)).unwrap().status,
+				ProviderStatus::Registered
+			);
+		}
+	})
+}
+
+#[test]
+fn genesis_registers_providers() {
+	use frame_support::traits::GenesisBuild;
+
+	let mut storage =
+		frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+	crate::GenesisConfig::<TestRuntime> {
+		regulators: vec![REGULATOR],
+		chain_ids: vec![chain_id()],
+		providers: vec![ProviderRegistration {
+			provider_id: massbit_id(1),
+			provider_type: ProviderType::Node,
+			owner: 2,
+			chain_id: chain_id(),
+		}],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		let provider = Dapi::providers(massbit_id(1)).unwrap();
+		assert_eq!(provider.provider_type, ProviderType::Node);
+		assert_eq!(provider.owner, 2);
+		assert_eq!(provider.status, ProviderStatus::Registered);
+	});
+}
+
+/// Move dAPI staking to the given era.
+fn set_current_era(era: u32) {
+	pallet_dapi_staking::Era::<TestRuntime>::mutate(|info| info.current = era);
+}
+
+#[test]
+fn submit_provider_rating_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let provider_id = massbit_id(1);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			2,
+			chain_id()
+		));
+
+		let project_id = massbit_id(3);
+		assert_noop!(
+			Dapi::submit_provider_rating(Origin::signed(CONSUMER), provider_id, project_id, 11),
+			Error::<TestRuntime>::InvalidRating
+		);
+		assert_noop!(
+			Dapi::submit_provider_rating(Origin::signed(CONSUMER), massbit_id(2), project_id, 5),
+			Error::<TestRuntime>::ProviderDNE
+		);
+		assert_noop!(
+			Dapi::submit_provider_rating(Origin::signed(CONSUMER), provider_id, project_id, 5),
+			Error::<TestRuntime>::ProjectDNE
+		);
+		assert_eq!(Dapi::provider_average_rating(provider_id), None);
+
+		register_project(project_id, 100 * QUOTA_PRICE);
+		// The project of another consumer doesn't make a consumer eligible
+		assert_noop!(
+			Dapi::submit_provider_rating(Origin::signed(1), provider_id, project_id, 5),
+			Error::<TestRuntime>::NotEligibleRater
+		);
+		assert_ok!(Dapi::submit_provider_rating(
+			Origin::signed(CONSUMER),
+			provider_id,
+			project_id,
+			8
+		));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderRated {
+			provider_id,
+			by: CONSUMER,
+			rating: 8,
+		}));
+		assert_eq!(Dapi::provider_ratings(provider_id, CONSUMER), Some(8));
+		assert_eq!(Dapi::provider_rating_aggregate(provider_id), (8, 1));
+		assert_eq!(Dapi::provider_average_rating(provider_id), Some(80));
+	})
+}
+
+#[test]
+fn provider_rating_cooldown_is_respected() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let provider_id = massbit_id(1);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			2,
+			chain_id()
+		));
+		register_project(massbit_id(3), 100 * QUOTA_PRICE);
+		set_current_era(1);
+		assert_ok!(Dapi::submit_provider_rating(
+			Origin::signed(CONSUMER),
+			provider_id,
+			massbit_id(3),
+			4
+		));
+
+		set_current_era(2);
+		assert_noop!(
+			Dapi::submit_provider_rating(Origin::signed(CONSUMER), provider_id, massbit_id(3), 9),
+			Error::<TestRuntime>::RatingCooldown
+		);
+
+		// Rating again replaces the previous rating in the aggregate
+		set_current_era(3);
+		assert_ok!(Dapi::submit_provider_rating(
+			Origin::signed(CONSUMER),
+			provider_id,
+			massbit_id(3),
+			9
+		));
+		assert_eq!(Dapi::provider_rating_aggregate(provider_id), (9, 1));
+		assert_eq!(Dapi::rating_last_era(provider_id, CONSUMER), Some(3));
+	})
+}
+
+#[test]
+fn provider_average_rating_is_in_tenths() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let provider_id = massbit_id(1);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			2,
+			chain_id()
+		));
+		let other_consumer = 1;
+		register_project(massbit_id(3), 100 * QUOTA_PRICE);
+		assert_ok!(Dapi::register_project(
+			Origin::signed(other_consumer),
+			massbit_id(4),
+			vec![chain_id()],
+			100,
+			false
+		));
+
+		assert_ok!(Dapi::submit_provider_rating(
+			Origin::signed(CONSUMER),
+			provider_id,
+			massbit_id(3),
+			8
+		));
+		assert_ok!(Dapi::submit_provider_rating(
+			Origin::signed(other_consumer),
+			provider_id,
+			massbit_id(4),
+			7
+		));
+		assert_eq!(Dapi::provider_rating_aggregate(provider_id), (15, 2));
+		assert_eq!(Dapi::provider_average_rating(provider_id), Some(75));
+	})
+}
+
+#[test]
+fn regulator_bond_is_required_and_slashable() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let regulator = 7;
+
+		assert_noop!(
+			Dapi::add_regulator(Origin::root(), regulator),
+			Error::<TestRuntime>::RegulatorNotBonded
+		);
+		assert_noop!(
+			Dapi::bond_as_regulator(Origin::signed(regulator), MIN_REGULATOR_BOND - 1),
+			Error::<TestRuntime>::InsufficientRegulatorBond
+		);
+		bond_regulator(regulator);
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorBonded {
+			who: regulator,
+			amount: MIN_REGULATOR_BOND,
+		}));
+		assert_eq!(Balances::reserved_balance(regulator), MIN_REGULATOR_BOND);
+
+		assert_ok!(Dapi::add_regulator(Origin::root(), regulator));
+		assert_noop!(
+			Dapi::unbond_regulator(Origin::signed(regulator)),
+			Error::<TestRuntime>::StillRegulator
+		);
+
+		assert_noop!(
+			Dapi::slash_regulator(Origin::signed(REGULATOR), regulator, Perbill::one()),
+			BadOrigin
+		);
+		assert_ok!(Dapi::slash_regulator(Origin::root(), regulator, Perbill::from_percent(40)));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorSlashed {
+			who: regulator,
+			amount: 2,
+		}));
+		assert_eq!(Dapi::regulator_bond(regulator), MIN_REGULATOR_BOND - 2);
+
+		assert_ok!(Dapi::remove_regulator(Origin::root(), regulator));
+		assert_ok!(Dapi::unbond_regulator(Origin::signed(regulator)));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorUnbonded {
+			who: regulator,
+			amount: MIN_REGULATOR_BOND - 2,
+		}));
+		assert_eq!(Balances::reserved_balance(regulator), 0);
+		assert_noop!(
+			Dapi::unbond_regulator(Origin::signed(regulator)),
+			Error::<TestRuntime>::RegulatorNotBonded
+		);
+	})
+}
+
+#[test]
+fn regulators_are_elected_by_stake() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		setup_stakers();
+		let candidate = 20;
+
+		assert_noop!(
+			Dapi::propose_regulator(Origin::signed(3), candidate),
+			Error::<TestRuntime>::RegulatorNotBonded
+		);
+		bond_regulator(candidate);
+		assert_noop!(
+			Dapi::propose_regulator(Origin::signed(11), candidate),
+			Error::<TestRuntime>::NoStake
+		);
+		assert_noop!(
+			Dapi::propose_regulator(Origin::signed(3), REGULATOR),
+			Error::<TestRuntime>::AlreadyExist
+		);
+
+		// Half of the stake isn't enough
+		assert_ok!(Dapi::propose_regulator(Origin::signed(3), candidate));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorProposalVoted {
+			candidate,
+			voter: 3,
+			change: RegulatorChange::Add,
+			approval_stake: 300,
+		}));
+		assert!(!Dapi::regulators().contains(&candidate));
+		assert_noop!(
+			Dapi::propose_regulator(Origin::signed(3), candidate),
+			Error::<TestRuntime>::AlreadyVoted
+		);
+
+		assert_ok!(Dapi::propose_regulator(Origin::signed(9), candidate));
+		assert!(Dapi::regulators().contains(&candidate));
+		assert_eq!(Dapi::regulator_proposals(candidate), None);
+		System::assert_has_event(mock::Event::Dapi(Event::RegulatorAdded {
+			account_id: candidate,
+		}));
+
+		assert_ok!(Dapi::propose_regulator_removal(Origin::signed(9), candidate));
+		assert!(Dapi::regulators().contains(&candidate));
+		assert_ok!(Dapi::propose_regulator_removal(Origin::signed(3), candidate));
+		assert!(!Dapi::regulators().contains(&candidate));
+		assert_noop!(
+			Dapi::propose_regulator_removal(Origin::signed(3), candidate),
+			Error::<TestRuntime>::PermissionDenied
+		);
+	})
+}
+
+#[test]
+fn regulator_approval_counts_current_stake() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		setup_stakers();
+		let provider_id = massbit_id(9);
+		assert_ok!(DapiStaking::delegate(Origin::signed(4), provider_id, 100));
+		let candidate = 20;
+		bond_regulator(candidate);
+
+		assert_ok!(Dapi::propose_regulator(Origin::signed(3), candidate));
+		assert_ok!(DapiStaking::delegator_unstake(Origin::signed(3), provider_id, 300));
+
+		// The stake unbonded by 3 no longer approves the candidate
+		assert_ok!(Dapi::propose_regulator(Origin::signed(4), candidate));
+		System::assert_last_event(mock::Event::Dapi(Event::RegulatorProposalVoted {
+			candidate,
+			voter: 4,
+			change: RegulatorChange::Add,
+			approval_stake: 100,
+		}));
+		assert!(!Dapi::regulators().contains(&candidate));
+	})
+}
+
+#[test]
+fn expired_regulator_proposal_starts_over() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		setup_stakers();
+		let candidate = 20;
+		bond_regulator(candidate);
+
+		assert_ok!(Dapi::propose_regulator(Origin::signed(3), candidate));
+		assert_eq!(Dapi::regulator_proposals(candidate).unwrap().expiry, 11);
+
+		System::set_block_number(12);
+		assert_ok!(Dapi::propose_regulator(Origin::signed(9), candidate));
+		let proposal = Dapi::regulator_proposals(candidate).unwrap();
+		assert_eq!((proposal.approval_stake, proposal.expiry), (200, 22));
+		assert_eq!(proposal.voters.into_inner(), vec![9]);
+		assert!(!Dapi::regulators().contains(&candidate));
+	})
+}
+
+#[test]
+fn bad_performance_offence_slashes_provider_bond() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let (bad_provider, out_of_sync_provider) = (massbit_id(1), massbit_id(2));
+		for provider_id in [bad_provider, out_of_sync_provider] {
+			assert_ok!(Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				provider_id,
+				ProviderType::Gateway,
+				owner,
+				chain_id()
+			));
+			assert_ok!(Dapi::deposit_provider(
+				Origin::signed(owner),
+				provider_id,
+				100,
+				Perbill::zero()
+			));
+		}
+		let total_issuance = Balances::total_issuance();
+
+		assert_ok!(Dapi::report_provider_offence(
+			Origin::signed(REGULATOR),
+			bad_provider,
+			ProviderDeactivateReason::BadPerformance {
+				requests: 100,
+				success_rate: 10,
+				average_latency: 1000
+			}
+		));
+		System::assert_has_event(mock::Event::DapiStaking(
+			pallet_dapi_staking::Event::ProviderSlashed { provider_id: bad_provider, amount: 10 },
+		));
+		assert_eq!(Balances::reserved_balance(&owner), 190);
+		assert_eq!(Balances::total_issuance(), total_issuance - 10);
+
+		assert_ok!(Dapi::report_provider_offence(
+			Origin::signed(REGULATOR),
+			out_of_sync_provider,
+			ProviderDeactivateReason::OutOfSync
+		));
+		assert_eq!(Balances::reserved_balance(&owner), 190);
+	})
+}
+
+#[test]
+fn chain_stake_of_active_providers_is_indexed() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let dot: Vec<u8> = "dot.mainnet".into();
+		assert_ok!(Dapi::add_chain_id(Origin::root(), dot.clone(), Default::default()));
+		let bounded = |chain_id: &Vec<u8>| -> BoundedVec<u8, ConstU32<64>> {
+			chain_id.clone().try_into().unwrap()
+		};
+		let (first, second, third) = (massbit_id(1), massbit_id(2), massbit_id(3));
+		for (provider_id, owner, chain, deposit) in [
+			(first, 10, chain_id(), 100),
+			(second, 10, chain_id(), 100),
+			(third, 11, dot.clone(), 300),
+		] {
+			assert_ok!(Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				provider_id,
+				ProviderType::Gateway,
+				owner,
+				chain
+			));
+			assert_ok!(Dapi::deposit_provider(
+				Origin::signed(owner),
+				provider_id,
+				deposit,
+				Perbill::zero()
+			));
+		}
+		let stake = |provider_id| DapiStaking::provider_tvl(provider_id);
+
+		// Chains are ranked by stake, not by number of providers
+		assert_eq!(Dapi::chain_stake(bounded(&chain_id())), Some(stake(first) + stake(second)));
+		assert_eq!(Dapi::chain_stake(bounded(&dot)), Some(stake(third)));
+		assert_eq!(Dapi::most_served_chain(), Some(dot.clone()));
+
+		assert_ok!(Dapi::add_provider_chain(Origin::signed(10), first, dot.clone()));
+		assert_eq!(Dapi::chain_stake(bounded(&dot)), Some(stake(first) + stake(third)));
+
+		assert_ok!(Dapi::report_provider_offence(
+			Origin::signed(REGULATOR),
+			third,
+			ProviderDeactivateReason::OutOfSync
+		));
+		assert_eq!(Dapi::chain_stake(bounded(&dot)), Some(stake(first)));
+		assert_eq!(Dapi::most_served_chain(), Some(chain_id()));
+
+		assert_ok!(Dapi::remove_provider_chain(Origin::signed(10), first, dot.clone()));
+		assert_eq!(Dapi::chain_stake(bounded(&dot)), None);
+
+		assert_ok!(Dapi::unregister_provider(Origin::signed(10), first));
+		assert_ok!(Dapi::unregister_provider(Origin::signed(10), second));
+		assert_eq!(Dapi::chain_stake(bounded(&chain_id())), None);
+		assert_eq!(Dapi::most_served_chain(), None);
+	})
+}
+
+#[test]
+fn deposit_provider_requires_min_stake_of_provider_type() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let (gateway, node) = (massbit_id(1), massbit_id(2));
+		for (provider_id, provider_type) in
+			[(gateway, ProviderType::Gateway), (node, ProviderType::Node)]
+		{
+			assert_ok!(Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				provider_id,
+				provider_type,
+				owner,
+				chain_id()
+			));
+		}
+
+		assert_noop!(
+			Dapi::deposit_provider(Origin::signed(owner), node, GATEWAY_MIN_STAKE, Perbill::zero()),
+			pallet_dapi_staking::Error::<TestRuntime>::InsufficientBond
+		);
+		assert_ok!(Dapi::deposit_provider(
+			Origin::signed(owner),
+			gateway,
+			GATEWAY_MIN_STAKE,
+			Perbill::zero()
+		));
+		assert_ok!(Dapi::deposit_provider(
+			Origin::signed(owner),
+			node,
+			NODE_MIN_STAKE,
+			Perbill::zero()
+		));
+		assert_eq!(Dapi::providers(node).unwrap().status, ProviderStatus::Active);
+	})
+}
+
+#[test]
+fn whitelist_mode_restricts_provider_activation() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let provider_id = massbit_id(1);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			owner,
+			chain_id()
+		));
+
+		assert_noop!(Dapi::set_whitelist_mode(Origin::signed(REGULATOR), true), BadOrigin);
+		assert_ok!(Dapi::set_whitelist_mode(Origin::root(), true));
+		System::assert_last_event(mock::Event::Dapi(Event::WhitelistModeChanged { enabled: true }));
+		assert_noop!(
+			Dapi::deposit_provider(
+				Origin::signed(owner),
+				provider_id,
+				GATEWAY_MIN_STAKE,
+				Perbill::zero()
+			),
+			Error::<TestRuntime>::ProviderNotWhitelisted
+		);
+
+		assert_noop!(Dapi::whitelist_provider(Origin::signed(owner), provider_id), BadOrigin);
+		assert_ok!(Dapi::whitelist_provider(Origin::root(), provider_id));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderWhitelisted { provider_id }));
+		assert_noop!(
+			Dapi::whitelist_provider(Origin::root(), provider_id),
+			Error::<TestRuntime>::AlreadyExist
+		);
+		assert_ok!(Dapi::deposit_provider(
+			Origin::signed(owner),
+			provider_id,
+			GATEWAY_MIN_STAKE,
+			Perbill::zero()
+		));
+
+		assert_ok!(Dapi::remove_from_whitelist(Origin::root(), provider_id));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderRemovedFromWhitelist {
+			provider_id,
+		}));
+		assert_noop!(
+			Dapi::remove_from_whitelist(Origin::root(), provider_id),
+			Error::<TestRuntime>::ProviderNotWhitelisted
+		);
+		assert!(Dapi::provider_whitelist().is_empty());
+	})
+}
+
+#[test]
+fn paused_pallet_rejects_user_calls() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let (project_id, provider_id) = (massbit_id(1), massbit_id(2));
+		register_project(project_id, 100 * QUOTA_PRICE);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			owner,
+			chain_id()
+		));
+
+		assert_noop!(Dapi::pause_pallet(Origin::signed(REGULATOR)), BadOrigin);
+		assert_ok!(Dapi::pause_pallet(Origin::root()));
+		System::assert_last_event(mock::Event::Dapi(Event::PalletPauseChanged { paused: true }));
+
+		assert_noop!(
+			Dapi::register_project(
+				Origin::signed(CONSUMER),
+				massbit_id(3),
+				vec![chain_id()],
+				100 * QUOTA_PRICE,
+				false
+			),
+			Error::<TestRuntime>::PalletPaused
+		);
+		assert_noop!(
+			Dapi::deposit_project(Origin::signed(CONSUMER), project_id, QUOTA_PRICE),
+			Error::<TestRuntime>::PalletPaused
+		);
+		assert_noop!(
+			Dapi::submit_project_usage(Origin::signed(REGULATOR), project_id, chain_id(), 10),
+			Error::<TestRuntime>::PalletPaused
+		);
+		assert_noop!(
+			Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				massbit_id(4),
+				ProviderType::Gateway,
+				owner,
+				chain_id()
+			),
+			Error::<TestRuntime>::PalletPaused
+		);
+		assert_noop!(
+			Dapi::deposit_provider(
+				Origin::signed(owner),
+				provider_id,
+				GATEWAY_MIN_STAKE,
+				Perbill::zero()
+			),
+			Error::<TestRuntime>::PalletPaused
+		);
+
+		// Admin calls keep working
+		assert_ok!(Dapi::add_chain_id(Origin::root(), b"dot.mainnet".to_vec(), Default::default()));
+
+		assert_noop!(Dapi::unpause_pallet(Origin::signed(REGULATOR)), BadOrigin);
+		assert_ok!(Dapi::unpause_pallet(Origin::root()));
+		System::assert_last_event(mock::Event::Dapi(Event::PalletPauseChanged { paused: false }));
+		assert_ok!(Dapi::deposit_provider(
+			Origin::signed(owner),
+			provider_id,
+			GATEWAY_MIN_STAKE,
+			Perbill::zero()
+		));
+	})
+}
+
+#[test]
+fn sla_violation_deactivates_provider() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let owner = 10;
+		let provider_id = massbit_id(1);
+		assert_ok!(Dapi::register_provider(
+			Origin::signed(REGULATOR),
+			provider_id,
+			ProviderType::Gateway,
+			owner,
+			chain_id()
+		));
+		assert_ok!(Dapi::deposit_provider(
+			Origin::signed(owner),
+			provider_id,
+			100,
+			Perbill::from_percent(75)
+		));
+		assert_eq!(Dapi::providers(provider_id).unwrap().sla_commitment, Perbill::from_percent(75));
+
+		// The commitment isn't evaluated before `SlaEvaluationPeriod` checks.
+		for success in [true, true, false] {
+			assert_ok!(Dapi::record_provider_uptime(
+				Origin::signed(REGULATOR),
+				provider_id,
+				success
+			));
+		}
+		assert_eq!(
+			Dapi::provider_uptime_history(provider_id),
+			UptimeRecord { total_checks: 3, successful_checks: 2 }
+		);
+		assert_eq!(Dapi::providers(provider_id).unwrap().status, ProviderStatus::Active);
+
+		assert_ok!(Dapi::record_provider_uptime(Origin::signed(REGULATOR), provider_id, false));
+		let reason =
+			ProviderDeactivateReason::SlaViolation { total_checks: 4, successful_checks: 2 };
+		System::assert_has_event(mock::Event::Dapi(Event::SlaViolation { provider_id }));
+		System::assert_last_event(mock::Event::Dapi(Event::ProviderDeactivated {
+			provider_id,
+			provider_type: ProviderType::Gateway,
+			reason,
+		}));
+		assert_eq!(
+			Dapi::providers(provider_id).unwrap().status,
+			ProviderStatus::InActive { reason }
+		);
+		assert_eq!(Balances::reserved_balance(&owner), 90);
+		assert_noop!(
+			Dapi::record_provider_uptime(Origin::signed(REGULATOR), provider_id, true),
+			Error::<TestRuntime>::InvalidProviderStatus
+		);
+	})
+}
+
+#[test]
+fn chain_metadata_is_ok() {
+	ExternalityBuilder::build().execute_with(|| {
+		let metadata = ChainMetadata {
+			display_name: b"Ethereum".to_vec(),
+			explorer_url: b"https://etherscan.io".to_vec(),
+			rpc_endpoint: b"https://eth.example.org".to_vec(),
+		};
+		assert_noop!(
+			Dapi::add_chain_id(Origin::signed(1), chain_id(), metadata.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			Dapi::add_chain_id(
+				Origin::root(),
+				chain_id(),
+				ChainMetadata { display_name: vec![b'n'; 17], ..metadata.clone() }
+			),
+			Error::<TestRuntime>::MetadataTooLong
+		);
+		assert_ok!(Dapi::add_chain_id(Origin::root(), chain_id(), metadata.clone()));
+		assert_eq!(Dapi::chain_metadata_of(chain_id()), Some(metadata.clone()));
+
+		let dot: Vec<u8> = "dot.mainnet".into();
+		assert_noop!(
+			Dapi::update_chain_metadata(Origin::root(), dot.clone(), metadata.clone()),
+			Error::<TestRuntime>::InvalidChainId
+		);
+		let updated = ChainMetadata { display_name: b"Ether".to_vec(), ..metadata };
+		assert_ok!(Dapi::update_chain_metadata(Origin::root(), chain_id(), updated.clone()));
+		System::assert_last_event(mock::Event::Dapi(Event::ChainMetadataUpdated {
+			chain_id: chain_id(),
+		}));
+		assert_eq!(Dapi::chain_metadata_of(chain_id()), Some(updated));
+		assert_eq!(Dapi::chain_metadata_of(dot), None);
+	})
+}
+
+#[test]
+fn migrate_chain_ids_to_v3_is_ok() {
+	use frame_support::{
+		storage::{unhashed, StoragePrefixedMap},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_std::collections::btree_set::BTreeSet;
+
+	ExternalityBuilder::build().execute_with(|| {
+		// Simulate the set of chain Ids
+		let chain_ids: BTreeSet<Vec<u8>> =
+			vec![chain_id(), "dot.mainnet".into()].into_iter().collect();
+		unhashed::put(&ChainIds::<TestRuntime>::final_prefix(), &chain_ids);
+		StorageVersion::new(2).put::<Dapi>();
+
+		crate::migrations::MigrateChainIdsToV3::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(ChainIds::<TestRuntime>::iter().count(), 2);
+		for chain_id in chain_ids {
+			assert_eq!(Dapi::chain_metadata_of(chain_id), Some(Default::default()));
+		}
+		assert!(unhashed::get_raw(&ChainIds::<TestRuntime>::final_prefix()).is_none());
+		assert_eq!(Dapi::on_chain_storage_version(), 3);
+	})
+}
+
+#[test]
+fn migrate_providers_to_v4_is_ok() {
+	use crate::{Provider, Providers};
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		// Simulate the layout without SLA commitment
+		let provider_id = massbit_id(1);
+		let chain_id: BoundedVec<u8, ConstU32<64>> = chain_id().try_into().unwrap();
+		let old = (ProviderType::Node, 10 as AccountId, chain_id.clone(), ProviderStatus::Active);
+		unhashed::put_raw(&Providers::<TestRuntime>::hashed_key_for(&provider_id), &old.encode());
+		StorageVersion::new(3).put::<Dapi>();
+
+		crate::migrations::MigrateProvidersToV4::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(
+			Dapi::providers(provider_id),
+			Some(Provider {
+				provider_type: ProviderType::Node,
+				owner: 10,
+				chain_id,
+				status: ProviderStatus::Active,
+				sla_commitment: Perbill::zero(),
+			})
+		);
+		assert_eq!(Dapi::on_chain_storage_version(), 4);
+	})
+}
+
+#[test]
+fn migrate_projects_to_v5_is_ok() {
+	use crate::{Project, Projects};
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExternalityBuilder::build().execute_with(|| {
+		// Simulate the layout without deposit and usage blocks
+		let project_id = massbit_id(1);
+		let chain_ids: BoundedVec<BoundedVec<u8, ConstU32<64>>, ConstU32<2>> =
+			vec![chain_id().try_into().unwrap()].try_into().unwrap();
+		let old = (
+			CONSUMER,
+			chain_ids.clone(),
+			100u128,
+			40u128,
+			None::<BlockNumber>,
+			ProjectStatus::Active,
+		);
+		unhashed::put_raw(&Projects::<TestRuntime>::hashed_key_for(&project_id), &old.encode());
+		StorageVersion::new(4).put::<Dapi>();
+		System::set_block_number(7);
+
+		crate::migrations::MigrateProjectsToV5::<TestRuntime>::on_runtime_upgrade();
+
+		assert_eq!(
+			Dapi::projects(project_id),
+			Some(Project {
+				consumer: CONSUMER,
+				chain_ids,
+				quota: 100,
+				usage: 40,
+				renewal_block: None,
+				status: ProjectStatus::Active,
+				last_deposit_block: 7,
+				last_usage_block: 7,
+			})
+		);
+		assert_eq!(Dapi::on_chain_storage_version(), 5);
+	})
+}
+
+#[test]
+fn populate_chain_stake_to_v6_is_ok() {
+	use crate::{ChainStake, IndexedProviderStake, MostStakedChain};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExternalityBuilder::build().execute_with(|| {
+		initialize();
+		let (active, registered) = (massbit_id(1), massbit_id(2));
+		for provider_id in [active, registered] {
+			assert_ok!(Dapi::register_provider(
+				Origin::signed(REGULATOR),
+				provider_id,
+				ProviderType::Gateway,
+				10,
+				chain_id()
+			));
+		}
+		assert_ok!(Dapi::deposit_provider(Origin::signed(10), active, 100, Perbill::zero()));
+		// Simulate the state before chain stake was indexed
+		let _ = ChainStake::<TestRuntime>::remove_all(None);
+		let _ = IndexedProviderStake::<TestRuntime>::remove_all(None);
+		MostStakedChain::<TestRuntime>::kill();
+		StorageVersion::new(5).put::<Dapi>();
+
+		crate::migrations::PopulateChainStakeToV6::<TestRuntime>::on_runtime_upgrade();
+
+		let bounded: BoundedVec<u8, ConstU32<64>> = chain_id().try_into().unwrap();
+		assert_eq!(Dapi::chain_stake(bounded), Some(DapiStaking::provider_tvl(active)));
+		assert_eq!(Dapi::most_served_chain(), Some(chain_id()));
+		assert_eq!(Dapi::on_chain_storage_version(), 6);
+	})
+}
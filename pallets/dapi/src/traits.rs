@@ -0,0 +1,6 @@
+use frame_support::pallet_prelude::DispatchResult;
+
+pub trait QuotaDeduction<ProjectId, Balance> {
+	/// Deduct the requests quota equivalent of `amount` from the project.
+	fn deduct(project_id: ProjectId, amount: Balance) -> DispatchResult;
+}
@@ -3,11 +3,28 @@
 use super::*;
 
 #[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct Project<AccountId, ChainId> {
+pub struct Project<AccountId, ChainIds, BlockNumber> {
 	pub consumer: AccountId,
-	pub chain_id: ChainId,
+	/// Chains the project can query, sharing its quota.
+	pub chain_ids: ChainIds,
 	pub quota: u128,
 	pub usage: u128,
+	/// Block from which the quota of a subscription project is due for renewal.
+	pub renewal_block: Option<BlockNumber>,
+	pub status: ProjectStatus,
+	/// Block of the last deposit, or quota renewal, of the project.
+	pub last_deposit_block: BlockNumber,
+	/// Block in which usage of the project was last submitted.
+	pub last_usage_block: BlockNumber,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum ProjectStatus {
+	Active,
+	/// Subscription couldn't be renewed.
+	Expired,
+	/// Suspended by a regulator, usage can't be submitted.
+	Suspended,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -24,12 +41,75 @@ pub enum ProviderStatus {
 	InActive { reason: ProviderDeactivateReason },
 }
 
+/// Provider entry of a batch or genesis registration.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProviderRegistration<AccountId, MassbitId> {
+	pub provider_id: MassbitId,
+	pub provider_type: ProviderType,
+	pub owner: AccountId,
+	pub chain_id: Vec<u8>,
+}
+
+/// Provider registration proposed by a regulator, waiting for the consent of its owner.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ProviderRegProposal<AccountId, ChainId, BlockNumber> {
+	pub provider_type: ProviderType,
+	pub owner: AccountId,
+	pub chain_id: ChainId,
+	/// Last block in which the owner can accept the registration.
+	pub expiry: BlockNumber,
+}
+
 #[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct Provider<AccountId, ChainId> {
 	pub provider_type: ProviderType,
 	pub owner: AccountId,
 	pub chain_id: ChainId,
 	pub status: ProviderStatus,
+	/// Share of uptime checks the provider committed to pass.
+	pub sla_commitment: Perbill,
+}
+
+/// Uptime checks of a provider reported by regulators.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct UptimeRecord {
+	pub total_checks: u32,
+	pub successful_checks: u32,
+}
+
+/// Human-readable provider information.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ProviderMetadataExtra<Name, Endpoint, Description> {
+	pub name: Name,
+	pub endpoint: Endpoint,
+	pub description: Description,
+}
+
+/// Human-readable chain information.
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ChainMetadata<Name, Url> {
+	pub display_name: Name,
+	pub explorer_url: Url,
+	pub rpc_endpoint: Url,
+}
+
+/// Change of the regulator set voted by stakers.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum RegulatorChange {
+	Add,
+	Remove,
+}
+
+/// Stake-weighted vote on a change of the regulator set.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ProposalInfo<Voters, Balance, BlockNumber> {
+	pub change: RegulatorChange,
+	pub voters: Voters,
+	/// Current stake of the voters, as recounted on the last vote.
+	pub approval_stake: Balance,
+	/// Last block in which the proposal can be voted.
+	pub expiry: BlockNumber,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -38,4 +118,5 @@ pub enum ProviderDeactivateReason {
 	BadPerformance { requests: u64, success_rate: u32, average_latency: u32 },
 	OutOfSync,
 	UnRegistered,
+	SlaViolation { total_checks: u32, successful_checks: u32 },
 }
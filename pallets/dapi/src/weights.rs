@@ -43,17 +43,81 @@ pub trait WeightInfo {
 	#[rustfmt::skip]
 	fn deposit_project() -> Weight;
 	#[rustfmt::skip]
+	fn refund_project() -> Weight;
+	#[rustfmt::skip]
+	fn transfer_project() -> Weight;
+	#[rustfmt::skip]
+	fn accept_project_transfer() -> Weight;
+	#[rustfmt::skip]
+	fn register_providers_batch(n: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn batch_register_providers(n: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn deposit_provider() -> Weight;
 	#[rustfmt::skip]
 	fn unregister_provider() -> Weight;
 	#[rustfmt::skip]
+	fn add_provider_chain() -> Weight;
+	#[rustfmt::skip]
+	fn remove_provider_chain() -> Weight;
+	#[rustfmt::skip]
+	fn update_provider_metadata() -> Weight;
+	#[rustfmt::skip]
+	fn submit_provider_rating() -> Weight;
+	#[rustfmt::skip]
+	fn update_provider_score() -> Weight;
+	#[rustfmt::skip]
+	fn record_provider_uptime() -> Weight;
+	#[rustfmt::skip]
+	fn suspend_project() -> Weight;
+	#[rustfmt::skip]
+	fn unsuspend_project() -> Weight;
+	#[rustfmt::skip]
+	fn set_monthly_reset_interval() -> Weight;
+	#[rustfmt::skip]
+	fn set_quota_price() -> Weight;
+	#[rustfmt::skip]
+	fn set_whitelist_mode() -> Weight;
+	#[rustfmt::skip]
+	fn pause_pallet() -> Weight;
+	#[rustfmt::skip]
+	fn unpause_pallet() -> Weight;
+	#[rustfmt::skip]
+	fn propose_provider_registration() -> Weight;
+	#[rustfmt::skip]
+	fn accept_provider_registration() -> Weight;
+	#[rustfmt::skip]
+	fn whitelist_provider() -> Weight;
+	#[rustfmt::skip]
+	fn remove_from_whitelist() -> Weight;
+	#[rustfmt::skip]
 	fn add_chain_id() -> Weight;
 	#[rustfmt::skip]
 	fn remove_chain_id() -> Weight;
 	#[rustfmt::skip]
+	fn update_chain_metadata() -> Weight;
+	#[rustfmt::skip]
+	fn add_chain_ids_batch(n: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn remove_chain_ids_batch(n: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn add_regulator() -> Weight;
 	#[rustfmt::skip]
 	fn remove_regulator() -> Weight;
+	#[rustfmt::skip]
+	fn batch_add_regulators(n: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn batch_remove_regulators(n: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn propose_regulator(v: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn propose_regulator_removal(v: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn bond_as_regulator() -> Weight;
+	#[rustfmt::skip]
+	fn unbond_regulator() -> Weight;
+	#[rustfmt::skip]
+	fn slash_regulator() -> Weight;
 }
 
 /// Weights for pallet_dapi using the Substrate node and recommended hardware.
@@ -63,10 +127,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Dapi ChainIds (r:1 w:0)
 	// Storage: System Account (r:2 w:2)
 	// Storage: DapiStaking RewardAccumulator (r:1 w:1)
+	// Storage: Dapi MonthlyResetInterval (r:1 w:0)
 	#[rustfmt::skip]
 	fn register_project() -> Weight {
-		(39_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: Dapi Projects (r:1 w:1)
@@ -78,29 +143,251 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: Dapi Projects (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	#[rustfmt::skip]
+	fn refund_project() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dapi Projects (r:1 w:0)
+	// Storage: Dapi PendingProjectTransfers (r:0 w:1)
+	#[rustfmt::skip]
+	fn transfer_project() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi PendingProjectTransfers (r:1 w:1)
+	// Storage: Dapi Projects (r:1 w:1)
+	#[rustfmt::skip]
+	fn accept_project_transfer() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn register_providers_batch(n: u32, ) -> Weight {
+		(5_284_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((17_936_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn batch_register_providers(n: u32, ) -> Weight {
+		(5_102_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((17_854_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi WhitelistMode (r:1 w:0)
+	// Storage: Dapi ProviderWhitelist (r:1 w:0)
 	// Storage: DapiStaking ProviderInfo (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking EraState (r:1 w:1)
-	// Storage: DapiStaking ProviderEraInfo (r:0 w:1)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:0 w:1)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
 	#[rustfmt::skip]
 	fn deposit_provider() -> Weight {
-		(29_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
 	}
 	// Storage: Dapi Providers (r:1 w:1)
 	// Storage: DapiStaking ProviderInfo (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
 	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
 	#[rustfmt::skip]
 	fn unregister_provider() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi ProviderChains (r:1 w:1)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	#[rustfmt::skip]
+	fn add_provider_chain() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderChains (r:1 w:1)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	#[rustfmt::skip]
+	fn remove_provider_chain() -> Weight {
 		(22_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderMetadata (r:0 w:1)
+	#[rustfmt::skip]
+	fn update_provider_metadata() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi Projects (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: Dapi RatingLastEra (r:1 w:1)
+	// Storage: Dapi ProviderRatings (r:1 w:1)
+	// Storage: Dapi ProviderRatingAggregate (r:1 w:1)
+	#[rustfmt::skip]
+	fn submit_provider_rating() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderScore (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn update_provider_score() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ProviderUptimeHistory (r:1 w:1)
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
+	#[rustfmt::skip]
+	fn record_provider_uptime() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Projects (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn suspend_project() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Projects (r:1 w:1)
+	#[rustfmt::skip]
+	fn unsuspend_project() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi MonthlyResetInterval (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_monthly_reset_interval() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi QuotaPrice (r:1 w:1)
+	#[rustfmt::skip]
+	fn set_quota_price() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi WhitelistMode (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_whitelist_mode() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi PalletPaused (r:0 w:1)
+	#[rustfmt::skip]
+	fn pause_pallet() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi PalletPaused (r:0 w:1)
+	#[rustfmt::skip]
+	fn unpause_pallet() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi PalletPaused (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	// Storage: Dapi PendingProviderRegistrations (r:0 w:1)
+	#[rustfmt::skip]
+	fn propose_provider_registration() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi PalletPaused (r:1 w:0)
+	// Storage: Dapi PendingProviderRegistrations (r:1 w:1)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	#[rustfmt::skip]
+	fn accept_provider_registration() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi ProviderWhitelist (r:1 w:1)
+	#[rustfmt::skip]
+	fn whitelist_provider() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi ProviderWhitelist (r:1 w:1)
+	#[rustfmt::skip]
+	fn remove_from_whitelist() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: Dapi ChainIds (r:1 w:1)
 	#[rustfmt::skip]
 	fn add_chain_id() -> Weight {
@@ -115,19 +402,126 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Dapi ChainIds (r:1 w:1)
+	#[rustfmt::skip]
+	fn update_chain_metadata() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi ChainIds (r:1 w:1)
+	#[rustfmt::skip]
+	fn add_chain_ids_batch(n: u32, ) -> Weight {
+		(9_512_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_204_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: Dapi ChainIds (r:1 w:1)
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi Projects (r:1 w:0)
+	#[rustfmt::skip]
+	fn remove_chain_ids_batch(n: u32, ) -> Weight {
+		(12_873_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_318_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorBond (r:1 w:0)
 	#[rustfmt::skip]
 	fn add_regulator() -> Weight {
-		(10_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
 	#[rustfmt::skip]
 	fn remove_regulator() -> Weight {
-		(11_000_000 as Weight)
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorBond (r:1 w:0)
+	#[rustfmt::skip]
+	fn batch_add_regulators(n: u32, ) -> Weight {
+		(10_471_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((3_312_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:0 w:1)
+	#[rustfmt::skip]
+	fn batch_remove_regulators(n: u32, ) -> Weight {
+		(10_902_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((2_315_000 as Weight).saturating_mul(n as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorBond (r:1 w:0)
+	// Storage: Dapi RegulatorProposals (r:1 w:1)
+	// Storage: DapiStaking DelegationInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	#[rustfmt::skip]
+	fn propose_regulator(v: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((4_120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorProposals (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	// Storage: DapiStaking DelegationInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	#[rustfmt::skip]
+	fn propose_regulator_removal(v: u32, ) -> Weight {
+		(31_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((4_120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dapi RegulatorBond (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn bond_as_regulator() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi RegulatorBond (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn unbond_regulator() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi RegulatorBond (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn slash_regulator() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 }
 
@@ -137,10 +531,11 @@ impl WeightInfo for () {
 	// Storage: Dapi ChainIds (r:1 w:0)
 	// Storage: System Account (r:2 w:2)
 	// Storage: DapiStaking RewardAccumulator (r:1 w:1)
+	// Storage: Dapi MonthlyResetInterval (r:1 w:0)
 	#[rustfmt::skip]
 	fn register_project() -> Weight {
-		(39_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	// Storage: Dapi Projects (r:1 w:1)
@@ -152,29 +547,251 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// Storage: Dapi Projects (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	#[rustfmt::skip]
+	fn refund_project() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dapi Projects (r:1 w:0)
+	// Storage: Dapi PendingProjectTransfers (r:0 w:1)
+	#[rustfmt::skip]
+	fn transfer_project() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi PendingProjectTransfers (r:1 w:1)
+	// Storage: Dapi Projects (r:1 w:1)
+	#[rustfmt::skip]
+	fn accept_project_transfer() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn register_providers_batch(n: u32, ) -> Weight {
+		(5_284_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((17_936_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn batch_register_providers(n: u32, ) -> Weight {
+		(5_102_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((17_854_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi WhitelistMode (r:1 w:0)
+	// Storage: Dapi ProviderWhitelist (r:1 w:0)
 	// Storage: DapiStaking ProviderInfo (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking EraState (r:1 w:1)
-	// Storage: DapiStaking ProviderEraInfo (r:0 w:1)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: DapiStaking EraProviderCount (r:1 w:1)
+	// Storage: DapiStaking EraProviders (r:0 w:1)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
 	#[rustfmt::skip]
 	fn deposit_provider() -> Weight {
-		(29_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
 	}
 	// Storage: Dapi Providers (r:1 w:1)
 	// Storage: DapiStaking ProviderInfo (r:1 w:1)
 	// Storage: DapiStaking Era (r:1 w:0)
 	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
 	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
 	#[rustfmt::skip]
 	fn unregister_provider() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi ProviderChains (r:1 w:1)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	#[rustfmt::skip]
+	fn add_provider_chain() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderChains (r:1 w:1)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:0)
+	#[rustfmt::skip]
+	fn remove_provider_chain() -> Weight {
 		(22_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderMetadata (r:0 w:1)
+	#[rustfmt::skip]
+	fn update_provider_metadata() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi Projects (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: Dapi RatingLastEra (r:1 w:1)
+	// Storage: Dapi ProviderRatings (r:1 w:1)
+	// Storage: Dapi ProviderRatingAggregate (r:1 w:1)
+	#[rustfmt::skip]
+	fn submit_provider_rating() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ProviderScore (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn update_provider_score() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ProviderUptimeHistory (r:1 w:1)
+	// Storage: DapiStaking ProviderInfo (r:1 w:1)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking ProviderEraInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: DapiStaking EraState (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	// Storage: Dapi ProviderChains (r:1 w:0)
+	// Storage: Dapi IndexedProviderStake (r:1 w:1)
+	// Storage: Dapi ChainStake (r:2 w:1)
+	// Storage: Dapi MostStakedChain (r:0 w:1)
+	#[rustfmt::skip]
+	fn record_provider_uptime() -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi Projects (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	#[rustfmt::skip]
+	fn suspend_project() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Projects (r:1 w:1)
+	#[rustfmt::skip]
+	fn unsuspend_project() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi MonthlyResetInterval (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_monthly_reset_interval() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi QuotaPrice (r:1 w:1)
+	#[rustfmt::skip]
+	fn set_quota_price() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi WhitelistMode (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_whitelist_mode() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi PalletPaused (r:0 w:1)
+	#[rustfmt::skip]
+	fn pause_pallet() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi PalletPaused (r:0 w:1)
+	#[rustfmt::skip]
+	fn unpause_pallet() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi PalletPaused (r:1 w:0)
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	// Storage: Dapi PendingProviderRegistrations (r:0 w:1)
+	#[rustfmt::skip]
+	fn propose_provider_registration() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi PalletPaused (r:1 w:0)
+	// Storage: Dapi PendingProviderRegistrations (r:1 w:1)
+	// Storage: Dapi Providers (r:1 w:1)
+	// Storage: Dapi ChainIds (r:1 w:0)
+	#[rustfmt::skip]
+	fn accept_provider_registration() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi ProviderWhitelist (r:1 w:1)
+	#[rustfmt::skip]
+	fn whitelist_provider() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi ProviderWhitelist (r:1 w:1)
+	#[rustfmt::skip]
+	fn remove_from_whitelist() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	// Storage: Dapi ChainIds (r:1 w:1)
 	#[rustfmt::skip]
 	fn add_chain_id() -> Weight {
@@ -189,18 +806,125 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Dapi ChainIds (r:1 w:1)
+	#[rustfmt::skip]
+	fn update_chain_metadata() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi ChainIds (r:1 w:1)
+	#[rustfmt::skip]
+	fn add_chain_ids_batch(n: u32, ) -> Weight {
+		(9_512_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_204_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: Dapi ChainIds (r:1 w:1)
+	// Storage: Dapi Providers (r:1 w:0)
+	// Storage: Dapi Projects (r:1 w:0)
+	#[rustfmt::skip]
+	fn remove_chain_ids_batch(n: u32, ) -> Weight {
+		(12_873_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((1_318_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
 	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorBond (r:1 w:0)
 	#[rustfmt::skip]
 	fn add_regulator() -> Weight {
-		(10_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
 	#[rustfmt::skip]
 	fn remove_regulator() -> Weight {
-		(11_000_000 as Weight)
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorBond (r:1 w:0)
+	#[rustfmt::skip]
+	fn batch_add_regulators(n: u32, ) -> Weight {
+		(10_471_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((3_312_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:0 w:1)
+	#[rustfmt::skip]
+	fn batch_remove_regulators(n: u32, ) -> Weight {
+		(10_902_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((2_315_000 as Weight).saturating_mul(n as Weight))
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorBond (r:1 w:0)
+	// Storage: Dapi RegulatorProposals (r:1 w:1)
+	// Storage: DapiStaking DelegationInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	#[rustfmt::skip]
+	fn propose_regulator(v: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((4_120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:1)
+	// Storage: Dapi RegulatorProposals (r:1 w:1)
+	// Storage: Dapi RegulatorActionLog (r:1 w:1)
+	// Storage: DapiStaking DelegationInfo (r:1 w:0)
+	// Storage: DapiStaking Era (r:1 w:0)
+	// Storage: DapiStaking EraState (r:1 w:0)
+	#[rustfmt::skip]
+	fn propose_regulator_removal(v: u32, ) -> Weight {
+		(31_000_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((4_120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dapi RegulatorBond (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn bond_as_regulator() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi Regulators (r:1 w:0)
+	// Storage: Dapi RegulatorBond (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn unbond_regulator() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dapi RegulatorBond (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn slash_regulator() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+//! RPC interface for the validator set pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_validator_set::runtime_api::ValidatorSetApi as ValidatorSetRuntimeApi;
+
+#[rpc(client, server)]
+pub trait ValidatorSetApi<BlockHash, AccountId> {
+	/// Returns the validator set assembled for `session_index`.
+	#[method(name = "validatorSet_atSession")]
+	fn at_session(
+		&self,
+		session_index: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Vec<AccountId>>>;
+}
+
+/// Provides RPC methods to query validator set data.
+pub struct ValidatorSet<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ValidatorSet<C, Block> {
+	/// Creates a new instance of the ValidatorSet RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code of a failed runtime call.
+const RUNTIME_ERROR: i32 = 1;
+
+#[async_trait]
+impl<C, Block, AccountId> ValidatorSetApiServer<<Block as BlockT>::Hash, AccountId>
+	for ValidatorSet<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ValidatorSetRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+{
+	fn at_session(
+		&self,
+		session_index: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Vec<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.validator_set_at_session(&at, session_index).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				RUNTIME_ERROR,
+				"Unable to query validator set.",
+				Some(e.to_string()),
+			))
+			.into()
+		})
+	}
+}
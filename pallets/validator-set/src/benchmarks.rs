@@ -97,6 +97,36 @@ benchmarks! {
 		assert_last_event::<T>(Event::NewInvulnerables(new_invulnerables).into());
 	}
 
+	add_invulnerable {
+		let b in 1 .. T::MaxInvulnerables::get() - 1;
+		let mut invulnerables = register_validators::<T>(b + 1);
+		let new_invulnerable = invulnerables.pop().unwrap();
+		<Invulnerables<T>>::put(invulnerables);
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		assert_ok!(
+			<ValidatorSet<T>>::add_invulnerable(origin, new_invulnerable.clone())
+		);
+	}
+	verify {
+		assert_last_event::<T>(Event::InvulnerableAdded(new_invulnerable).into());
+	}
+
+	remove_invulnerable {
+		let b in (T::MinInvulnerables::get() + 1) .. T::MaxInvulnerables::get();
+		let invulnerables = register_validators::<T>(b);
+		let removed = invulnerables[0].clone();
+		<Invulnerables<T>>::put(invulnerables);
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		assert_ok!(
+			<ValidatorSet<T>>::remove_invulnerable(origin, removed.clone())
+		);
+	}
+	verify {
+		assert_last_event::<T>(Event::InvulnerableRemoved(removed).into());
+	}
+
 	set_desired_candidates {
 		let max: u32 = 999;
 		let origin = T::UpdateOrigin::successful_origin();
@@ -121,6 +151,30 @@ benchmarks! {
 		assert_last_event::<T>(Event::NewCandidacyBond(bond).into());
 	}
 
+	set_slash_destination {
+		let dest: T::AccountId = account("dest", 0, SEED);
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		assert_ok!(
+			<ValidatorSet<T>>::set_slash_destination(origin, dest.clone())
+		);
+	}
+	verify {
+		assert_last_event::<T>(Event::SlashDestinationSet { dest }.into());
+	}
+
+	clear_slash_destination {
+		<SlashDestination<T>>::put(account::<T::AccountId>("dest", 0, SEED));
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		assert_ok!(
+			<ValidatorSet<T>>::clear_slash_destination(origin)
+		);
+	}
+	verify {
+		assert_last_event::<T>(Event::SlashDestinationCleared.into());
+	}
+
 	// worse case is when we have all the max-candidate slots filled except one, and we fill that one.
 	register_as_candidate {
 		let c in 1 .. T::MaxCandidates::get();
@@ -161,14 +215,30 @@ benchmarks! {
 		assert_last_event::<T>(Event::CandidateRemoved(leaving).into());
 	}
 
+	leave_waiting_list {
+		let w in 1 .. T::MaxWaitingCandidates::get();
+		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
+		<DesiredCandidates<T>>::put(0);
+
+		register_validators::<T>(w);
+		register_candidates::<T>(w);
+
+		let leaving = <WaitingList<T>>::get().last().unwrap().who.clone();
+		whitelist!(leaving);
+	}: _(RawOrigin::Signed(leaving.clone()))
+	verify {
+		assert_last_event::<T>(Event::CandidateLeftWaitingList(leaving).into());
+	}
+
 	// worse case is paying a non-existing candidate account.
 	note_author {
 		<CandidacyBond<T>>::put(T::Currency::minimum_balance());
 		T::Currency::make_free_balance_be(
 			&<ValidatorSet<T>>::account_id(),
-			T::Currency::minimum_balance() * 4u32.into(),
+			T::Currency::minimum_balance() * 100u32.into(),
 		);
 		let author = account("author", 0, SEED);
+		<ValidatorActiveSessions<T>>::insert(&author, T::MinSessionsForRewards::get());
 		let new_block: T::BlockNumber = 10u32.into();
 
 		frame_system::Pallet::<T>::set_block_number(new_block);
@@ -176,10 +246,33 @@ benchmarks! {
 	}: {
 		<ValidatorSet<T> as EventHandler<_, _>>::note_author(author.clone())
 	} verify {
-		assert!(T::Currency::free_balance(&author) > 0u32.into());
+		let reward = T::AuthorRewardPercent::get() * (T::Currency::minimum_balance() * 99u32.into());
+		assert_eq!(T::Currency::free_balance(&author), reward);
 		assert_eq!(frame_system::Pallet::<T>::block_number(), new_block);
 	}
 
+	// worst case is paying every author of the ended session.
+	end_session {
+		let a in 1 .. T::MaxCandidates::get();
+		let _ = <SessionBlocksAuthored<T>>::remove_all(None);
+		T::Currency::make_free_balance_be(
+			&<ValidatorSet<T>>::account_id(),
+			T::Currency::minimum_balance() * (2 * a + 1).into(),
+		);
+		for i in 0..a {
+			let who: T::AccountId = account("author", i, SEED);
+			<SessionBlocksAuthored<T>>::insert(&who, 1);
+			<ValidatorActiveSessions<T>>::insert(&who, T::MinSessionsForRewards::get());
+		}
+	}: {
+		<ValidatorSet<T> as SessionManager<_>>::end_session(0)
+	} verify {
+		assert_last_event::<T>(Event::SessionRewardsPaid {
+			session_index: 0,
+			per_validator: T::Currency::minimum_balance() * 2u32.into(),
+		}.into());
+	}
+
 	// worst case for new session.
 	new_session {
 		let r in 1 .. T::MaxCandidates::get();
@@ -228,6 +321,23 @@ benchmarks! {
 			assert!(<Candidates<T>>::get().len() == pre_length);
 		}
 	}
+
+	prune_validator_set_snapshots {
+		let s in 1 .. T::MaxSessionSnapshots::get();
+		let validators: BoundedVec<_, MaxValidators<T>> =
+			vec![whitelisted_caller()].try_into().unwrap();
+		for session in 0..s {
+			<ValidatorSetBySession<T>>::insert(session, validators.clone());
+		}
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		assert_ok!(
+			<ValidatorSet<T>>::prune_validator_set_snapshots(origin, s)
+		);
+	}
+	verify {
+		assert_eq!(<ValidatorSetBySession<T>>::iter().count(), 0);
+	}
 }
 
 #[cfg(test)]
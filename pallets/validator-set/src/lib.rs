@@ -21,7 +21,14 @@
 //! ### Rewards
 //!
 //! The Validator Set pallet maintains an on-chain account (the "Pot"). In each block, the validator
-//! who authored it receives total value of the Pot and transaction fees within the block.
+//! who authored it receives `AuthorRewardPercent` of the Pot. The rest of the Pot is split equally
+//! at the end of each session between the validators which authored blocks in it.
+//!
+//! ### Snapshots
+//!
+//! Validator set assembled for each session is kept in [`ValidatorSetBySession`], so that light
+//! clients can query the authority set of recent sessions. At most `MaxSessionSnapshots` sessions
+//! are retained.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -29,13 +36,17 @@ use frame_support::{
 	ensure,
 	pallet_prelude::*,
 	traits::{
-		Currency, ExistenceRequirement::KeepAlive, Get, ReservableCurrency, ValidatorRegistration,
+		Currency, ExistenceRequirement::KeepAlive, Get, Randomness, ReservableCurrency,
+		ValidatorRegistration,
 	},
 	PalletId,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AccountIdConversion, CheckedSub, Convert, Saturating, Zero},
+	traits::{
+		AccountIdConversion, CheckedSub, Convert, Hash as HashT, Saturating, TrailingZeroInput,
+		Zero,
+	},
 	Perbill,
 };
 use sp_staking::SessionIndex;
@@ -50,6 +61,7 @@ mod tests;
 #[cfg(any(feature = "runtime-benchmarks"))]
 pub mod benchmarks;
 
+pub mod runtime_api;
 pub mod weights;
 
 pub use pallet::*;
@@ -71,6 +83,14 @@ pub mod pallet {
 		}
 	}
 
+	/// Max number of validators assembled for a session, invulnerables included.
+	pub struct MaxValidators<T>(PhantomData<T>);
+	impl<T: Config> Get<u32> for MaxValidators<T> {
+		fn get() -> u32 {
+			T::MaxCandidates::get().saturating_add(T::MaxInvulnerables::get())
+		}
+	}
+
 	/// Basic information about a candidate.
 	#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 	pub struct CandidateInfo<AccountId, Balance> {
@@ -80,6 +100,16 @@ pub mod pallet {
 		pub deposit: Balance,
 	}
 
+	/// Authoring track record of a validator.
+	#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct ValidatorPerfInfo {
+		/// Number of blocks authored since becoming a validator.
+		pub blocks_authored: u32,
+		/// Number of sessions in a row in which less than `MinBlocksPerSession` blocks were
+		/// authored.
+		pub consecutive_missed_sessions: u32,
+	}
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -110,15 +140,59 @@ pub mod pallet {
 		/// Maximum number of invulnerables.
 		type MaxInvulnerables: Get<u32>;
 
+		/// Minimum number of invulnerables that must remain when removing one.
+		type MinInvulnerables: Get<u32>;
+
+		/// Maximum number of accounts waiting for a free candidate slot.
+		type MaxWaitingCandidates: Get<u32>;
+
 		/// Validator will be kicked if block is not produced in threshold.
 		type KickThreshold: Get<Self::BlockNumber>;
 
+		/// Number of authored blocks which extend the kick threshold of a candidate by another
+		/// `KickThreshold`, unless it underperformed in its last session (set 0 to disable).
+		#[pallet::constant]
+		type KickGraceFactor: Get<u32>;
+
 		/// Validate a user is registered.
 		type ValidatorRegistration: ValidatorRegistration<Self::ValidatorId>;
 
 		/// How many in percentage stakes of kicked validators should be slashed (set 0 to disable).
 		type SlashRatio: Get<Perbill>;
 
+		/// Upper bound of the slash ratio, which grows with every previous slash of a validator.
+		type MaxSlashRatio: Get<Perbill>;
+
+		/// Minimum number of blocks a validator should author in a session to not be considered
+		/// underperforming.
+		type MinBlocksPerSession: Get<u32>;
+
+		/// Number of underperforming sessions after which a candidate is slashed and removed
+		/// (set 0 to disable).
+		type SlashAccumulationThreshold: Get<u32>;
+
+		/// Slash ratio applied per underperforming session once the threshold is reached.
+		type PerEpochSlashRate: Get<Perbill>;
+
+		/// Number of sessions a validator should be part of before receiving block rewards.
+		type MinSessionsForRewards: Get<u32>;
+
+		/// Share of the Pot paid to the author of each block. The rest is split at session end.
+		#[pallet::constant]
+		type AuthorRewardPercent: Get<Perbill>;
+
+		/// Maximum number of session validator set snapshots to keep.
+		type MaxSessionSnapshots: Get<u32>;
+
+		/// Source of randomness used to select the session candidates.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// Whether session candidates are selected at random, weighted by the number of blocks
+		/// they authored during the previous session. When disabled, all active candidates are
+		/// kept.
+		#[pallet::constant]
+		type UseWeightedSelection: Get<bool>;
+
 		/// The weight information of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -134,12 +208,49 @@ pub mod pallet {
 	pub type Candidates<T: Config> =
 		StorageValue<_, Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>, ValueQuery>;
 
+	/// Accounts waiting for a free candidate slot, in order of registration. Their bond is
+	/// reserved already.
+	#[pallet::storage]
+	#[pallet::getter(fn waiting_list)]
+	pub type WaitingList<T: Config> =
+		StorageValue<_, Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>, ValueQuery>;
+
 	/// Last block authored by validator.
 	#[pallet::storage]
 	#[pallet::getter(fn last_authored_block)]
 	pub type LastAuthoredBlock<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber, ValueQuery>;
 
+	/// Number of blocks authored by validator during the current session.
+	#[pallet::storage]
+	#[pallet::getter(fn session_blocks_authored)]
+	pub type SessionBlocksAuthored<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Number of sessions in which a candidate authored less than `MinBlocksPerSession` blocks.
+	#[pallet::storage]
+	#[pallet::getter(fn low_performance_epochs)]
+	pub type LowPerformanceEpochs<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Authoring track record of each validator.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_performance)]
+	pub type ValidatorPerformance<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ValidatorPerfInfo, ValueQuery>;
+
+	/// Number of sessions a validator has been part of the validator set.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_active_sessions)]
+	pub type ValidatorActiveSessions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Number of times each validator has been slashed.
+	#[pallet::storage]
+	#[pallet::getter(fn slash_history)]
+	pub type SlashHistory<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
 	/// Desired number of candidates.
 	///
 	/// This should ideally always be less than [`Config::MaxCandidates`] for weights to be correct.
@@ -154,11 +265,17 @@ pub mod pallet {
 	#[pallet::getter(fn candidacy_bond)]
 	pub type CandidacyBond<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
-	/// Destination account for slashed amount.
+	/// Destination account for slashed amount. Slashed amounts are burned when unset.
 	#[pallet::storage]
 	#[pallet::getter(fn slash_destination)]
 	pub type SlashDestination<T> = StorageValue<_, <T as frame_system::Config>::AccountId>;
 
+	/// Validator set assembled for a session.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_set_by_session)]
+	pub type ValidatorSetBySession<T: Config> =
+		StorageMap<_, Twox64Concat, SessionIndex, BoundedVec<T::AccountId, MaxValidators<T>>>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub invulnerables: Vec<T::AccountId>,
@@ -206,11 +323,22 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		NewInvulnerables(Vec<T::AccountId>),
+		InvulnerableAdded(T::AccountId),
+		InvulnerableRemoved(T::AccountId),
 		NewDesiredCandidates(u32),
 		NewCandidacyBond(BalanceOf<T>),
+		SlashDestinationSet { dest: T::AccountId },
+		SlashDestinationCleared,
 		CandidateAdded(T::AccountId, BalanceOf<T>),
 		CandidateRemoved(T::AccountId),
-		CandidateSlashed(T::AccountId),
+		CandidateWaiting(T::AccountId, BalanceOf<T>),
+		CandidateLeftWaitingList(T::AccountId),
+		CandidateSlashed { who: T::AccountId, slash_amount: BalanceOf<T>, slash_count: u32 },
+		AccumulatedSlashApplied { who: T::AccountId, epochs: u32, slash_amount: BalanceOf<T> },
+		RewardWithheldForNewValidator { who: T::AccountId },
+		BlockAuthorRewarded { author: T::AccountId, amount: BalanceOf<T> },
+		SessionRewardsPaid { session_index: SessionIndex, per_validator: BalanceOf<T> },
+		ValidatorPerformanceUpdated { who: T::AccountId, blocks_authored: u32 },
 	}
 
 	#[pallet::error]
@@ -224,6 +352,11 @@ pub mod pallet {
 		AlreadyInvulnerable,
 		NoAssociatedValidatorId,
 		ValidatorNotRegistered,
+		NotWaitingCandidate,
+		WaitingListFull,
+		TooManyInvulnerables,
+		TooFewInvulnerables,
+		HardCapReached,
 	}
 
 	#[pallet::hooks]
@@ -238,12 +371,16 @@ pub mod pallet {
 			new: Vec<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
-			// we trust origin calls, this is just a for more accurate benchmarking
-			if (new.len() as u32) > T::MaxInvulnerables::get() {
-				log::warn!(
-					"invulnerables > T::MaxInvulnerables; you might need to run benchmarks again"
-				);
-			}
+			let candidates = <Candidates<T>>::decode_len().unwrap_or_default() as u32;
+			ensure!(
+				(new.len() as u32).saturating_add(candidates) <= MaxValidators::<T>::get(),
+				Error::<T>::HardCapReached
+			);
+			// Validator sets have to fit in `ValidatorSetBySession`
+			ensure!(
+				(new.len() as u32) <= T::MaxInvulnerables::get(),
+				Error::<T>::TooManyInvulnerables
+			);
 
 			for account_id in &new {
 				let validator_key = T::ValidatorIdOf::convert(account_id.clone())
@@ -259,6 +396,59 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Add a single invulnerable validator, keeping the existing ones.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_invulnerable(T::MaxInvulnerables::get()))]
+		pub fn add_invulnerable(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let validator_key = T::ValidatorIdOf::convert(who.clone())
+				.ok_or(Error::<T>::NoAssociatedValidatorId)?;
+			ensure!(
+				T::ValidatorRegistration::is_registered(&validator_key),
+				Error::<T>::ValidatorNotRegistered
+			);
+
+			<Invulnerables<T>>::try_mutate(|invulnerables| -> DispatchResult {
+				ensure!(!invulnerables.contains(&who), Error::<T>::AlreadyInvulnerable);
+				ensure!(
+					(invulnerables.len() as u32) < T::MaxInvulnerables::get(),
+					Error::<T>::TooManyInvulnerables
+				);
+				invulnerables.push(who.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::InvulnerableAdded(who));
+			Ok(().into())
+		}
+
+		/// Remove a single invulnerable validator. At least `MinInvulnerables` must remain.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_invulnerable(T::MaxInvulnerables::get()))]
+		pub fn remove_invulnerable(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			<Invulnerables<T>>::try_mutate(|invulnerables| -> DispatchResult {
+				let index = invulnerables
+					.iter()
+					.position(|invulnerable| *invulnerable == who)
+					.ok_or(Error::<T>::Unknown)?;
+				ensure!(
+					(invulnerables.len() as u32) > T::MinInvulnerables::get(),
+					Error::<T>::TooFewInvulnerables
+				);
+				invulnerables.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::InvulnerableRemoved(who));
+			Ok(().into())
+		}
+
 		/// Set the ideal number of validators (not including the invulnerables).
 		/// If lowering this number, then the number of running validators could be higher than this
 		/// figure. Aside from that edge case, there should be no other way to have more validators
@@ -290,17 +480,43 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Set the account receiving slashed amounts.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_slash_destination())]
+		pub fn set_slash_destination(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			<SlashDestination<T>>::put(&dest);
+			Self::deposit_event(Event::SlashDestinationSet { dest });
+			Ok(().into())
+		}
+
+		/// Remove the account receiving slashed amounts, so that they are burned.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::clear_slash_destination())]
+		pub fn clear_slash_destination(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			<SlashDestination<T>>::kill();
+			Self::deposit_event(Event::SlashDestinationCleared);
+			Ok(().into())
+		}
+
 		/// Register this account as a validator candidate. The account must (a) already have
 		/// registered session keys and (b) be able to reserve the `CandidacyBond`.
 		///
+		/// If all candidate slots are taken, the account joins the waiting list instead and is
+		/// promoted to candidate once a slot frees up.
+		///
 		/// This call is not available to `Invulnerable` validators.
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::register_as_candidate(T::MaxCandidates::get()))]
 		pub fn register_as_candidate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
-			let length = <Candidates<T>>::decode_len().unwrap_or_default();
-			ensure!((length as u32) < Self::desired_candidates(), Error::<T>::TooManyCandidates);
 			ensure!(!<Invulnerables<T>>::get().contains(&who), Error::<T>::AlreadyInvulnerable);
+			ensure!(
+				!Self::waiting_list().iter().any(|waiting| waiting.who == who),
+				Error::<T>::AlreadyCandidate
+			);
 
 			let validator_key = T::ValidatorIdOf::convert(who.clone())
 				.ok_or(Error::<T>::NoAssociatedValidatorId)?;
@@ -311,6 +527,13 @@ pub mod pallet {
 
 			let deposit = <CandidacyBond<T>>::get();
 			let new_candidate = CandidateInfo { who: who.clone(), deposit };
+			let length = <Candidates<T>>::decode_len().unwrap_or_default();
+			if (length as u32) >= Self::desired_candidates() {
+				return Self::join_waiting_list(new_candidate)
+			}
+			// `desired_candidates` may be set above the hard cap, which is never exceeded.
+			ensure!((length as u32) < T::MaxCandidates::get(), Error::<T>::HardCapReached);
+
 			let _ = <Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
 				if candidates.into_iter().any(|candidate| candidate.who == who) {
 					Err(Error::<T>::AlreadyCandidate)?
@@ -342,7 +565,42 @@ pub mod pallet {
 				Self::candidates().len() as u32 > T::MinCandidates::get(),
 				Error::<T>::TooFewCandidates
 			);
-			let _ = Self::try_remove_candidate(&who, false)?;
+			let _ = Self::try_remove_candidate(&who, None)?;
+			Ok(().into())
+		}
+
+		/// Leave the waiting list of candidates. The reserved `CandidacyBond` is unreserved
+		/// immediately.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::leave_waiting_list(T::MaxWaitingCandidates::get()))]
+		pub fn leave_waiting_list(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			<WaitingList<T>>::try_mutate(|waiting_list| -> DispatchResult {
+				let index = waiting_list
+					.iter()
+					.position(|waiting| waiting.who == who)
+					.ok_or(Error::<T>::NotWaitingCandidate)?;
+				T::Currency::unreserve(&who, waiting_list[index].deposit);
+				waiting_list.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CandidateLeftWaitingList(who));
+			Ok(().into())
+		}
+
+		/// Remove validator set snapshots of all sessions before `older_than_session`.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::prune_validator_set_snapshots(T::MaxSessionSnapshots::get()))]
+		pub fn prune_validator_set_snapshots(
+			origin: OriginFor<T>,
+			older_than_session: SessionIndex,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let outdated = <ValidatorSetBySession<T>>::iter_keys()
+				.filter(|session| *session < older_than_session)
+				.collect::<Vec<_>>();
+			for session in outdated {
+				<ValidatorSetBySession<T>>::remove(session);
+			}
 			Ok(().into())
 		}
 	}
@@ -353,9 +611,47 @@ pub mod pallet {
 			T::PalletId::get().into_account_truncating()
 		}
 
+		/// Balance of the Pot above the existential deposit.
+		fn distributable_pot() -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::account_id())
+				.checked_sub(&T::Currency::minimum_balance())
+				.unwrap_or_else(Zero::zero)
+		}
+
+		/// Split the Pot equally between the eligible validators which authored blocks in the
+		/// ended session. Returns the number of validators which authored blocks.
+		fn distribute_session_rewards(index: SessionIndex) -> u32 {
+			let authors: Vec<T::AccountId> = <SessionBlocksAuthored<T>>::iter_keys().collect();
+			let eligible: Vec<&T::AccountId> = authors
+				.iter()
+				.filter(|who| {
+					Self::validator_active_sessions(who) >= T::MinSessionsForRewards::get()
+				})
+				.collect();
+			if eligible.is_empty() {
+				return authors.len() as u32
+			}
+
+			let per_validator = Self::distributable_pot() / (eligible.len() as u32).into();
+			if per_validator.is_zero() {
+				return authors.len() as u32
+			}
+			let pot = Self::account_id();
+			for who in eligible {
+				if let Err(e) = T::Currency::transfer(&pot, who, per_validator, KeepAlive) {
+					log::warn!("failed to pay session reward to {:?}: {:?}", who, e);
+				}
+			}
+			Self::deposit_event(Event::SessionRewardsPaid { session_index: index, per_validator });
+			authors.len() as u32
+		}
+
 		/// Removes a candidate if they exist and sends them back their deposit
-		/// If second argument is `true` then a candidate will be slashed
-		fn try_remove_candidate(who: &T::AccountId, slash: bool) -> Result<usize, DispatchError> {
+		/// If a slash ratio is given then the candidate's deposit will be slashed by it
+		fn try_remove_candidate(
+			who: &T::AccountId,
+			slash_ratio: Option<Perbill>,
+		) -> Result<usize, DispatchError> {
 			let current_count =
 				<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
 					let index = candidates
@@ -364,8 +660,10 @@ pub mod pallet {
 						.ok_or(Error::<T>::NotCandidate)?;
 					let deposit = candidates[index].deposit;
 
-					if slash {
-						let slash_amount = T::SlashRatio::get() * deposit;
+					if let Some(base_ratio) = slash_ratio {
+						let previous_slashes = Self::slash_history(who);
+						let slash_amount =
+							Self::escalated_slash_ratio(base_ratio, previous_slashes) * deposit;
 						let remain = deposit - slash_amount;
 						let (imbalance, _) = T::Currency::slash_reserved(&who, slash_amount);
 						T::Currency::unreserve(&who, remain);
@@ -374,12 +672,24 @@ pub mod pallet {
 							T::Currency::resolve_creating(&dest, imbalance);
 						}
 
-						Self::deposit_event(Event::CandidateSlashed(who.clone()));
+						let slash_count = previous_slashes.saturating_add(1);
+						<SlashHistory<T>>::insert(who, slash_count);
+						Self::deposit_event(Event::CandidateSlashed {
+							who: who.clone(),
+							slash_amount,
+							slash_count,
+						});
 					} else {
 						T::Currency::unreserve(&who, deposit);
 					}
 					candidates.remove(index);
 					<LastAuthoredBlock<T>>::remove(who.clone());
+					<LowPerformanceEpochs<T>>::remove(who);
+					<ValidatorPerformance<T>>::remove(who);
+					<ValidatorActiveSessions<T>>::remove(who);
+					if (candidates.len() as u32) < Self::desired_candidates() {
+						Self::promote_waiting_candidate(candidates);
+					}
 					Ok(candidates.len())
 				})?;
 
@@ -387,6 +697,48 @@ pub mod pallet {
 			Ok(current_count)
 		}
 
+		/// Adds `new_candidate` to the end of the waiting list, reserving its deposit.
+		fn join_waiting_list(
+			new_candidate: CandidateInfo<T::AccountId, BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				!Self::candidates().iter().any(|candidate| candidate.who == new_candidate.who),
+				Error::<T>::AlreadyCandidate
+			);
+			let CandidateInfo { who, deposit } = new_candidate.clone();
+			<WaitingList<T>>::try_mutate(|waiting_list| -> DispatchResult {
+				ensure!(
+					(waiting_list.len() as u32) < T::MaxWaitingCandidates::get(),
+					Error::<T>::WaitingListFull
+				);
+				T::Currency::reserve(&who, deposit)?;
+				waiting_list.push(new_candidate);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CandidateWaiting(who, deposit));
+			Ok(().into())
+		}
+
+		/// Moves the first account of the waiting list to `candidates`, if there is any.
+		fn promote_waiting_candidate(
+			candidates: &mut Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>,
+		) {
+			let mut waiting_list = Self::waiting_list();
+			if waiting_list.is_empty() {
+				return
+			}
+
+			let promoted = waiting_list.remove(0);
+			<WaitingList<T>>::put(waiting_list);
+			<LastAuthoredBlock<T>>::insert(
+				promoted.who.clone(),
+				frame_system::Pallet::<T>::block_number() + T::KickThreshold::get(),
+			);
+			Self::deposit_event(Event::CandidateAdded(promoted.who.clone(), promoted.deposit));
+			candidates.push(promoted);
+		}
+
 		/// Assemble the current set of candidates and invulnerables into the next validator set.
 		///
 		/// This is done on the fly, as frequent as we are told to do so, as the session manager.
@@ -396,23 +748,47 @@ pub mod pallet {
 			validators
 		}
 
-		/// Kicks out and candidates that did not produce a block in the kick threshold.
+		/// Kick threshold of `who`, extended by its authoring track record unless it
+		/// underperformed in its last session.
+		pub fn effective_kick_threshold(who: &T::AccountId) -> T::BlockNumber {
+			let kick_threshold = T::KickThreshold::get();
+			let grace_factor = T::KickGraceFactor::get();
+			let performance = Self::validator_performance(who);
+			if grace_factor.is_zero() || !performance.consecutive_missed_sessions.is_zero() {
+				return kick_threshold
+			}
+			let multiplier = (performance.blocks_authored / grace_factor).saturating_add(1);
+			kick_threshold.saturating_mul(multiplier.into())
+		}
+
+		/// Kicks out and candidates that did not produce a block in their kick threshold.
+		///
+		/// With weighted selection, candidates which were left out of the validator set of the
+		/// `ended` session had no slot to author in, so they are kept.
 		pub fn kick_stale_candidates(
 			candidates: Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>,
+			ended: Option<SessionIndex>,
 		) -> Vec<T::AccountId> {
 			let now = frame_system::Pallet::<T>::block_number();
-			let kick_threshold = T::KickThreshold::get();
+			let ended_validators = ended
+				.filter(|_| T::UseWeightedSelection::get())
+				.and_then(Self::validator_set_at_session);
 			let new_candidates = candidates
 				.into_iter()
 				.filter_map(|c| {
 					let last_block = <LastAuthoredBlock<T>>::get(&c.who);
 					let since_last = now.saturating_sub(last_block);
-					if since_last < kick_threshold ||
+					let left_out = ended_validators
+						.as_ref()
+						.map_or(false, |validators| !validators.contains(&c.who));
+					if left_out ||
+						since_last < Self::effective_kick_threshold(&c.who) ||
 						Self::candidates().len() as u32 <= T::MinCandidates::get()
 					{
 						Some(c.who)
 					} else {
-						let outcome = Self::try_remove_candidate(&c.who, true);
+						let outcome =
+							Self::try_remove_candidate(&c.who, Some(T::SlashRatio::get()));
 						if let Err(why) = outcome {
 							debug_assert!(false, "failed to remove candidate {:?}", why);
 						}
@@ -422,6 +798,145 @@ pub mod pallet {
 				.collect::<Vec<_>>();
 			new_candidates
 		}
+
+		/// Slash ratio for a candidate that underperformed during `epochs` sessions.
+		pub fn graduated_slash_ratio(epochs: u32) -> Perbill {
+			Perbill::from_parts(T::PerEpochSlashRate::get().deconstruct().saturating_mul(epochs))
+		}
+
+		/// Slash ratio for a validator that has already been slashed `previous_slashes` times,
+		/// growing linearly with `base_ratio` up to `MaxSlashRatio`.
+		pub fn escalated_slash_ratio(base_ratio: Perbill, previous_slashes: u32) -> Perbill {
+			Perbill::from_parts(
+				base_ratio.deconstruct().saturating_mul(previous_slashes.saturating_add(1)),
+			)
+			.min(T::MaxSlashRatio::get())
+		}
+
+		/// Slashes and removes candidates that underperformed during at least
+		/// `SlashAccumulationThreshold` sessions.
+		pub fn apply_accumulated_slashes() {
+			let threshold = T::SlashAccumulationThreshold::get();
+			if threshold.is_zero() {
+				return
+			}
+
+			for candidate in Self::candidates() {
+				let epochs = Self::low_performance_epochs(&candidate.who);
+				if epochs < threshold || Self::candidates().len() as u32 <= T::MinCandidates::get()
+				{
+					continue
+				}
+
+				let slash_ratio = Self::graduated_slash_ratio(epochs);
+				let previous_slashes = Self::slash_history(&candidate.who);
+				let slash_amount =
+					Self::escalated_slash_ratio(slash_ratio, previous_slashes) * candidate.deposit;
+				match Self::try_remove_candidate(&candidate.who, Some(slash_ratio)) {
+					Ok(_) => Self::deposit_event(Event::AccumulatedSlashApplied {
+						who: candidate.who,
+						epochs,
+						slash_amount,
+					}),
+					Err(why) => debug_assert!(false, "failed to slash candidate {:?}", why),
+				}
+			}
+		}
+
+		/// Counts a low performance session for `candidates` that were part of the validator set
+		/// of session `ended` but authored less than `MinBlocksPerSession` blocks, and updates
+		/// their track record.
+		fn note_low_performance(ended: SessionIndex, candidates: &[T::AccountId]) {
+			let validators = match Self::validator_set_at_session(ended) {
+				Some(validators) => validators,
+				None => return,
+			};
+
+			let min_blocks = T::MinBlocksPerSession::get();
+			for who in candidates.iter().filter(|who| validators.contains(who)) {
+				let missed = Self::session_blocks_authored(who) < min_blocks;
+				if missed {
+					<LowPerformanceEpochs<T>>::mutate(who, |epochs| {
+						*epochs = epochs.saturating_add(1)
+					});
+				}
+				let blocks_authored = <ValidatorPerformance<T>>::mutate(who, |performance| {
+					performance.consecutive_missed_sessions = if missed {
+						performance.consecutive_missed_sessions.saturating_add(1)
+					} else {
+						0
+					};
+					performance.blocks_authored
+				});
+				Self::deposit_event(Event::ValidatorPerformanceUpdated {
+					who: who.clone(),
+					blocks_authored,
+				});
+			}
+		}
+
+		/// Select `n` of `candidates` at random without replacement.
+		///
+		/// Each candidate is weighted by the number of blocks it authored during the current
+		/// session plus one, so that candidates without authored blocks can still be selected.
+		/// All candidates are returned if there are no more than `n` of them.
+		pub fn weighted_select_candidates(
+			candidates: Vec<T::AccountId>,
+			n: u32,
+			seed: T::Hash,
+		) -> Vec<T::AccountId> {
+			if candidates.len() <= n as usize {
+				return candidates
+			}
+
+			let mut weighted = candidates
+				.into_iter()
+				.map(|who| {
+					let weight = u64::from(Self::session_blocks_authored(&who)).saturating_add(1);
+					(who, weight)
+				})
+				.collect::<Vec<_>>();
+			let mut selected = Vec::with_capacity(n as usize);
+			for round in 0..n {
+				let total = weighted.iter().fold(0u64, |acc, (_, w)| acc.saturating_add(*w));
+				let random = T::Hashing::hash_of(&(seed, round));
+				let random = u64::decode(&mut TrailingZeroInput::new(random.as_ref()))
+					.expect("infinite length input; no invalid inputs for type; qed");
+				let mut target = random % total;
+				let index = weighted
+					.iter()
+					.position(|(_, weight)| {
+						if target < *weight {
+							true
+						} else {
+							target -= *weight;
+							false
+						}
+					})
+					.unwrap_or_default();
+				selected.push(weighted.swap_remove(index).0);
+			}
+			selected
+		}
+
+		/// Store the validator set of session `index`, evicting the oldest snapshot if needed.
+		fn snapshot_validator_set(index: SessionIndex, validators: &[T::AccountId]) {
+			// At most `MaxInvulnerables` invulnerables and `MaxCandidates` candidates are assembled
+			let mut validators = validators.to_vec();
+			validators.truncate(MaxValidators::<T>::get() as usize);
+			let snapshot = BoundedVec::<_, MaxValidators<T>>::try_from(validators)
+				.expect("truncated to the bound; qed");
+
+			<ValidatorSetBySession<T>>::insert(index, snapshot);
+			if let Some(oldest) = index.checked_sub(T::MaxSessionSnapshots::get()) {
+				<ValidatorSetBySession<T>>::remove(oldest);
+			}
+		}
+
+		/// Validator set assembled for session `index`, if it is still retained.
+		pub fn validator_set_at_session(index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			<ValidatorSetBySession<T>>::get(index).map(|snapshot| snapshot.into_inner())
+		}
 	}
 }
 
@@ -430,12 +945,25 @@ impl<T: Config + pallet_authorship::Config>
 	pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T>
 {
 	fn note_author(author: T::AccountId) {
-		let pot = Self::account_id();
-		let reward = T::Currency::free_balance(&pot)
-			.checked_sub(&T::Currency::minimum_balance())
-			.unwrap_or_else(Zero::zero);
-		let success = T::Currency::transfer(&pot, &author, reward, KeepAlive);
-		debug_assert!(success.is_ok());
+		// rewards of new validators are kept in the pot for eligible ones.
+		if Self::validator_active_sessions(&author) >= T::MinSessionsForRewards::get() {
+			let reward = T::AuthorRewardPercent::get() * Self::distributable_pot();
+			if !reward.is_zero() {
+				let success =
+					T::Currency::transfer(&Self::account_id(), &author, reward, KeepAlive);
+				debug_assert!(success.is_ok());
+				Self::deposit_event(Event::BlockAuthorRewarded {
+					author: author.clone(),
+					amount: reward,
+				});
+			}
+		} else {
+			Self::deposit_event(Event::RewardWithheldForNewValidator { who: author.clone() });
+		}
+		<SessionBlocksAuthored<T>>::mutate(&author, |blocks| *blocks = blocks.saturating_add(1));
+		<ValidatorPerformance<T>>::mutate(&author, |performance| {
+			performance.blocks_authored = performance.blocks_authored.saturating_add(1)
+		});
 		<LastAuthoredBlock<T>>::insert(author, frame_system::Pallet::<T>::block_number());
 
 		frame_system::Pallet::<T>::register_extra_weight_unchecked(
@@ -457,12 +985,30 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 			<frame_system::Pallet<T>>::block_number(),
 		);
 
-		let candidates = Self::candidates();
-		let candidates_len_before = candidates.len();
-		let active_candidates = Self::kick_stale_candidates(candidates);
+		let candidates_len_before = Self::candidates().len();
+		Self::apply_accumulated_slashes();
+		// validators planned in session `index - 2` have just finished their session.
+		let ended = index.checked_sub(2);
+		let active_candidates = Self::kick_stale_candidates(Self::candidates(), ended);
 		let active_candidates_len = active_candidates.len();
+		if let Some(ended) = ended {
+			Self::note_low_performance(ended, &active_candidates);
+		}
+		let active_candidates = if T::UseWeightedSelection::get() {
+			let (seed, _) = T::Randomness::random(b"validator-set/selection");
+			Self::weighted_select_candidates(active_candidates, Self::desired_candidates(), seed)
+		} else {
+			active_candidates
+		};
+		let _ = <SessionBlocksAuthored<T>>::remove_all(None);
 		let result = Self::assemble_validators(active_candidates);
+		for who in &result {
+			<ValidatorActiveSessions<T>>::mutate(who, |sessions| {
+				*sessions = sessions.saturating_add(1)
+			});
+		}
 		let removed = candidates_len_before - active_candidates_len;
+		Self::snapshot_validator_set(index, &result);
 
 		frame_system::Pallet::<T>::register_extra_weight_unchecked(
 			<T as pallet::Config>::WeightInfo::new_session(
@@ -474,7 +1020,13 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 		Some(result)
 	}
 
-	fn end_session(_end_index: SessionIndex) {}
+	fn end_session(end_index: SessionIndex) {
+		let authors = Self::distribute_session_rewards(end_index);
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			<T as pallet::Config>::WeightInfo::end_session(authors),
+			DispatchClass::Mandatory,
+		);
+	}
 
 	fn start_session(_start_index: SessionIndex) {}
 }
@@ -2,7 +2,7 @@ use super::*;
 use crate as validator_set;
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{FindAuthor, GenesisBuild, ValidatorRegistration},
+	traits::{FindAuthor, GenesisBuild, Randomness, ValidatorRegistration},
 	PalletId,
 };
 use frame_system as system;
@@ -181,9 +181,29 @@ parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
 	pub const MaxCandidates: u32 = 20;
 	pub const MaxInvulnerables: u32 = 20;
+	pub const MinInvulnerables: u32 = 1;
+	pub const MaxWaitingCandidates: u32 = 1;
 	pub const MinCandidates: u32 = 1;
+	pub const MaxSessionSnapshots: u32 = 2;
 	pub const MaxAuthorities: u32 = 100_000;
 	pub const SlashRatio: Perbill = Perbill::from_percent(10);
+	pub const MaxSlashRatio: Perbill = Perbill::from_percent(30);
+	pub const MinBlocksPerSession: u32 = 2;
+	pub const SlashAccumulationThreshold: u32 = 3;
+	pub const PerEpochSlashRate: Perbill = Perbill::from_percent(10);
+	pub static UseWeightedSelection: bool = false;
+	pub static MinSessionsForRewards: u32 = 0;
+	pub static AuthorRewardPercent: Perbill = Perbill::from_percent(100);
+	pub static KickGraceFactor: u32 = 0;
+	pub static RandomSeed: u64 = 0;
+}
+
+/// Deterministic randomness source returning the configured [`RandomSeed`].
+pub struct TestRandomness;
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(_subject: &[u8]) -> (H256, u64) {
+		(H256::from_low_u64_be(RandomSeed::get()), System::block_number())
+	}
 }
 
 pub struct IsRegistered;
@@ -205,9 +225,21 @@ impl Config for TestRuntime {
 	type MaxCandidates = MaxCandidates;
 	type MinCandidates = MinCandidates;
 	type MaxInvulnerables = MaxInvulnerables;
+	type MinInvulnerables = MinInvulnerables;
+	type MaxWaitingCandidates = MaxWaitingCandidates;
 	type KickThreshold = Period;
+	type KickGraceFactor = KickGraceFactor;
 	type ValidatorRegistration = IsRegistered;
 	type SlashRatio = SlashRatio;
+	type MaxSlashRatio = MaxSlashRatio;
+	type MinBlocksPerSession = MinBlocksPerSession;
+	type SlashAccumulationThreshold = SlashAccumulationThreshold;
+	type PerEpochSlashRate = PerEpochSlashRate;
+	type MinSessionsForRewards = MinSessionsForRewards;
+	type AuthorRewardPercent = AuthorRewardPercent;
+	type MaxSessionSnapshots = MaxSessionSnapshots;
+	type Randomness = TestRandomness;
+	type UseWeightedSelection = UseWeightedSelection;
 	type WeightInfo = ();
 }
 
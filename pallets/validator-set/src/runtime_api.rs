@@ -0,0 +1,13 @@
+//! Runtime API definition for validator set pallet.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait ValidatorSetApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Returns the validator set assembled for `session`, if its snapshot is still retained.
+		fn validator_set_at_session(session: u32) -> Option<Vec<AccountId>>;
+	}
+}
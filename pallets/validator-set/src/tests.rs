@@ -1,11 +1,14 @@
 use crate as validator_set;
-use crate::{mock::*, CandidateInfo, Error};
+use crate::{mock::*, CandidateInfo, Error, ValidatorPerfInfo};
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency, GenesisBuild, OnInitialize},
 };
 use pallet_balances::Error as BalancesError;
-use sp_runtime::traits::BadOrigin;
+use pallet_session::SessionManager;
+use sp_core::H256;
+use sp_runtime::{traits::BadOrigin, Perbill};
+use sp_std::collections::btree_set::BTreeSet;
 
 #[test]
 fn basic_setup_works() {
@@ -46,6 +49,71 @@ fn set_invulnerables() {
 	})
 }
 
+#[test]
+fn add_and_remove_invulnerable() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(ValidatorSet::invulnerables(), vec![1, 2]);
+
+		assert_ok!(ValidatorSet::add_invulnerable(Origin::signed(RootAccount::get()), 3));
+		assert_eq!(ValidatorSet::invulnerables(), vec![1, 2, 3]);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::InvulnerableAdded(3)));
+
+		assert_noop!(ValidatorSet::add_invulnerable(Origin::signed(1), 4), BadOrigin);
+		assert_noop!(
+			ValidatorSet::add_invulnerable(Origin::signed(RootAccount::get()), 3),
+			Error::<TestRuntime>::AlreadyInvulnerable
+		);
+		assert_noop!(
+			ValidatorSet::add_invulnerable(Origin::signed(RootAccount::get()), 7),
+			Error::<TestRuntime>::ValidatorNotRegistered
+		);
+
+		assert_ok!(ValidatorSet::remove_invulnerable(Origin::signed(RootAccount::get()), 1));
+		assert_eq!(ValidatorSet::invulnerables(), vec![2, 3]);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::InvulnerableRemoved(1)));
+
+		assert_noop!(ValidatorSet::remove_invulnerable(Origin::signed(2), 2), BadOrigin);
+		assert_noop!(
+			ValidatorSet::remove_invulnerable(Origin::signed(RootAccount::get()), 1),
+			Error::<TestRuntime>::Unknown
+		);
+
+		// The last invulnerable can't be removed
+		assert_ok!(ValidatorSet::remove_invulnerable(Origin::signed(RootAccount::get()), 2));
+		assert_noop!(
+			ValidatorSet::remove_invulnerable(Origin::signed(RootAccount::get()), 3),
+			Error::<TestRuntime>::TooFewInvulnerables
+		);
+
+		// Nor can invulnerables be added beyond the maximum
+		let full: Vec<_> = (10..10 + MaxInvulnerables::get() as u64).collect();
+		assert_ok!(ValidatorSet::set_invulnerables(Origin::signed(RootAccount::get()), full));
+		assert_noop!(
+			ValidatorSet::add_invulnerable(Origin::signed(RootAccount::get()), 3),
+			Error::<TestRuntime>::TooManyInvulnerables
+		);
+	})
+}
+
+#[test]
+fn set_and_clear_slash_destination() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_eq!(ValidatorSet::slash_destination(), None);
+
+		assert_noop!(ValidatorSet::set_slash_destination(Origin::signed(1), 5), BadOrigin);
+		assert_ok!(ValidatorSet::set_slash_destination(Origin::signed(RootAccount::get()), 5));
+		assert_eq!(ValidatorSet::slash_destination(), Some(5));
+		System::assert_last_event(Event::ValidatorSet(crate::Event::SlashDestinationSet {
+			dest: 5,
+		}));
+
+		assert_noop!(ValidatorSet::clear_slash_destination(Origin::signed(1)), BadOrigin);
+		assert_ok!(ValidatorSet::clear_slash_destination(Origin::signed(RootAccount::get())));
+		assert_eq!(ValidatorSet::slash_destination(), None);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::SlashDestinationCleared));
+	})
+}
+
 #[test]
 fn set_desired_candidates() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -71,25 +139,118 @@ fn set_candidacy_bond() {
 }
 
 #[test]
-fn cannot_register_candidate_if_too_many() {
+fn register_candidate_joins_waiting_list_if_too_many() {
 	ExternalityBuilder::build().execute_with(|| {
 		<crate::DesiredCandidates<TestRuntime>>::put(0);
 
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_eq!(ValidatorSet::candidates(), vec![]);
+		assert_eq!(ValidatorSet::waiting_list(), vec![CandidateInfo { who: 3, deposit: 10 }]);
+		assert_eq!(Balances::free_balance(3), 90);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::CandidateWaiting(3, 10)));
+
 		assert_noop!(
 			ValidatorSet::register_as_candidate(Origin::signed(3)),
-			Error::<TestRuntime>::TooManyCandidates,
+			Error::<TestRuntime>::AlreadyCandidate,
+		);
+		assert_noop!(
+			ValidatorSet::register_as_candidate(Origin::signed(5)),
+			Error::<TestRuntime>::WaitingListFull,
 		);
 
 		<crate::DesiredCandidates<TestRuntime>>::put(1);
 		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		assert_noop!(
+			ValidatorSet::register_as_candidate(Origin::signed(4)),
+			Error::<TestRuntime>::AlreadyCandidate,
+		);
+	})
+}
+
+#[test]
+fn register_candidate_respects_hard_cap() {
+	ExternalityBuilder::build().execute_with(|| {
+		// desired candidates above the hard cap
+		<crate::DesiredCandidates<TestRuntime>>::put(MaxCandidates::get() + 5);
+		let full: Vec<_> = (100..100 + MaxCandidates::get() as u64)
+			.map(|who| CandidateInfo { who, deposit: 10 })
+			.collect();
+		<crate::Candidates<TestRuntime>>::put(full);
 
 		assert_noop!(
-			ValidatorSet::register_as_candidate(Origin::signed(5)),
-			Error::<TestRuntime>::TooManyCandidates,
+			ValidatorSet::register_as_candidate(Origin::signed(3)),
+			Error::<TestRuntime>::HardCapReached,
 		);
 	})
 }
 
+#[test]
+fn set_invulnerables_respects_hard_cap() {
+	ExternalityBuilder::build().execute_with(|| {
+		let full: Vec<_> = (100..100 + MaxCandidates::get() as u64)
+			.map(|who| CandidateInfo { who, deposit: 10 })
+			.collect();
+		<crate::Candidates<TestRuntime>>::put(full);
+
+		let max: Vec<_> = (10..10 + MaxInvulnerables::get() as u64).collect();
+		assert_ok!(ValidatorSet::set_invulnerables(Origin::signed(RootAccount::get()), max));
+
+		let over: Vec<_> = (10..11 + MaxInvulnerables::get() as u64).collect();
+		assert_noop!(
+			ValidatorSet::set_invulnerables(Origin::signed(RootAccount::get()), over.clone()),
+			Error::<TestRuntime>::HardCapReached,
+		);
+
+		// Nor can invulnerables exceed their own maximum with free candidate slots
+		<crate::Candidates<TestRuntime>>::kill();
+		assert_noop!(
+			ValidatorSet::set_invulnerables(Origin::signed(RootAccount::get()), over),
+			Error::<TestRuntime>::TooManyInvulnerables,
+		);
+	})
+}
+
+#[test]
+fn waiting_candidate_is_promoted_when_slot_frees_up() {
+	ExternalityBuilder::build().execute_with(|| {
+		<crate::DesiredCandidates<TestRuntime>>::put(2);
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(5)));
+		assert_eq!(ValidatorSet::waiting_list(), vec![CandidateInfo { who: 5, deposit: 10 }]);
+
+		assert_ok!(ValidatorSet::leave_intent(Origin::signed(3)));
+		assert_eq!(
+			ValidatorSet::candidates(),
+			vec![CandidateInfo { who: 4, deposit: 10 }, CandidateInfo { who: 5, deposit: 10 }]
+		);
+		assert_eq!(ValidatorSet::waiting_list(), vec![]);
+		assert_eq!(ValidatorSet::last_authored_block(5), 10);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Balances::free_balance(5), 90);
+		System::assert_has_event(Event::ValidatorSet(crate::Event::CandidateAdded(5, 10)));
+	})
+}
+
+#[test]
+fn leave_waiting_list_works() {
+	ExternalityBuilder::build().execute_with(|| {
+		<crate::DesiredCandidates<TestRuntime>>::put(0);
+		assert_noop!(
+			ValidatorSet::leave_waiting_list(Origin::signed(3)),
+			Error::<TestRuntime>::NotWaitingCandidate,
+		);
+
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_eq!(Balances::free_balance(3), 90);
+
+		assert_ok!(ValidatorSet::leave_waiting_list(Origin::signed(3)));
+		assert_eq!(ValidatorSet::waiting_list(), vec![]);
+		assert_eq!(Balances::free_balance(3), 100);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::CandidateLeftWaitingList(3)));
+	})
+}
+
 #[test]
 fn cannot_unregister_candidate_if_too_few() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -204,6 +365,64 @@ fn authorship_event_handler() {
 	});
 }
 
+#[test]
+fn rewards_withheld_for_new_validators() {
+	ExternalityBuilder::build().execute_with(|| {
+		MinSessionsForRewards::set(2);
+		Balances::make_free_balance_be(&ValidatorSet::account_id(), 105);
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+
+		initialize_to_block(1);
+		System::assert_has_event(Event::ValidatorSet(
+			crate::Event::RewardWithheldForNewValidator { who: 4 },
+		));
+		assert_eq!(Balances::free_balance(4), 90);
+
+		initialize_to_block(19);
+		assert_eq!(ValidatorSet::validator_active_sessions(3), 1);
+		assert_eq!(ValidatorSet::validator_active_sessions(4), 1);
+		assert_eq!(Balances::free_balance(4), 90);
+		assert_eq!(Balances::free_balance(ValidatorSet::account_id()), 105);
+
+		initialize_to_block(20);
+		// 3 was kicked and has to qualify again.
+		assert_eq!(ValidatorSet::validator_active_sessions(3), 0);
+		assert_eq!(ValidatorSet::validator_active_sessions(4), 2);
+		assert_eq!(Balances::free_balance(4), 190);
+		assert_eq!(Balances::free_balance(ValidatorSet::account_id()), 5);
+	});
+}
+
+#[test]
+fn author_reward_leaves_rest_for_session_end() {
+	ExternalityBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		AuthorRewardPercent::set(Perbill::from_percent(50));
+		Balances::make_free_balance_be(&ValidatorSet::account_id(), 105);
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+
+		Authorship::on_initialize(1);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::BlockAuthorRewarded {
+			author: 4,
+			amount: 50,
+		}));
+		assert_eq!(Balances::free_balance(4), 140);
+		assert_eq!(Balances::free_balance(ValidatorSet::account_id()), 55);
+
+		// the rest is split between the authors of the session.
+		<crate::SessionBlocksAuthored<TestRuntime>>::insert(3, 1);
+		<ValidatorSet as SessionManager<_>>::end_session(0);
+		System::assert_last_event(Event::ValidatorSet(crate::Event::SessionRewardsPaid {
+			session_index: 0,
+			per_validator: 25,
+		}));
+		assert_eq!(Balances::free_balance(3), 125);
+		assert_eq!(Balances::free_balance(4), 165);
+		assert_eq!(Balances::free_balance(ValidatorSet::account_id()), 5);
+	});
+}
+
 #[test]
 fn session_management_works() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -241,7 +460,7 @@ fn session_management_works() {
 #[test]
 fn kick_and_slash_mechanism() {
 	ExternalityBuilder::build().execute_with(|| {
-		<crate::SlashDestination<TestRuntime>>::put(5);
+		assert_ok!(ValidatorSet::set_slash_destination(Origin::signed(RootAccount::get()), 5));
 
 		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
 		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
@@ -267,6 +486,125 @@ fn kick_and_slash_mechanism() {
 	});
 }
 
+#[test]
+fn kick_spares_candidates_left_out_by_weighted_selection() {
+	ExternalityBuilder::build().execute_with(|| {
+		UseWeightedSelection::set(true);
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		// only 4 was selected for the ended session, neither of them authored a block.
+		<crate::ValidatorSetBySession<TestRuntime>>::insert(0, vec![1, 2, 4].try_into().unwrap());
+		System::set_block_number(30);
+
+		assert_eq!(
+			ValidatorSet::kick_stale_candidates(ValidatorSet::candidates(), Some(0)),
+			vec![3]
+		);
+		assert_eq!(ValidatorSet::candidates(), vec![CandidateInfo { who: 3, deposit: 10 }]);
+	});
+}
+
+#[test]
+fn kick_threshold_extends_with_track_record() {
+	KickGraceFactor::set(5);
+	for (consecutive_missed_sessions, kicked_at) in [(0, 40), (1, 20)] {
+		ExternalityBuilder::build().execute_with(|| {
+			assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+			assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+			<crate::ValidatorPerformance<TestRuntime>>::insert(
+				3,
+				ValidatorPerfInfo { blocks_authored: 10, consecutive_missed_sessions },
+			);
+			let expected_threshold = if consecutive_missed_sessions == 0 { 30 } else { 10 };
+			assert_eq!(ValidatorSet::effective_kick_threshold(&3), expected_threshold);
+
+			initialize_to_block(kicked_at - 10);
+			assert_eq!(ValidatorSet::candidates().len(), 2);
+			initialize_to_block(kicked_at);
+			assert_eq!(ValidatorSet::candidates(), vec![CandidateInfo { who: 4, deposit: 10 }]);
+			assert_eq!(ValidatorSet::validator_performance(3), ValidatorPerfInfo::default());
+		});
+	}
+}
+
+#[test]
+fn session_end_updates_validator_performance() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		initialize_to_block(30);
+		// 4 has been in the validator set since session 2 and authors every block. Session 2
+		// ends before 4 authors block 30.
+		System::assert_has_event(Event::ValidatorSet(crate::Event::ValidatorPerformanceUpdated {
+			who: 4,
+			blocks_authored: 29,
+		}));
+		assert_eq!(
+			ValidatorSet::validator_performance(4),
+			ValidatorPerfInfo { blocks_authored: 30, consecutive_missed_sessions: 0 }
+		);
+	});
+}
+
+#[test]
+fn repeated_slashes_are_escalated() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(ValidatorSet::set_slash_destination(Origin::signed(RootAccount::get()), 5));
+		// 3 has already been slashed once
+		<crate::SlashHistory<TestRuntime>>::insert(3, 1);
+
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		initialize_to_block(20);
+
+		// slashed twice 10% (of 10 bond)
+		assert_eq!(Balances::free_balance(3), 98);
+		assert_eq!(Balances::free_balance(5), 102);
+		assert_eq!(ValidatorSet::slash_history(3), 2);
+		System::assert_has_event(Event::ValidatorSet(crate::Event::CandidateSlashed {
+			who: 3,
+			slash_amount: 2,
+			slash_count: 2,
+		}));
+
+		// escalation is capped by `MaxSlashRatio`
+		let base_ratio = Perbill::from_percent(10);
+		assert_eq!(ValidatorSet::escalated_slash_ratio(base_ratio, 1), Perbill::from_percent(20));
+		assert_eq!(ValidatorSet::escalated_slash_ratio(base_ratio, 5), MaxSlashRatio::get());
+	});
+}
+
+#[test]
+fn accumulated_low_performance_is_slashed() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		initialize_to_block(10);
+
+		// 3 authors a single block per session, enough to not be kicked.
+		for (block, epochs) in [(20, 0), (30, 1), (40, 2), (50, 3)] {
+			<crate::LastAuthoredBlock<TestRuntime>>::insert(3, block - 1);
+			<crate::SessionBlocksAuthored<TestRuntime>>::insert(3, 1);
+			initialize_to_block(block);
+			assert_eq!(ValidatorSet::low_performance_epochs(3), epochs);
+			assert_eq!(ValidatorSet::low_performance_epochs(4), 0);
+			assert_eq!(ValidatorSet::candidates().len(), 2);
+		}
+
+		<crate::LastAuthoredBlock<TestRuntime>>::insert(3, 59);
+		initialize_to_block(60);
+		let validator = CandidateInfo { who: 4, deposit: 10 };
+		assert_eq!(ValidatorSet::candidates(), vec![validator]);
+		assert_eq!(ValidatorSet::low_performance_epochs(3), 0);
+		// slashed 3 epochs of 10% (of 10 bond)
+		assert_eq!(Balances::free_balance(3), 97);
+		System::assert_has_event(Event::ValidatorSet(crate::Event::AccumulatedSlashApplied {
+			who: 3,
+			epochs: 3,
+			slash_amount: 3,
+		}));
+	});
+}
+
 #[test]
 #[should_panic = "duplicate invulnerables in genesis."]
 fn cannot_set_genesis_value_twice() {
@@ -281,3 +619,147 @@ fn cannot_set_genesis_value_twice() {
 	};
 	validator_set.assimilate_storage(&mut t).unwrap();
 }
+
+#[test]
+fn validator_set_snapshot_taken_on_new_session() {
+	ExternalityBuilder::build().execute_with(|| {
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+
+		initialize_to_block(10);
+		// validators are assembled for the session after the current one
+		let session = Session::current_index() + 1;
+		assert_eq!(
+			ValidatorSet::validator_set_at_session(session),
+			Some(ValidatorSet::assemble_validators(vec![3]))
+		);
+		assert_eq!(ValidatorSet::validator_set_at_session(session), Some(vec![1, 2, 3]));
+		assert_eq!(ValidatorSet::validator_set_at_session(session + 1), None);
+	});
+}
+
+#[test]
+fn validator_set_snapshots_are_capped() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_to_block(10);
+		let first_session = Session::current_index() + 1;
+		assert!(ValidatorSet::validator_set_at_session(first_session).is_some());
+
+		initialize_to_block(30);
+		let last_session = Session::current_index() + 1;
+		assert_eq!(last_session, first_session + 2);
+
+		assert_eq!(
+			<crate::ValidatorSetBySession<TestRuntime>>::iter().count() as u32,
+			MaxSessionSnapshots::get()
+		);
+		assert_eq!(ValidatorSet::validator_set_at_session(first_session), None);
+		assert!(ValidatorSet::validator_set_at_session(last_session - 1).is_some());
+		assert!(ValidatorSet::validator_set_at_session(last_session).is_some());
+	});
+}
+
+#[test]
+fn validator_set_snapshots_include_invulnerables() {
+	ExternalityBuilder::build().execute_with(|| {
+		let invulnerables: Vec<_> = (10..10 + MaxInvulnerables::get() as u64).collect();
+		assert_ok!(ValidatorSet::set_invulnerables(
+			Origin::signed(RootAccount::get()),
+			invulnerables.clone()
+		));
+		let candidates: Vec<_> = (100..100 + MaxCandidates::get() as u64).collect();
+		for who in candidates.iter() {
+			<crate::LastAuthoredBlock<TestRuntime>>::insert(who, 10);
+		}
+		<crate::Candidates<TestRuntime>>::put(
+			candidates
+				.iter()
+				.map(|&who| CandidateInfo { who, deposit: 10 })
+				.collect::<Vec<_>>(),
+		);
+
+		// The full validator set is retained, beyond `MaxCandidates`
+		initialize_to_block(10);
+		let validators = ValidatorSet::validator_set_at_session(Session::current_index() + 1);
+		assert_eq!(validators, Some([invulnerables, candidates].concat()));
+	});
+}
+
+#[test]
+fn prune_validator_set_snapshots() {
+	ExternalityBuilder::build().execute_with(|| {
+		initialize_to_block(20);
+		let last_session = Session::current_index() + 1;
+
+		assert_noop!(
+			ValidatorSet::prune_validator_set_snapshots(Origin::signed(1), last_session),
+			BadOrigin
+		);
+
+		assert_ok!(ValidatorSet::prune_validator_set_snapshots(
+			Origin::signed(RootAccount::get()),
+			last_session
+		));
+		assert_eq!(ValidatorSet::validator_set_at_session(last_session - 1), None);
+		assert_eq!(ValidatorSet::validator_set_at_session(last_session), Some(vec![1, 2]));
+	});
+}
+
+#[test]
+fn weighted_selection_favours_active_authors() {
+	ExternalityBuilder::build().execute_with(|| {
+		let candidates = (11..=20).collect::<Vec<u64>>();
+		for who in 11..=15 {
+			<crate::SessionBlocksAuthored<TestRuntime>>::insert(who, 9);
+		}
+
+		let mut appearances = [0u32; 10];
+		for seed in 0..1000u64 {
+			let selected = ValidatorSet::weighted_select_candidates(
+				candidates.clone(),
+				5,
+				H256::from_low_u64_be(seed),
+			);
+			assert_eq!(selected.len(), 5);
+			assert_eq!(selected.iter().collect::<BTreeSet<_>>().len(), 5);
+			for who in selected {
+				appearances[(who - 11) as usize] += 1;
+			}
+		}
+
+		let (high, low) = appearances.split_at(5);
+		let min_high = high.iter().min().unwrap();
+		let max_low = low.iter().max().unwrap();
+		assert!(min_high > max_low, "{:?}", appearances);
+		assert!(high.iter().sum::<u32>() > 2 * low.iter().sum::<u32>());
+	});
+}
+
+#[test]
+fn weighted_selection_keeps_all_candidates_below_limit() {
+	ExternalityBuilder::build().execute_with(|| {
+		let selected =
+			ValidatorSet::weighted_select_candidates(vec![3, 4], 2, H256::from_low_u64_be(1));
+		assert_eq!(selected, vec![3, 4]);
+	});
+}
+
+#[test]
+fn new_session_uses_weighted_selection() {
+	ExternalityBuilder::build().execute_with(|| {
+		UseWeightedSelection::set(true);
+		initialize_to_block(1);
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(3)));
+		assert_ok!(ValidatorSet::register_as_candidate(Origin::signed(4)));
+		assert_ok!(ValidatorSet::set_desired_candidates(Origin::signed(RootAccount::get()), 1));
+		assert!(ValidatorSet::session_blocks_authored(4) > 0);
+
+		initialize_to_block(10);
+		let session = Session::current_index() + 1;
+		let validators = ValidatorSet::validator_set_at_session(session).unwrap();
+		assert_eq!(validators.len(), 3);
+		assert_eq!(&validators[..2], &[1, 2]);
+		// both candidates remain registered, only one of them is selected for the session.
+		assert_eq!(ValidatorSet::candidates().len(), 2);
+		assert_eq!(ValidatorSet::session_blocks_authored(4), 0);
+	});
+}
@@ -41,17 +41,31 @@ pub trait WeightInfo {
 	#[rustfmt::skip]
 	fn set_invulnerables(b: u32, ) -> Weight;
 	#[rustfmt::skip]
+	fn add_invulnerable(b: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn remove_invulnerable(b: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn set_desired_candidates() -> Weight;
 	#[rustfmt::skip]
 	fn set_candidacy_bond() -> Weight;
 	#[rustfmt::skip]
+	fn set_slash_destination() -> Weight;
+	#[rustfmt::skip]
+	fn clear_slash_destination() -> Weight;
+	#[rustfmt::skip]
 	fn register_as_candidate(c: u32, ) -> Weight;
 	#[rustfmt::skip]
 	fn leave_intent(c: u32, ) -> Weight;
 	#[rustfmt::skip]
+	fn leave_waiting_list(w: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn note_author() -> Weight;
 	#[rustfmt::skip]
+	fn end_session(a: u32, ) -> Weight;
+	#[rustfmt::skip]
 	fn new_session(r: u32, c: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn prune_validator_set_snapshots(s: u32, ) -> Weight;
 }
 
 /// Weights for pallet_validator_set using the Substrate node and recommended hardware.
@@ -67,6 +81,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Session NextKeys (r:1 w:0)
+	// Storage: ValidatorSet Invulnerables (r:1 w:1)
+	#[rustfmt::skip]
+	fn add_invulnerable(b: u32, ) -> Weight {
+		(14_519_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((98_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Invulnerables (r:1 w:1)
+	#[rustfmt::skip]
+	fn remove_invulnerable(b: u32, ) -> Weight {
+		(10_874_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((86_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: ValidatorSet DesiredCandidates (r:0 w:1)
 	#[rustfmt::skip]
 	fn set_desired_candidates() -> Weight {
@@ -79,6 +112,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		(8_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: ValidatorSet SlashDestination (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_slash_destination() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet SlashDestination (r:0 w:1)
+	#[rustfmt::skip]
+	fn clear_slash_destination() -> Weight {
+		(7_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: ValidatorSet Candidates (r:1 w:1)
 	// Storage: ValidatorSet DesiredCandidates (r:1 w:0)
 	// Storage: ValidatorSet Invulnerables (r:1 w:0)
@@ -103,14 +148,40 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: ValidatorSet WaitingList (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn leave_waiting_list(w: u32, ) -> Weight {
+		(24_310_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((58_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 	// Storage: System Account (r:2 w:2)
 	// Storage: System BlockWeight (r:1 w:1)
+	// Storage: ValidatorSet ValidatorActiveSessions (r:1 w:0)
+	// Storage: ValidatorSet SessionBlocksAuthored (r:1 w:1)
+	// Storage: ValidatorSet ValidatorPerformance (r:1 w:1)
 	// Storage: ValidatorSet LastAuthoredBlock (r:0 w:1)
 	#[rustfmt::skip]
 	fn note_author() -> Weight {
-		(30_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: ValidatorSet SessionBlocksAuthored (r:201 w:0)
+	// Storage: ValidatorSet ValidatorActiveSessions (r:200 w:0)
+	// Storage: System Account (r:201 w:201)
+	#[rustfmt::skip]
+	fn end_session(a: u32, ) -> Weight {
+		(6_208_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((24_731_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
 	}
 	// Storage: ValidatorSet Candidates (r:1 w:1)
 	// Storage: ValidatorSet LastAuthoredBlock (r:200 w:1)
@@ -119,16 +190,33 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Balances TotalIssuance (r:1 w:1)
 	// Storage: ValidatorSet Invulnerables (r:1 w:0)
 	// Storage: System BlockWeight (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ValidatorSet SessionBlocksAuthored (r:200 w:200)
+	// Storage: ValidatorSet LowPerformanceEpochs (r:200 w:200)
+	// Storage: ValidatorSet ValidatorPerformance (r:200 w:200)
+	// Storage: ValidatorSet ValidatorActiveSessions (r:220 w:220)
+	// Storage: ValidatorSet ValidatorSetBySession (r:1 w:2)
 	#[rustfmt::skip]
 	fn new_session(r: u32, c: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 1_796_000
 			.saturating_add((7_415_000 as Weight).saturating_mul(r as Weight))
 			// Standard Error: 1_796_000
-			.saturating_add((25_726_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add((34_268_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ValidatorSet ValidatorSetBySession (r:1 w:1)
+	#[rustfmt::skip]
+	fn prune_validator_set_snapshots(s: u32, ) -> Weight {
+		(5_712_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((2_519_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
 	}
 }
 
@@ -144,6 +232,25 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(b as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Session NextKeys (r:1 w:0)
+	// Storage: ValidatorSet Invulnerables (r:1 w:1)
+	#[rustfmt::skip]
+	fn add_invulnerable(b: u32, ) -> Weight {
+		(14_519_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((98_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Invulnerables (r:1 w:1)
+	#[rustfmt::skip]
+	fn remove_invulnerable(b: u32, ) -> Weight {
+		(10_874_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((86_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	// Storage: ValidatorSet DesiredCandidates (r:0 w:1)
 	#[rustfmt::skip]
 	fn set_desired_candidates() -> Weight {
@@ -156,6 +263,18 @@ impl WeightInfo for () {
 		(8_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: ValidatorSet SlashDestination (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_slash_destination() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet SlashDestination (r:0 w:1)
+	#[rustfmt::skip]
+	fn clear_slash_destination() -> Weight {
+		(7_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	// Storage: ValidatorSet Candidates (r:1 w:1)
 	// Storage: ValidatorSet DesiredCandidates (r:1 w:0)
 	// Storage: ValidatorSet Invulnerables (r:1 w:0)
@@ -180,14 +299,40 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Storage: ValidatorSet WaitingList (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn leave_waiting_list(w: u32, ) -> Weight {
+		(24_310_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((58_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 	// Storage: System Account (r:2 w:2)
 	// Storage: System BlockWeight (r:1 w:1)
+	// Storage: ValidatorSet ValidatorActiveSessions (r:1 w:0)
+	// Storage: ValidatorSet SessionBlocksAuthored (r:1 w:1)
+	// Storage: ValidatorSet ValidatorPerformance (r:1 w:1)
 	// Storage: ValidatorSet LastAuthoredBlock (r:0 w:1)
 	#[rustfmt::skip]
 	fn note_author() -> Weight {
-		(30_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: ValidatorSet SessionBlocksAuthored (r:201 w:0)
+	// Storage: ValidatorSet ValidatorActiveSessions (r:200 w:0)
+	// Storage: System Account (r:201 w:201)
+	#[rustfmt::skip]
+	fn end_session(a: u32, ) -> Weight {
+		(6_208_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((24_731_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
 	}
 	// Storage: ValidatorSet Candidates (r:1 w:1)
 	// Storage: ValidatorSet LastAuthoredBlock (r:200 w:1)
@@ -196,15 +341,32 @@ impl WeightInfo for () {
 	// Storage: Balances TotalIssuance (r:1 w:1)
 	// Storage: ValidatorSet Invulnerables (r:1 w:0)
 	// Storage: System BlockWeight (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: ValidatorSet SessionBlocksAuthored (r:200 w:200)
+	// Storage: ValidatorSet LowPerformanceEpochs (r:200 w:200)
+	// Storage: ValidatorSet ValidatorPerformance (r:200 w:200)
+	// Storage: ValidatorSet ValidatorActiveSessions (r:220 w:220)
+	// Storage: ValidatorSet ValidatorSetBySession (r:1 w:2)
 	#[rustfmt::skip]
 	fn new_session(r: u32, c: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 1_796_000
 			.saturating_add((7_415_000 as Weight).saturating_mul(r as Weight))
 			// Standard Error: 1_796_000
-			.saturating_add((25_726_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add((34_268_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ValidatorSet ValidatorSetBySession (r:1 w:1)
+	#[rustfmt::skip]
+	fn prune_validator_set_snapshots(s: u32, ) -> Weight {
+		(5_712_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((2_519_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
 	}
 }
\ No newline at end of file
@@ -5,7 +5,9 @@
 use codec::Encode;
 use frame_support::{
 	construct_runtime, log, parameter_types,
-	traits::{Contains, Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced},
+	traits::{
+		Contains, Currency, EitherOfDiverse, Get, Imbalance, KeyOwnerProofSystem, OnUnbalanced,
+	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		ConstantMultiplier, DispatchClass, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
@@ -31,7 +33,7 @@ use sp_runtime::{
 		NumberFor, OpaqueKeys, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, FixedPointNumber, MultiSignature, Perbill, Perquintill,
+	ApplyExtrinsicResult, FixedPointNumber, MultiSignature, Perbill, Permill, Perquintill,
 };
 use sp_std::prelude::*;
 
@@ -78,10 +80,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("massbit-local"),
 	impl_name: create_runtime_str!("massbit-local"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 2,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -426,20 +428,69 @@ parameter_types! {
 	pub const MaxCandidates: u32 = 200;
 	pub const MinCandidates: u32 = 1;
 	pub const MaxInvulnerables: u32 = 20;
+	pub const MinInvulnerables: u32 = 1;
+	pub const MaxWaitingCandidates: u32 = 50;
 	pub const SlashRatio: Perbill = Perbill::from_percent(1);
+	pub const MaxSlashRatio: Perbill = Perbill::from_percent(10);
+	pub const MinBlocksPerSession: u32 = 1;
+	pub const SlashAccumulationThreshold: u32 = 3;
+	pub const PerEpochSlashRate: Perbill = Perbill::from_percent(1);
+	pub const MinSessionsForRewards: u32 = 2;
+	pub const KickGraceFactor: u32 = 1_000;
+	pub const AuthorRewardPercent: Perbill = Perbill::from_percent(50);
+	pub const MaxSessionSnapshots: u32 = 64;
+	pub const UseWeightedSelection: bool = true;
 }
 
+impl pallet_randomness_collective_flip::Config for Runtime {}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+pub type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root or more than half of the council.
+pub type EnsureRootOrHalfCouncil = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+>;
+
 impl pallet_validator_set::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
-	type UpdateOrigin = EnsureRoot<AccountId>;
+	type UpdateOrigin = EnsureRootOrHalfCouncil;
 	type PalletId = ValidatorPot;
 	type MaxCandidates = MaxCandidates;
 	type MinCandidates = MinCandidates;
 	type MaxInvulnerables = MaxInvulnerables;
+	type MinInvulnerables = MinInvulnerables;
+	type MaxWaitingCandidates = MaxWaitingCandidates;
 	type KickThreshold = SessionPeriod;
+	type KickGraceFactor = KickGraceFactor;
 	type ValidatorRegistration = Session;
 	type SlashRatio = SlashRatio;
+	type MaxSlashRatio = MaxSlashRatio;
+	type MinBlocksPerSession = MinBlocksPerSession;
+	type SlashAccumulationThreshold = SlashAccumulationThreshold;
+	type PerEpochSlashRate = PerEpochSlashRate;
+	type MinSessionsForRewards = MinSessionsForRewards;
+	type AuthorRewardPercent = AuthorRewardPercent;
+	type MaxSessionSnapshots = MaxSessionSnapshots;
+	type Randomness = RandomnessCollectiveFlip;
+	type UseWeightedSelection = UseWeightedSelection;
 	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
 }
 
@@ -453,8 +504,35 @@ impl OnUnbalanced<NegativeImbalance> for ToValidatorPot {
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 100 * MBTL;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	pub const Burn: Permill = Permill::zero();
+	pub const MaxApprovals: u32 = 100;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type MaxApprovals = MaxApprovals;
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+}
+
 pub struct BeneficiaryPayout();
-impl pallet_block_reward::BeneficiaryPayout<NegativeImbalance> for BeneficiaryPayout {
+impl pallet_block_reward::BeneficiaryPayout<NegativeImbalance, AccountId> for BeneficiaryPayout {
 	fn validators(reward: NegativeImbalance) {
 		ToValidatorPot::on_unbalanced(reward);
 	}
@@ -462,16 +540,69 @@ impl pallet_block_reward::BeneficiaryPayout<NegativeImbalance> for BeneficiaryPa
 	fn providers(reward: NegativeImbalance) {
 		DapiStaking::handle_imbalance(reward)
 	}
+
+	fn community(reward: NegativeImbalance, beneficiaries: &[(AccountId, Perbill)]) {
+		let total = reward.peek();
+		let mut remainder = reward;
+		for (account, share) in beneficiaries {
+			let (portion, rest) = remainder.split(*share * total);
+			Balances::resolve_creating(account, portion);
+			remainder = rest;
+		}
+		// Rounding leftovers go to validators
+		ToValidatorPot::on_unbalanced(remainder);
+	}
+
+	fn treasury(reward: NegativeImbalance) {
+		Treasury::on_unbalanced(reward);
+	}
 }
 
 parameter_types! {
 	pub const RewardAmount: Balance = 1_000 * MILLIMBTL;
+	pub const MaxCommunityBeneficiaries: u32 = 16;
+	pub const MaxConfigHistory: u32 = 32;
+	pub const MinActiveProviders: u32 = 1;
+	pub const TargetStakeRatio: Perbill = Perbill::from_percent(50);
+	pub const MaxRewardMultiplier: u32 = 2;
+}
+
+pub struct ActiveProviderCount;
+impl Get<u32> for ActiveProviderCount {
+	fn get() -> u32 {
+		DapiStaking::active_provider_count()
+	}
+}
+
+pub struct TotalStaked;
+impl Get<Balance> for TotalStaked {
+	fn get() -> Balance {
+		DapiStaking::era_state(DapiStaking::era().current)
+			.map(|era_state| era_state.staked)
+			.unwrap_or_default()
+	}
+}
+
+pub struct DapiChainProviderInfo;
+impl pallet_block_reward::ChainProviderInfo for DapiChainProviderInfo {
+	fn most_served_chain() -> Option<Vec<u8>> {
+		Dapi::most_served_chain()
+	}
 }
 
 impl pallet_block_reward::Config for Runtime {
 	type Currency = Balances;
 	type BeneficiaryPayout = BeneficiaryPayout;
 	type RewardAmount = RewardAmount;
+	type MaxBeneficiaries = MaxCommunityBeneficiaries;
+	type MaxConfigHistory = MaxConfigHistory;
+	type MinActiveProviders = MinActiveProviders;
+	type ProviderCountOracle = ActiveProviderCount;
+	type ProviderChainInfoOracle = DapiChainProviderInfo;
+	type StakingInfo = TotalStaked;
+	type TargetStakeRatio = TargetStakeRatio;
+	type MaxRewardMultiplier = MaxRewardMultiplier;
+	type MaxChainIdLength = MaxBytesInChainId;
 	type Event = Event;
 	type WeightInfo = pallet_block_reward::weights::SubstrateWeight<Runtime>;
 }
@@ -479,15 +610,37 @@ impl pallet_block_reward::Config for Runtime {
 parameter_types! {
 	pub const DapiStakingPot: PalletId = PalletId(*b"dapistak");
 	pub const RegisterDeposit: Balance = 90 * MBTL;
+	pub const MaxProviderBond: Balance = 100_000 * MBTL;
 	pub const ProviderRewardsPercentage: Perbill = Perbill::from_percent(80);
 	pub const MaxNumberOfStakersPerProvider: u32 = 10;
 	pub const MinimumStakingAmount: Balance = 10 * MBTL;
+	pub const MaxDelegationsPerDelegator: u32 = 32;
 	pub const MaxUnlockingChunks: u32 = 2;
+	pub const MaxBatchRegistrations: u32 = 16;
+	pub const MaxProvidersPerEraClean: u32 = 64;
+	pub const MaxProvidersExpiredPerBlock: u32 = 64;
+	pub const MaxEraHistoryQuery: u32 = 100;
+	pub const MaxInactiveEras: u32 = 28;
+	pub const MaxClaimableEraAge: u32 = 60;
+	pub const MaxClaimHistory: u32 = 20;
+	pub const ProviderTransferPeriod: BlockNumber = 3 * DAYS;
+	pub const CommissionChangeDelay: u32 = 14;
+	pub const DelegatorRewardVestingPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxRewardVestingSchedules: u32 = 28;
+	pub const ScoreRewardMultiplier: Perbill = Perbill::from_percent(50);
+	pub const StakeEffectDelay: u32 = 1;
 	pub const UnbondingPeriod: u32 = 2;
-	pub const MaxEraStakeValues: u32 = 5;
+	pub const DefaultMaxEraStakeValues: u32 = 5;
 	pub const DefaultBlocksPerEra: u32 = 7200;
 }
 
+pub struct DapiProviderScore;
+impl pallet_dapi_staking::traits::ProviderScoreInfo<MassbitId> for DapiProviderScore {
+	fn provider_score(provider_id: &MassbitId) -> Option<u32> {
+		Dapi::provider_score(provider_id)
+	}
+}
+
 impl pallet_dapi_staking::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -495,12 +648,31 @@ impl pallet_dapi_staking::Config for Runtime {
 	type ProviderId = MassbitId;
 	type ProviderRewardsPercentage = ProviderRewardsPercentage;
 	type MinProviderStake = RegisterDeposit;
+	type MaxProviderBond = MaxProviderBond;
 	type MaxDelegatorsPerProvider = MaxNumberOfStakersPerProvider;
 	type MinDelegatorStake = MinimumStakingAmount;
-	type MaxEraStakeValues = MaxEraStakeValues;
+	type MaxDelegationsPerDelegator = MaxDelegationsPerDelegator;
+	type DefaultMaxEraStakeValues = DefaultMaxEraStakeValues;
+	type StakeEffectDelay = StakeEffectDelay;
 	type UnbondingPeriod = UnbondingPeriod;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxBatchRegistrations = MaxBatchRegistrations;
+	type MaxProvidersPerEraClean = MaxProvidersPerEraClean;
+	type MaxProvidersExpiredPerBlock = MaxProvidersExpiredPerBlock;
+	type MaxEraHistoryQuery = MaxEraHistoryQuery;
+	type MaxInactiveEras = MaxInactiveEras;
+	type MaxClaimableEraAge = MaxClaimableEraAge;
+	type MaxClaimHistory = MaxClaimHistory;
+	type ProviderTransferPeriod = ProviderTransferPeriod;
+	type CommissionChangeDelay = CommissionChangeDelay;
+	type VestingPeriod = DelegatorRewardVestingPeriod;
+	type MaxVestingSchedules = MaxRewardVestingSchedules;
+	type ProviderScore = DapiProviderScore;
+	type ScoreRewardMultiplier = ScoreRewardMultiplier;
 	type PalletId = DapiStakingPot;
+	type ForceEraOrigin = EnsureRootOrHalfCouncil;
+	type SlashDestination = Treasury;
+	type ExpiredRewardDestination = Treasury;
 	type WeightInfo = pallet_dapi_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -513,6 +685,34 @@ impl OnUnbalanced<NegativeImbalance> for OnProjectPayment {
 
 parameter_types! {
 	pub const MaxBytesInChainId: u32 = 64;
+	pub const MaxRegulatorLogEntries: u32 = 64;
+	pub const MaxProviderChains: u32 = 8;
+	pub const MaxProjectChains: u32 = 8;
+	pub const MaxBatchChainIds: u32 = 64;
+	pub const MaxBatchProviders: u32 = 32;
+	pub const RatingCooldownEras: u32 = 7;
+	pub const MaxProviderNameLength: u32 = 64;
+	pub const MaxProviderEndpointLength: u32 = 256;
+	pub const MaxProviderDescriptionLength: u32 = 512;
+	pub const MaxChainDisplayNameLength: u32 = 64;
+	pub const MaxChainUrlLength: u32 = 256;
+	pub const MaxSuspendReasonLength: u32 = 256;
+	pub const ProjectTransferWindow: BlockNumber = DAYS;
+	pub const ProviderRegistrationTimeout: BlockNumber = 7 * DAYS;
+	pub const ProjectDepositPeriod: BlockNumber = 90 * DAYS;
+	pub const MinQuotaPrice: Balance = pallet_dapi::QUOTA_PRICE / 1_000;
+	pub const MaxQuotaPrice: Balance = pallet_dapi::QUOTA_PRICE * 1_000;
+	pub const RegulatorApprovalThreshold: Perbill = Perbill::from_percent(50);
+	pub const QuotaLowThreshold: Perbill = Perbill::from_percent(80);
+	pub const RegulatorProposalDuration: BlockNumber = 7 * DAYS;
+	pub const MaxRegulatorVoters: u32 = 256;
+	pub const MaxRegulatorsPerBatch: u32 = 32;
+	pub const OffenceSlashRatio: Perbill = Perbill::from_percent(10);
+	pub const GatewayMinStake: Balance = 90 * MBTL;
+	pub const NodeMinStake: Balance = 180 * MBTL;
+	pub const SlaEvaluationPeriod: u32 = 100;
+	pub const MinRegulatorBond: Balance = 1_000 * MBTL;
+	pub const DapiPalletId: PalletId = PalletId(*b"mbt/dapi");
 }
 
 impl pallet_dapi::Config for Runtime {
@@ -521,7 +721,37 @@ impl pallet_dapi::Config for Runtime {
 	type DapiStaking = DapiStaking;
 	type UpdateOrigin = EnsureRoot<AccountId>;
 	type MaxChainIdLength = MaxBytesInChainId;
+	type MaxLogEntries = MaxRegulatorLogEntries;
+	type MaxSupportedChains = MaxProviderChains;
+	type MaxChainsPerProject = MaxProjectChains;
+	type MaxBatchChainIds = MaxBatchChainIds;
+	type MaxBatchProviders = MaxBatchProviders;
+	type RatingCooldownEras = RatingCooldownEras;
+	type MaxNameLength = MaxProviderNameLength;
+	type MaxEndpointLength = MaxProviderEndpointLength;
+	type MaxDescriptionLength = MaxProviderDescriptionLength;
+	type MaxChainMetadataLength = MaxChainDisplayNameLength;
+	type MaxUrlLength = MaxChainUrlLength;
+	type MaxReasonLength = MaxSuspendReasonLength;
+	type TransferWindowBlocks = ProjectTransferWindow;
+	type RegistrationTimeout = ProviderRegistrationTimeout;
+	type ProjectDepositPeriod = ProjectDepositPeriod;
+	type QuotaOracle = pallet_dapi::StorageValueOracle<Runtime>;
+	type MinQuotaPrice = MinQuotaPrice;
+	type MaxQuotaPrice = MaxQuotaPrice;
+	type QuotaLowThreshold = QuotaLowThreshold;
+	type RegulatorApprovalThreshold = RegulatorApprovalThreshold;
+	type RegulatorProposalDuration = RegulatorProposalDuration;
+	type MaxRegulatorVoters = MaxRegulatorVoters;
+	type MaxRegulatorsPerBatch = MaxRegulatorsPerBatch;
+	type OffenceSlashRatio = OffenceSlashRatio;
+	type GatewayMinStake = GatewayMinStake;
+	type NodeMinStake = NodeMinStake;
+	type SlaEvaluationPeriod = SlaEvaluationPeriod;
+	type MinRegulatorBond = MinRegulatorBond;
+	type RegulatorSlashDestination = Treasury;
 	type MassbitId = MassbitId;
+	type PalletId = DapiPalletId;
 	type OnProjectPayment = OnProjectPayment;
 	type WeightInfo = pallet_dapi::weights::SubstrateWeight<Runtime>;
 }
@@ -547,6 +777,9 @@ construct_runtime!(
 		Dapi: pallet_dapi::{Pallet, Call, Storage, Config<T>, Event<T>},
 		DapiStaking: pallet_dapi_staking::{Pallet, Call, Storage, Event<T>},
 		BlockReward: pallet_block_reward::{Pallet, Call, Storage, Config, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
 	}
 );
 
@@ -593,7 +826,29 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
+
+/// Storage migrations executed on runtime upgrade.
+pub type Migrations = (
+	pallet_dapi_staking::migrations::PopulateDelegatorProviderCount<Runtime>,
+	pallet_dapi_staking::migrations::MigrateProviderEraInfoToV2<Runtime>,
+	pallet_dapi_staking::migrations::PopulateActiveProviders<Runtime>,
+	pallet_dapi_staking::migrations::PopulateProviderDelegators<Runtime>,
+	pallet_dapi_staking::migrations::MigrateProviderInfoToV5<Runtime>,
+	pallet_dapi_staking::migrations::MigrateDelegationInfoToV6<Runtime>,
+	pallet_dapi_staking::migrations::PopulateEraCountersToV7<Runtime>,
+	pallet_dapi_staking::migrations::MigrateProviderEraInfoToV8<Runtime>,
+	pallet_dapi_staking::migrations::PopulateEraProvidersToV9<Runtime>,
+	pallet_dapi::migrations::MigrateProjectsToV1<Runtime>,
+	pallet_dapi::migrations::MigrateProjectsToV2<Runtime>,
+	pallet_dapi::migrations::MigrateChainIdsToV3<Runtime>,
+	pallet_dapi::migrations::MigrateProvidersToV4<Runtime>,
+	pallet_dapi::migrations::MigrateProjectsToV5<Runtime>,
+	pallet_dapi::migrations::PopulateChainStakeToV6<Runtime>,
+	pallet_block_reward::migrations::MigrateDistributionConfigToV1<Runtime>,
+	pallet_block_reward::migrations::MigrateDistributionConfigToV2<Runtime>,
+);
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 
 impl_runtime_apis! {
@@ -727,6 +982,151 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_block_reward::runtime_api::BlockRewardApi<Block, Balance> for Runtime {
+		fn simulate_reward_distribution(
+			config: pallet_block_reward::DistributionConfig,
+		) -> pallet_block_reward::RewardSimulation<Balance> {
+			BlockReward::simulate_reward_distribution(&config)
+		}
+
+		fn config_at_block(block: BlockNumber) -> Option<pallet_block_reward::DistributionConfig> {
+			BlockReward::config_at_block(block)
+		}
+	}
+
+	impl pallet_validator_set::runtime_api::ValidatorSetApi<Block, AccountId> for Runtime {
+		fn validator_set_at_session(session: u32) -> Option<Vec<AccountId>> {
+			ValidatorSet::validator_set_at_session(session)
+		}
+	}
+
+	impl pallet_dapi::runtime_api::DapiApi<Block, MassbitId, Balance> for Runtime {
+		fn provider_chains(provider_id: MassbitId) -> Vec<Vec<u8>> {
+			Dapi::all_provider_chains(provider_id)
+		}
+
+		fn providers_by_chain(chain_id: Vec<u8>) -> Vec<MassbitId> {
+			Dapi::providers_by_chain(chain_id)
+		}
+
+		fn chain_id_exists(chain_id: Vec<u8>) -> bool {
+			Dapi::chain_id_exists(chain_id)
+		}
+
+		fn chain_metadata(
+			chain_id: Vec<u8>,
+		) -> Option<pallet_dapi::ChainMetadata<Vec<u8>, Vec<u8>>> {
+			Dapi::chain_metadata_of(chain_id)
+		}
+
+		fn provider_average_rating(provider_id: MassbitId) -> Option<u32> {
+			Dapi::provider_average_rating(provider_id)
+		}
+
+		fn estimate_deposit_for_quota(target_quota: u128) -> Balance {
+			Dapi::calculate_deposit(target_quota)
+		}
+
+		fn calculate_quota_for_deposit(deposit: Balance) -> u128 {
+			Dapi::calculate_quota(deposit)
+		}
+	}
+
+	impl
+		pallet_dapi_staking::runtime_api::DapiStakingApi<
+			Block,
+			AccountId,
+			MassbitId,
+			Balance,
+			BlockNumber,
+		> for Runtime
+	{
+		fn delegator_provider_count(who: AccountId) -> u32 {
+			DapiStaking::delegator_provider_count(who)
+		}
+
+		fn provider_total_rewards_claimed(provider_id: MassbitId) -> Balance {
+			DapiStaking::provider_total_rewards_claimed(provider_id)
+		}
+
+		fn delegator_total_rewards_claimed(who: AccountId, provider_id: MassbitId) -> Balance {
+			DapiStaking::delegator_total_rewards_claimed(who, provider_id)
+		}
+
+		fn remaining_blocks() -> BlockNumber {
+			DapiStaking::era().remaining_blocks(System::block_number())
+		}
+
+		fn progress_perbill() -> Perbill {
+			DapiStaking::era().progress_perbill(System::block_number())
+		}
+
+		fn delegators_of(provider_id: MassbitId) -> Vec<AccountId> {
+			DapiStaking::delegators_of(provider_id)
+		}
+
+		fn pending_rewards(delegator: AccountId, provider_id: MassbitId) -> Balance {
+			DapiStaking::pending_rewards(delegator, provider_id)
+		}
+
+		fn provider_pending_reward(
+			provider_id: MassbitId,
+			era: pallet_dapi_staking::types::EraIndex,
+		) -> Balance {
+			DapiStaking::provider_pending_reward(provider_id, era)
+		}
+
+		fn provider_tvl(provider_id: MassbitId) -> Balance {
+			DapiStaking::provider_tvl(provider_id)
+		}
+
+		fn total_tvl() -> Balance {
+			DapiStaking::total_tvl()
+		}
+
+		fn delegator_total_staked(delegator: AccountId) -> Balance {
+			DapiStaking::delegator_total_staked(&delegator)
+		}
+
+		fn claim_history(
+			delegator: AccountId,
+			provider_id: MassbitId,
+		) -> Vec<pallet_dapi_staking::types::ClaimRecord<Balance, BlockNumber>> {
+			DapiStaking::claim_history(delegator, provider_id).into_inner()
+		}
+
+		fn estimated_delegator_apr(provider_id: MassbitId) -> Perbill {
+			DapiStaking::estimated_delegator_apr(&provider_id, 365 * DAYS)
+		}
+
+		fn all_providers_apr() -> Vec<(MassbitId, Perbill)> {
+			DapiStaking::all_providers_apr(365 * DAYS)
+		}
+
+		fn era_active_providers(era: pallet_dapi_staking::types::EraIndex) -> u32 {
+			DapiStaking::era_active_provider_count(era)
+		}
+
+		fn total_delegators() -> u32 {
+			DapiStaking::delegator_count()
+		}
+
+		fn era_history(
+			from_era: pallet_dapi_staking::types::EraIndex,
+			to_era: pallet_dapi_staking::types::EraIndex,
+		) -> Vec<pallet_dapi_staking::types::EraHistoryEntry<Balance>> {
+			DapiStaking::era_history(from_era, to_era)
+		}
+
+		fn provider_history(
+			provider_id: MassbitId,
+			from_era: pallet_dapi_staking::types::EraIndex,
+			to_era: pallet_dapi_staking::types::EraIndex,
+		) -> Vec<pallet_dapi_staking::types::ProviderEraEntry<Balance>> {
+			DapiStaking::provider_history(provider_id, from_era, to_era)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -781,3 +1181,6 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo, sp_io::TestExternalities};
+use sp_runtime::traits::{BadOrigin, Hash};
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+
+fn new_test_ext() -> TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_collective::GenesisConfig::<Runtime, CouncilCollective> {
+		members: vec![ALICE, BOB, CHARLIE],
+		phantom: Default::default(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Proposes `call` to the council, collects `ayes` and closes the motion.
+fn council_approves(call: Call, ayes: &[AccountId]) {
+	let length_bound = call.encoded_size() as u32;
+	let weight_bound = call.get_dispatch_info().weight;
+	let proposal_hash = BlakeTwo256::hash_of(&call);
+	let threshold = ayes.len() as u32;
+
+	assert_ok!(Council::propose(Origin::signed(ALICE), threshold, Box::new(call), length_bound));
+	let index = Council::proposal_count() - 1;
+	for voter in ayes {
+		assert_ok!(Council::vote(Origin::signed(voter.clone()), proposal_hash, index, true));
+	}
+	assert_ok!(Council::close(
+		Origin::signed(ALICE),
+		proposal_hash,
+		index,
+		weight_bound,
+		length_bound
+	));
+}
+
+#[test]
+fn council_majority_can_update_validator_set() {
+	new_test_ext().execute_with(|| {
+		council_approves(
+			Call::ValidatorSet(pallet_validator_set::Call::set_desired_candidates { max: 42 }),
+			&[ALICE, BOB],
+		);
+		assert_eq!(ValidatorSet::desired_candidates(), 42);
+
+		council_approves(
+			Call::ValidatorSet(pallet_validator_set::Call::set_candidacy_bond { bond: 7 * MBTL }),
+			&[ALICE, BOB, CHARLIE],
+		);
+		assert_eq!(ValidatorSet::candidacy_bond(), 7 * MBTL);
+	});
+}
+
+#[test]
+fn council_minority_cannot_update_validator_set() {
+	new_test_ext().execute_with(|| {
+		// With a threshold of one the motion is executed right away, as 1/3 of the council.
+		let call =
+			Call::ValidatorSet(pallet_validator_set::Call::set_desired_candidates { max: 42 });
+		let length_bound = call.encoded_size() as u32;
+		assert_ok!(Council::propose(Origin::signed(ALICE), 1, Box::new(call), length_bound));
+		assert_eq!(ValidatorSet::desired_candidates(), 0);
+
+		assert_noop!(ValidatorSet::set_desired_candidates(Origin::signed(ALICE), 42), BadOrigin);
+		assert_ok!(ValidatorSet::set_desired_candidates(Origin::root(), 42));
+		assert_eq!(ValidatorSet::desired_candidates(), 42);
+	});
+}